@@ -0,0 +1,37 @@
+use essay_plot::{
+    artist::{ArrowStyle, BoxStyle, ConnectionStyle, TextCoords},
+    prelude::*
+};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+    let mut chart = figure.chart();
+
+    let x = linspace(0., 6.28, 100);
+    let y = x.sin();
+
+    chart.title("annotate");
+    chart.plot(&x, &y);
+
+    chart.annotate("max", [1.57, 1.], [3., 1.5]);
+
+    chart.annotate("min", [4.71, -1.], [-40., 20.])
+        .text_coords(TextCoords::OffsetPoints)
+        .arrow(ArrowStyle::Simple)
+        .arrow_color("red");
+
+    chart.annotate("zero", [3.14, 0.], [0.2, 0.2])
+        .text_coords(TextCoords::FrameFraction)
+        .connection(ConnectionStyle::Arc(0.3))
+        .bbox(BoxStyle::Round)
+        .bbox_edge_color("teal");
+
+    chart.annotate("start", [0., 0.], [0.1, 0.9])
+        .text_coords(TextCoords::FigureFraction)
+        .connection(ConnectionStyle::Angle)
+        .arrow(ArrowStyle::Line)
+        .bbox(BoxStyle::Square);
+
+    figure.show();
+}
@@ -0,0 +1,33 @@
+use essay_plot::{artist::Orientation, prelude::*};
+use essay_tensor::ten;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("Grouped");
+
+            chart.bar_grouped(ten![[20., 34., 30., 35.], [25., 32., 34., 20.], [18., 24., 28., 31.]])
+                .categories(&["Q1", "Q2", "Q3", "Q4"])
+                .labels(&["North", "South", "West"])
+                .bar_label(true);
+
+            let mut chart = ui.chart();
+            chart.title("Stacked");
+
+            chart.bar_stacked(ten![[3., 5., 2.], [4., 1., 3.]])
+                .categories(&["apples", "pears", "plums"])
+                .labels(&["2023", "2024"])
+                .orientation(Orientation::Horizontal);
+
+            let mut chart = ui.chart();
+            chart.title("Horizontal");
+
+            chart.barh([3., -1., 2.]).bar_label(true);
+        });
+    });
+
+    figure.show();
+}
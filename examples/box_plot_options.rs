@@ -0,0 +1,33 @@
+use essay_plot::prelude::*;
+use essay_tensor::tensor::Tensor;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    let a = Tensor::random_normal([50], None);
+    let b = Tensor::random_normal([50], None) * 2.;
+    let c = Tensor::random_normal([50], None) + 1.;
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("Notched Box Plot");
+
+            chart.box_plot([&a, &b, &c])
+                .notch(true)
+                .show_means(true)
+                .colors(&["pink", "lightblue", "lightgreen"]);
+
+            let mut chart = ui.chart();
+            chart.title("Horizontal Box Plot");
+
+            chart.box_plot([&a, &b, &c])
+                .vert(false)
+                .positions([1., 2., 4.])
+                .widths([0.3, 0.5, 0.8])
+                .whisker((5., 95.));
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,35 @@
+use essay_plot::prelude::*;
+use essay_tensor::ten;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("Fruit");
+
+            chart.bar_xy(["apples", "oranges", "lemons"], [10., 15., 5.]);
+            // new categories are added after the existing ones
+            chart.scatter(["oranges", "limes"], [12., 8.]);
+
+            let mut chart = ui.chart();
+            chart.title("Weekdays");
+
+            chart.plot(["Mon", "Tue", "Wed", "Thu", "Fri"], [3., 4., 2., 5., 6.]);
+
+            let tue = ten![3.5, 4., 4.5, 5.];
+            let thu = ten![4., 5., 5.5, 6.];
+            chart.box_plot_xy(["Tue", "Thu"], [&tue, &thu]);
+
+            let mut chart = ui.chart();
+            chart.title("Sizes");
+
+            // horizontal bars and boxes put their categories on the y axis
+            chart.barh_xy(["small", "medium", "large"], [2., 5., 9.]);
+            chart.box_ploth_xy(["medium", "large"], [&tue, &thu]);
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,30 @@
+use essay_plot::prelude::*;
+use essay_tensor::init::{arange, meshgrid};
+
+fn main() {
+    // non-uniform spacing, denser near zero
+    let x = arange(0., 2., 0.05).powi(2);
+    let y = arange(0., 2., 0.1).powi(2) * 0.5;
+    let [xx, yy] = meshgrid([x.clone(), y.clone()]);
+
+    let z = (-(xx.clone() - 1.).powi(2) - (yy.clone() - 1.).powi(2)).exp();
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("contourf_xy");
+            chart.contourf_xy(&x, &y, &z);
+            chart.contour_xy(&xx, &yy, &z)
+                .clabel(true);
+
+            let mut chart = ui.chart();
+            chart.title("grid_color_xy");
+            chart.grid_color_xy(&x, &y, &z);
+            chart.colorbar();
+        });
+    });
+
+    figure.show();
+}
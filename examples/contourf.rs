@@ -0,0 +1,34 @@
+use essay_plot::{artist::Extend, prelude::*};
+use essay_tensor::init::{arange, meshgrid};
+
+fn main() {
+    let delta = 0.025;
+    let x = arange(-3., 3., delta);
+    let y = arange(-2., 2., delta);
+    let [x, y] = meshgrid([x, y]);
+
+    let z1 = (-x.powi(2) - y.powi(2)).exp();
+    let z2 = (-(x.clone() - 1.).powi(2) - (y.clone() - 1.).powi(2)).exp();
+    let z = (z1 - z2) * 2.;
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("contourf");
+            chart.contourf(&z)
+                .levels(&[-1., -0.5, 0., 0.5, 1.])
+                .extend(Extend::Both);
+            chart.colorbar();
+
+            let mut chart = ui.chart();
+            chart.title("contour labels");
+            chart.contour(&z)
+                .n_levels(9)
+                .clabel(true);
+        });
+    });
+
+    figure.show();
+}
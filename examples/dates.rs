@@ -0,0 +1,36 @@
+use essay_plot::{chart::{AutoDateLocator, DateFormatter, DateTime}, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.vertical(|ui| {
+            // daily values over half a year, as epoch seconds
+            let start = DateTime::new(2026, 1, 1).to_epoch() as f32;
+            let end = DateTime::new(2026, 7, 1).to_epoch() as f32;
+
+            let t = linspace(start, end, 180);
+            let y = linspace(0., 12., 180).sin();
+
+            let mut chart = ui.chart();
+            chart.plot(&t, &y);
+            chart.x().dates();
+            chart.title("Concise dates");
+
+            // minutes after an explicit epoch keep full precision
+            let epoch = DateTime::new(2026, 3, 1).hms(9, 0, 0.).to_epoch();
+
+            let t = linspace(0., 4. * 3600., 240);
+            let y = linspace(0., 6., 240).cos();
+
+            let mut chart = ui.chart();
+            chart.plot(&t, &y);
+            chart.x().locator(AutoDateLocator::new().epoch(epoch));
+            chart.x().formatter(DateFormatter::new("%H:%M").epoch(epoch));
+            chart.title("Time of day");
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,28 @@
+use essay_plot::prelude::*;
+use essay_tensor::{init::linspace, tensor::Tensor};
+
+fn main() {
+    let mut figure = Figure::new();
+    let mut chart = figure.chart();
+
+    let x = linspace(0.5, 4.5, 9);
+    let y = (&x * 0.8).sin();
+
+    chart.errorbar(&x, &y)
+        .yerr(0.2)
+        .cap_size(4.)
+        .marker("o")
+        .label("symmetric");
+
+    // asymmetric lower and upper errors
+    let lower = Tensor::from(vec![0.1; 9]);
+    let upper = &lower * 3.;
+
+    chart.errorbar(&x, &(&y + 1.5))
+        .yerr(lower.stack([upper], 0))
+        .xerr(0.1)
+        .cap_size(4.)
+        .label("asymmetric");
+
+    figure.show();
+}
@@ -0,0 +1,58 @@
+use essay_plot::{
+    chart::{FixedFormatter, Formatter, FuncFormatter},
+    prelude::*
+};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                // large values near each other use an offset
+                let x = linspace(100000., 100001., 20);
+                let y = linspace(0., 6., 20).sin();
+
+                let mut chart = ui.chart();
+                chart.plot(&x, &y);
+                chart.title("Offset");
+
+                // small values use a shared exponent
+                let x = linspace(0., 1e-6, 20);
+
+                let mut chart = ui.chart();
+                chart.plot(&x, &y);
+                chart.title("Scientific");
+
+                let mut chart = ui.chart();
+                chart.plot(&linspace(0., 1., 20), &y);
+                chart.x().formatter(Formatter::Percent { xmax: 1. });
+                chart.title("Percent");
+            });
+
+            ui.horizontal(|ui| {
+                let y = linspace(0., 6., 20).cos();
+
+                let mut chart = ui.chart();
+                chart.plot(&linspace(0., 20000., 20), &y);
+                chart.x().formatter(Formatter::Engineering { unit: "Hz".into() });
+                chart.title("Engineering");
+
+                let mut chart = ui.chart();
+                chart.plot(&linspace(0., 10., 20), &y);
+                chart.x().formatter(Formatter::StrMethod("{x:.1f} m".into()));
+                chart.y().formatter(FuncFormatter::new(|v, _| format!("${:.2}", v)));
+                chart.title("Format string and closure");
+
+                let mut chart = ui.chart();
+                chart.plot(&linspace(0., 3., 20), &y);
+                chart.x().ticks(&[0., 1., 2., 3.]);
+                chart.x().formatter(FixedFormatter::new(&["a", "b", "c", "d"]));
+                chart.title("Fixed");
+            });
+        });
+    });
+
+    figure.show();
+}
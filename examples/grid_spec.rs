@@ -0,0 +1,26 @@
+use essay_plot::prelude::*;
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.grid_spec(2, 3, |grid| {
+        grid.height_ratios(&[2., 1.]).wspace(0.3);
+
+        let t = linspace(0., 10., 200);
+
+        // top chart spans all columns
+        let mut top = grid.chart(0, ..);
+        top.plot(&t, &t.sin());
+        top.title("Overview");
+
+        let mut left = grid.chart(1, 0);
+        left.plot(&t, &t.cos());
+
+        // bottom right chart spans two columns
+        let mut right = grid.chart(1, 1..3);
+        right.plot(&t, &(&t * 0.5).sin());
+    });
+
+    figure.show();
+}
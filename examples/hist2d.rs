@@ -0,0 +1,28 @@
+use essay_plot::{artist::Norms, prelude::*};
+use essay_tensor::tensor::Tensor;
+
+fn main() {
+    let x = Tensor::random_normal([100_000], None);
+    let y = Tensor::random_normal([100_000], None) * 0.5 + &x * 0.8;
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("hist2d");
+            chart.hist2d(&x, &y)
+                .n_bins(40, 40)
+                .norm(Norms::Log10);
+            chart.colorbar();
+
+            let mut chart = ui.chart();
+            chart.title("hexbin");
+            chart.hexbin(&x, &y, 30)
+                .min_count(2);
+            chart.colorbar();
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,47 @@
+use essay_plot::{artist::{HistType, Orientation}, prelude::*};
+use essay_tensor::tensor::Tensor;
+
+fn main() {
+    let a = Tensor::random_normal([500], None);
+    let b = Tensor::random_normal([300], None) * 0.5 + 1.;
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                let mut chart = ui.chart();
+                chart.title("Stacked");
+                chart.hist_list([&a, &b])
+                    .n_bins(20)
+                    .stacked(true);
+
+                let mut chart = ui.chart();
+                chart.title("Density Step");
+                chart.hist(&a)
+                    .bins([-3., -2., -1., -0.5, 0., 0.5, 1., 2., 3.])
+                    .density(true)
+                    .hist_type(HistType::Step);
+            });
+
+            ui.horizontal(|ui| {
+                let mut chart = ui.chart();
+                chart.title("Cumulative");
+                chart.hist_list([&a, &b])
+                    .range(-3., 3.)
+                    .n_bins(30)
+                    .density(true)
+                    .cumulative(true)
+                    .hist_type(HistType::StepFilled);
+
+                let mut chart = ui.chart();
+                chart.title("Horizontal");
+                chart.hist(&b)
+                    .weights(Tensor::ones([300]) * 0.1)
+                    .orientation(Orientation::Horizontal);
+            });
+        });
+    });
+
+    figure.show();
+}
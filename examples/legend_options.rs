@@ -0,0 +1,36 @@
+use essay_plot::{chart::{LegendLoc, Side}, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let x = linspace(0., 6.28, 40);
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("best, two columns");
+            chart.plot(&x, &x.sin()).label("sin");
+            chart.plot(&x, &x.cos()).label("cos");
+            chart.plot(&x, &(x.sin() * 0.5)).label("sin / 2");
+            chart.legend()
+                .loc(LegendLoc::Best)
+                .n_cols(2)
+                .font_size(8.);
+
+            let mut chart = ui.chart();
+            chart.title("outside, ordered");
+            chart.plot(&x, &x.sin()).label("sin");
+            chart.plot(&x, &x.cos()).label("cos");
+            chart.scatter(&x, &(x.cos() * x.sin())).label("points");
+            chart.legend()
+                .loc(LegendLoc::Outside(Side::Right))
+                .title("Series")
+                .entries(&["points", "sin"])
+                .marker_scale(0.5)
+                .frame(false);
+        });
+    });
+
+    figure.show();
+}
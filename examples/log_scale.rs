@@ -0,0 +1,36 @@
+use essay_plot::{chart::{Scale, ShowGrid}, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    let x = linspace(0.1, 4., 40);
+    let y = x.exp();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+
+            chart.plot(&x, &y);
+            chart.y().scale(Scale::Log10);
+            chart.y().show_grid(ShowGrid::Both);
+            chart.title("Log10");
+
+            let mut chart = ui.chart();
+
+            let y2 = (&x - 2.) * 100.;
+            chart.plot(&x, &y2);
+            chart.y().scale(Scale::SymLog { linthresh: 1. });
+            chart.title("SymLog");
+
+            let mut chart = ui.chart();
+
+            let p = linspace(0.001, 0.999, 100);
+            chart.plot(&p, &p);
+            chart.x().scale(Scale::Logit);
+            chart.title("Logit");
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,33 @@
+use essay_plot::{chart::Scale, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    let w = linspace(0.1, 100., 200);
+    let gain = (&w * -0.05).exp();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+
+            chart.title("$G(\\omega) = e^{-\\omega / 20}$, $\\frac{1}{\\sqrt{2}}$");
+            chart.plot(&w, &gain).label("$\\alpha_i^2$");
+            chart.x().scale(Scale::Log10);
+            chart.y().scale(Scale::Log10);
+            chart.x_label("$\\omega$ (rad/s)");
+            chart.y_label("$\\mathbf{G}_{\\mathrm{dB}}$");
+            chart.legend();
+
+            let mut chart = ui.chart();
+
+            let x = linspace(0., 6.28, 100);
+            chart.plot(&x, &x.sin());
+            chart.title("$\\sin\\theta \\approx \\theta - \\theta^3/6$");
+            chart.x_label("$\\theta$ in $[0, 2\\pi]$");
+            chart.text([3.14, 0.5], "$\\mathbb{R}^n \\to \\mathbb{R}$");
+        });
+    });
+
+    figure.show();
+}
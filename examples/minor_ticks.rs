@@ -0,0 +1,33 @@
+use essay_plot::{
+    chart::{AutoMinorLocator, MultipleLocator, ShowGrid},
+    prelude::*
+};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    let x = linspace(0., 10., 100);
+    let y = x.sin();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            // minor grid lines subdivide the major ticks
+            let mut chart = ui.chart();
+            chart.plot(&x, &y);
+            chart.x().show_grid(ShowGrid::Both);
+            chart.y().show_grid(ShowGrid::Both);
+            chart.title("Minor grid");
+
+            // explicit major and minor locators
+            let mut chart = ui.chart();
+            chart.plot(&x, &y);
+            chart.x().locator(MultipleLocator::new(2.5));
+            chart.x().minor().locator(AutoMinorLocator::new(Some(5)));
+            chart.y().minor().locator(MultipleLocator::new(0.1));
+            chart.title("Minor ticks");
+        });
+    });
+
+    figure.show();
+}
@@ -30,7 +30,7 @@ fn main_plot() {
     chart.artist(patch).color("red").edge_color("black");
     // graph.artist()
 
-    figure.save("../test.png", 144.);
+    figure.save("../test.png", 144.).unwrap();
     figure.show();
 }
 
@@ -48,7 +48,7 @@ fn main_grid() {
     grid_color(&mut graph1, &z).shading(Shading::Flat);
     grid_color(&mut graph2, &z).shading(Shading::Gouraud);
 
-    figure.save("../test.png", 144.);
+    figure.save("../test.png", 144.).unwrap();
 }
 
 fn main_plot2() {
@@ -129,6 +129,6 @@ fn main_plot2() {
     //graph3.title("Reward");
     //graph4.title("Reward");
 
-    figure.save("../test.png", 144.);
+    figure.save("../test.png", 144.).unwrap();
     figure.show();
 }
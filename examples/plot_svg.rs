@@ -0,0 +1,24 @@
+use std::f32::consts::TAU;
+
+use essay_plot::{artist::Patch, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+    let mut chart = figure.chart();
+
+    let x = linspace(0., TAU, 40);
+    let y = x.sin();
+
+    chart.title("SVG Output");
+
+    chart.plot(&x, &y);
+    chart.scatter(&x, &x.cos());
+
+    let patch = Patch::rect([0.5, 0.5], [2., 0.5]);
+    chart.artist(patch).color("teal").edge_color("black");
+
+    figure.save("../test.svg", 144.).unwrap();
+    figure.save("../test.pdf", 144.).unwrap();
+    figure.save("../test.eps", 144.).unwrap();
+}
@@ -17,5 +17,5 @@ fn main() {
     graph.plot(&x, &y);
 
     // figure.show();
-    figure.save("test.png", 200.);
+    figure.save("test.png", 200.).unwrap();
 }
@@ -0,0 +1,37 @@
+use essay_plot::prelude::*;
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                // stacked charts with a common time axis
+                let t = linspace(0., 10., 200);
+
+                let mut top = ui.chart();
+                top.plot(&t, &t.sin());
+                top.title("Shared x");
+
+                let mut bottom = ui.chart_sharing_x(&top);
+                bottom.plot(&(&t * 2.), &(&t * 2.).cos());
+                bottom.x_label("time");
+            });
+
+            ui.horizontal(|ui| {
+                // side by side charts with a common y range
+                let x = linspace(0., 1., 50);
+
+                let mut left = ui.chart();
+                left.plot(&x, &(&x * 3.));
+                left.title("Shared y");
+
+                let mut right = ui.chart_sharing_y(&left);
+                right.plot(&x, &(&x * &x));
+            });
+        });
+    });
+
+    figure.show();
+}
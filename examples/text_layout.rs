@@ -0,0 +1,37 @@
+use essay_plot::{
+    artist::{BoxStyle, RotationMode},
+    prelude::*
+};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+    let mut chart = figure.chart();
+
+    let x = linspace(0., 6.28, 100);
+    let y = x.sin();
+
+    chart.title("multi-line\ntitle").line_spacing(1.4);
+    chart.plot(&x, &y);
+
+    chart.text([1., -0.5], "left aligned\nsecond line\n$x^2 + y^2$")
+        .halign(HorizAlign::Left)
+        .bbox(BoxStyle::Round)
+        .bbox_face_color("lightyellow");
+
+    chart.text([4., 0.5], "default mode")
+        .rotation(Angle::Unit(0.125))
+        .halign(HorizAlign::Left)
+        .valign(VertAlign::Bottom)
+        .bbox(BoxStyle::Square);
+
+    chart.text([4., -0.5], "anchor mode")
+        .rotation(Angle::Unit(0.125))
+        .rotation_mode(RotationMode::Anchor)
+        .halign(HorizAlign::Left)
+        .valign(VertAlign::Bottom)
+        .bbox(BoxStyle::Square)
+        .bbox_pad(2.);
+
+    figure.show();
+}
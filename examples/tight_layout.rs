@@ -0,0 +1,32 @@
+use essay_plot::{chart::Scale, prelude::*};
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.grid_spec(2, 2, |grid| {
+        let t = linspace(0., 10., 200);
+
+        let mut a = grid.chart(0, 0);
+        a.plot(&t, &(t.sin() * 123456.));
+        a.title("wide tick labels");
+        a.y_label("amplitude");
+
+        let mut b = grid.chart(0, 1);
+        b.plot(&t, &t.cos());
+        b.title("multi-line\ntitle");
+
+        let mut c = grid.chart(1, 0);
+        c.plot(&t, &(&t * 0.5).sin());
+        c.x_label("time (s)");
+
+        let mut d = grid.chart(1, 1);
+        d.plot(&t, &t.exp());
+        d.y().scale(Scale::Log10);
+        d.x_label("time (s)");
+    });
+
+    figure.tight_layout(true);
+
+    figure.show();
+}
@@ -0,0 +1,53 @@
+use essay_plot::{plot, prelude::*, tri::triangulate};
+use essay_tensor::tensor::Tensor;
+
+fn main() {
+    // annulus of points, with the hole masked out
+    let (n_angles, n_radii, min_radius) = (36, 8, 0.25);
+
+    let mut xy = Vec::<[f32; 2]>::new();
+    let mut z = Vec::<f32>::new();
+
+    for i in 0..n_radii {
+        let r = min_radius + (0.95 - min_radius) * i as f32 / (n_radii - 1) as f32;
+
+        for j in 0..n_angles {
+            let theta = 2. * std::f32::consts::PI * (j as f32 + 0.5 * i as f32) / n_angles as f32;
+
+            xy.push([r * theta.cos(), r * theta.sin()]);
+            z.push(r.cos() * (3. * theta).cos());
+        }
+    }
+
+    let mut tri = triangulate(&Tensor::from(xy.clone()));
+
+    let mask: Vec<bool> = tri.triangles().iter_row().map(|t| {
+        let x = (xy[t[0]][0] + xy[t[1]][0] + xy[t[2]][0]) / 3.;
+        let y = (xy[t[0]][1] + xy[t[1]][1] + xy[t[2]][1]) / 3.;
+
+        x.hypot(y) < min_radius
+    }).collect();
+
+    tri.set_mask(Tensor::from(mask));
+
+    let z = Tensor::from(z);
+
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("tricontourf");
+            chart.tricontourf(tri.clone(), &z);
+            chart.tricontour(tri.clone(), &z);
+            chart.colorbar();
+
+            let mut chart = ui.chart();
+            chart.title("tripcolor");
+            chart.tripcolor(tri.clone(), &z);
+            plot::triplot(&mut chart, tri.clone());
+        });
+    });
+
+    figure.show();
+}
@@ -0,0 +1,33 @@
+use essay_plot::prelude::*;
+use essay_tensor::init::linspace;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            // two quantities with different units against the same time
+            let t = linspace(0.01, 10., 100);
+
+            let mut chart = ui.chart();
+            chart.plot(&t, &t.exp());
+            chart.x_label("time (s)");
+            chart.y_label("exp");
+
+            let mut twin = chart.twin_x();
+            twin.plot(&t, &(&t * 6.).sin()).color("red");
+            twin.y_label("sin");
+
+            // frequency on the bottom, period on top
+            let f = linspace(1., 100., 100);
+
+            let mut chart = ui.chart();
+            chart.plot(&f, &(&f * 0.2).cos());
+            chart.x_label("frequency (Hz)");
+            chart.secondary_x_axis(|f| 1. / f, |p| 1. / p);
+            chart.secondary_x_label("period (s)");
+        });
+    });
+
+    figure.show();
+}
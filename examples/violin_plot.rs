@@ -0,0 +1,31 @@
+use essay_plot::{plot::{Bandwidth, ViolinInner}, prelude::*};
+use essay_tensor::tensor::Tensor;
+
+fn main() {
+    let mut figure = Figure::new();
+
+    let a = Tensor::random_normal([200], None);
+    let b = Tensor::random_normal([200], None) * 0.5 + 1.;
+    let c = Tensor::random_normal([200], None) * 2.;
+
+    figure.multichart(|ui| {
+        ui.horizontal(|ui| {
+            let mut chart = ui.chart();
+            chart.title("Violin");
+            chart.violin_plot([&a, &b, &c]);
+
+            let mut chart = ui.chart();
+            chart.title("Split, quartiles");
+            chart.violin_plot([&a, &b, &b, &c])
+                .split(true)
+                .inner(ViolinInner::Quartiles)
+                .bandwidth(Bandwidth::Silverman);
+
+            let mut chart = ui.chart();
+            chart.title("Horizontal");
+            chart.violin_plot([&a, &c]).vert(false);
+        });
+    });
+
+    figure.show();
+}
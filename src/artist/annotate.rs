@@ -0,0 +1,457 @@
+use essay_graphics::api::{
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Color, HorizAlign, Path, PathCode, PathOpt, Point, TextStyle, VertAlign
+};
+
+use crate::{
+    chart::{Data, LegendHandler},
+    config::{ConfigArc, PathStyle},
+    data_artist_option_struct, path_style_options, text_bbox_options,
+    transform::ToCanvas
+};
+
+use super::{
+    patch::Arrow,
+    text_layout::{RotationMode, TextBBox, TextLayout},
+    Artist, ArtistDraw, ArtistView, TextCoords
+};
+
+///
+/// Shape of the leader from an annotation's text to its point.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArrowStyle {
+    /// No leader, only the text.
+    None,
+    /// A plain line.
+    Line,
+    /// A line with a filled head at the point.
+    Head,
+    /// A filled arrow tapering from a narrow tail to the head, for a
+    /// straight connection. Curved connections use `Head`.
+    Simple,
+}
+
+///
+/// Route of the leader between the text and the point.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionStyle {
+    Straight,
+    /// Quadratic curve bowed to the side by the fraction `rad` of the
+    /// leader's length. Positive values bend counterclockwise.
+    Arc(f32),
+    /// Horizontal from the text, then vertical to the point.
+    Angle,
+}
+
+impl ConnectionStyle {
+    ///
+    /// Bezier control point for an arc or the corner for an angle.
+    ///
+    fn control(&self, start: Point, end: Point) -> Option<Point> {
+        match self {
+            ConnectionStyle::Straight => None,
+            ConnectionStyle::Arc(rad) => {
+                let (dx, dy) = (end.x() - start.x(), end.y() - start.y());
+                let (mx, my) = (0.5 * (start.x() + end.x()), 0.5 * (start.y() + end.y()));
+
+                Some(Point(mx - rad * dy, my + rad * dx))
+            }
+            ConnectionStyle::Angle => Some(Point(end.x(), start.y())),
+        }
+    }
+
+    fn to_path(&self, start: Point, control: Option<Point>, end: Point) -> Path<Canvas> {
+        let mut codes = vec![PathCode::MoveTo(start)];
+
+        match (self, control) {
+            (ConnectionStyle::Arc(_), Some(control)) => {
+                codes.push(PathCode::Bezier2(control, end));
+            }
+            (ConnectionStyle::Angle, Some(corner)) => {
+                codes.push(PathCode::LineTo(corner));
+                codes.push(PathCode::LineTo(end));
+            }
+            _ => {
+                codes.push(PathCode::LineTo(end));
+            }
+        }
+
+        Path::new(codes)
+    }
+}
+
+///
+/// Text labeling a point, with an optional leader from the text to the
+/// point. The point and the text each have their own coordinate system.
+///
+pub struct Annotation {
+    text: String,
+
+    xy: Point,
+    xy_coords: TextCoords,
+    xy_text: Point,
+    text_coords: TextCoords,
+
+    arrow: ArrowStyle,
+    connection: ConnectionStyle,
+    head_length: f32,
+    head_width: f32,
+    shrink: f32,
+    arrow_style: PathStyle,
+
+    bbox: TextBBox,
+
+    path_style: PathStyle,
+    text_style: TextStyle,
+}
+
+impl Annotation {
+    pub fn new(
+        text: impl AsRef<str>,
+        xy: impl Into<Point>,
+        xy_text: impl Into<Point>,
+    ) -> Self {
+        let mut text_style = TextStyle::new();
+        text_style.halign(HorizAlign::Center);
+        text_style.valign(VertAlign::Center);
+
+        Self {
+            text: text.as_ref().to_string(),
+
+            xy: xy.into(),
+            xy_coords: TextCoords::Data,
+            xy_text: xy_text.into(),
+            text_coords: TextCoords::Data,
+
+            arrow: ArrowStyle::Head,
+            connection: ConnectionStyle::Straight,
+            head_length: 8.,
+            head_width: 6.,
+            shrink: 2.,
+            arrow_style: PathStyle::new(),
+
+            bbox: TextBBox::new(),
+
+            path_style: PathStyle::new(),
+            text_style,
+        }
+    }
+
+    fn draw_arrow(
+        &self,
+        renderer: &mut dyn Renderer,
+        text_box: &Bounds<Canvas>,
+        xy: Point,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let center = Point(text_box.xmid(), text_box.ymid());
+
+        let toward = self.connection.control(center, xy).unwrap_or(xy);
+        let start = clip_to_box(center, toward, text_box);
+
+        let control = self.connection.control(start, xy);
+        let end = shrink_toward(xy, control.unwrap_or(start), renderer.to_px(self.shrink));
+
+        // no leader when the point is under the text
+        if distance(start, end) <= 0. || is_inside(text_box, end) {
+            return Ok(());
+        }
+
+        let style = self.arrow_style.push(style);
+
+        let head_length = renderer.to_px(self.head_length);
+        let head_width = renderer.to_px(self.head_width);
+
+        let from = control.unwrap_or(start);
+
+        match self.arrow {
+            ArrowStyle::None => Ok(()),
+            ArrowStyle::Line => {
+                renderer.draw_path(&self.connection.to_path(start, control, end), &style)
+            }
+            ArrowStyle::Simple if control.is_none() => {
+                let length = distance(start, end);
+
+                let arrow = Arrow::new(start, Point(end.x() - start.x(), end.y() - start.y()))
+                    .width(head_width)
+                    .head_width(1.)
+                    .tail_width(0.3)
+                    .head_length((head_length / length).min(1.));
+
+                renderer.draw_path(&arrow.arrow_path::<Canvas>(), &style)
+            }
+            ArrowStyle::Head | ArrowStyle::Simple => {
+                let length = distance(from, end).min(head_length);
+                let base = shrink_toward(end, from, length);
+
+                if distance(start, base) > 0. {
+                    let line = self.connection.to_path(start, control, base);
+                    renderer.draw_path(&line, &style)?;
+                }
+
+                if distance(base, end) <= 0. {
+                    return Ok(());
+                }
+
+                let head = Arrow::new(base, Point(end.x() - base.x(), end.y() - base.y()))
+                    .width(head_width)
+                    .head_width(1.)
+                    .tail_width(0.)
+                    .head_length(1.);
+
+                renderer.draw_path(&head.arrow_path::<Canvas>(), &style)
+            }
+        }
+    }
+}
+
+impl ArtistDraw<Data> for Annotation {
+    fn bounds(&mut self) -> Bounds<Data> {
+        Bounds::none()
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let xy = self.xy_coords.to_canvas(self.xy, None, to_canvas, renderer);
+        let pos = self.text_coords.to_canvas(self.xy_text, Some(xy), to_canvas, renderer);
+
+        let layout = TextLayout::new(
+            renderer,
+            &self.text,
+            &self.text_style,
+            TextLayout::LINE_SPACING
+        );
+
+        let text_box = layout.extent(
+            renderer,
+            pos,
+            0.,
+            RotationMode::Anchor,
+            &self.text_style,
+            &self.bbox,
+        );
+
+        if self.arrow != ArrowStyle::None {
+            self.draw_arrow(renderer, &text_box, xy, style)?;
+        }
+
+        if self.text.len() > 0 || self.bbox.is_visible() {
+            layout.draw(
+                renderer,
+                pos,
+                0.,
+                RotationMode::Anchor,
+                &self.path_style.push(style),
+                &self.text_style,
+                &self.bbox,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Artist<Data> for Annotation {
+    type Opt = AnnotationOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.arrow_style = PathStyle::from_config(cfg, "annotate");
+        self.bbox = TextBBox::from_config(cfg, "annotate.bbox");
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, Annotation>) -> Self::Opt {
+        AnnotationOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+}
+
+data_artist_option_struct!(AnnotationOpt, Annotation);
+
+impl AnnotationOpt {
+    path_style_options!(path_style);
+    text_bbox_options!(bbox);
+
+    pub fn text(&mut self, text: impl AsRef<str>) -> &mut Self {
+        self.write(|artist| {
+            artist.text = text.as_ref().to_string();
+        });
+
+        self
+    }
+
+    ///
+    /// Coordinate system of the annotated point.
+    ///
+    pub fn xy_coords(&mut self, coords: TextCoords) -> &mut Self {
+        self.write(|artist| {
+            artist.xy_coords = coords;
+        });
+
+        self
+    }
+
+    ///
+    /// Coordinate system of the text. `OffsetPoints` is relative to the
+    /// annotated point.
+    ///
+    pub fn text_coords(&mut self, coords: TextCoords) -> &mut Self {
+        self.write(|artist| {
+            artist.text_coords = coords;
+        });
+
+        self
+    }
+
+    pub fn arrow(&mut self, arrow: ArrowStyle) -> &mut Self {
+        self.write(|artist| {
+            artist.arrow = arrow;
+        });
+
+        self
+    }
+
+    pub fn connection(&mut self, connection: ConnectionStyle) -> &mut Self {
+        self.write(|artist| {
+            artist.connection = connection;
+        });
+
+        self
+    }
+
+    ///
+    /// Arrow head length and width in points.
+    ///
+    pub fn head_size(&mut self, length: f32, width: f32) -> &mut Self {
+        assert!(length >= 0. && width >= 0.);
+
+        self.write(|artist| {
+            artist.head_length = length;
+            artist.head_width = width;
+        });
+
+        self
+    }
+
+    ///
+    /// Gap in points between the arrow tip and the annotated point.
+    ///
+    pub fn shrink(&mut self, shrink: f32) -> &mut Self {
+        assert!(shrink >= 0.);
+
+        self.write(|artist| {
+            artist.shrink = shrink;
+        });
+
+        self
+    }
+
+    pub fn arrow_color(&mut self, color: impl Into<Color>) -> &mut Self {
+        self.write(|artist| {
+            artist.arrow_style.color(color);
+        });
+
+        self
+    }
+
+    pub fn arrow_width(&mut self, width: f32) -> &mut Self {
+        self.write(|artist| {
+            artist.arrow_style.line_width(width);
+        });
+
+        self
+    }
+
+    pub fn size(&mut self, size: f32) -> &mut Self {
+        self.write(|artist| {
+            artist.text_style.size(size);
+        });
+
+        self
+    }
+}
+
+///
+/// Point where the ray from the box center toward `toward` leaves the box.
+///
+fn clip_to_box(center: Point, toward: Point, bounds: &Bounds<Canvas>) -> Point {
+    let (dx, dy) = (toward.x() - center.x(), toward.y() - center.y());
+    let (w2, h2) = (0.5 * bounds.width(), 0.5 * bounds.height());
+
+    let tx = if dx != 0. { w2 / dx.abs() } else { f32::MAX };
+    let ty = if dy != 0. { h2 / dy.abs() } else { f32::MAX };
+
+    let t = tx.min(ty).min(1.);
+
+    Point(center.x() + t * dx, center.y() + t * dy)
+}
+
+///
+/// Moves `point` by `length` toward `from`, stopping at `from`.
+///
+fn shrink_toward(point: Point, from: Point, length: f32) -> Point {
+    let d = distance(point, from);
+
+    if d <= length {
+        from
+    } else {
+        let t = length / d;
+
+        Point(
+            point.x() + t * (from.x() - point.x()),
+            point.y() + t * (from.y() - point.y()),
+        )
+    }
+}
+
+fn is_inside(bounds: &Bounds<Canvas>, point: Point) -> bool {
+    bounds.xmin() < point.x() && point.x() < bounds.xmax()
+        && bounds.ymin() < point.y() && point.y() < bounds.ymax()
+}
+
+#[inline]
+fn distance(a: Point, b: Point) -> f32 {
+    (b.x() - a.x()).hypot(b.y() - a.y())
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{Bounds, Point};
+
+    use super::{clip_to_box, shrink_toward, ConnectionStyle};
+
+    #[test]
+    fn annotate_clip_to_box() {
+        let bounds = Bounds::new(Point(0., 0.), Point(4., 2.));
+
+        assert_eq!(clip_to_box(Point(2., 1.), Point(10., 1.), &bounds), Point(4., 1.));
+        assert_eq!(clip_to_box(Point(2., 1.), Point(2., -5.), &bounds), Point(2., 0.));
+        assert_eq!(clip_to_box(Point(2., 1.), Point(6., 3.), &bounds), Point(4., 2.));
+        // a target inside the box isn't clipped
+        assert_eq!(clip_to_box(Point(2., 1.), Point(3., 1.), &bounds), Point(3., 1.));
+    }
+
+    #[test]
+    fn annotate_shrink() {
+        assert_eq!(shrink_toward(Point(10., 0.), Point(0., 0.), 2.), Point(8., 0.));
+        assert_eq!(shrink_toward(Point(1., 0.), Point(0., 0.), 2.), Point(0., 0.));
+    }
+
+    #[test]
+    fn annotate_connection_control() {
+        let (a, b) = (Point(0., 0.), Point(4., 2.));
+
+        assert_eq!(ConnectionStyle::Straight.control(a, b), None);
+        assert_eq!(ConnectionStyle::Angle.control(a, b), Some(Point(4., 0.)));
+        assert_eq!(ConnectionStyle::Arc(0.).control(a, b), Some(Point(2., 1.)));
+        assert_eq!(ConnectionStyle::Arc(0.5).control(a, b), Some(Point(1., 3.)));
+    }
+}
@@ -6,6 +6,7 @@ use essay_graphics::api::{
 };
 
 use crate::{chart::LegendHandler, transform::ToCanvas};
+use crate::artist::ColorbarHandler;
 use crate::config::{ConfigArc, StyleCycle};
 
 pub trait ArtistDraw<M: Coord> : Send {
@@ -29,6 +30,13 @@ pub trait Artist<M: Coord> : ArtistDraw<M> + Send + Sized {
     fn get_legend(&self) -> Option<LegendHandler> {
         None
     }
+
+    ///
+    /// Color map and value range for the chart's colorbar.
+    ///
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        None
+    }
 }
 
 pub trait IntoArtist<M: Coord> {
@@ -147,6 +155,15 @@ impl<M: Coord> ArtistContainer<M> {
         vec
         */
     }
+
+    ///
+    /// Colorbar handler of the most recently added color-mapped artist.
+    ///
+    pub fn get_colorbar(&mut self) -> Option<ColorbarHandler> {
+        let mut vec = self.artists.0.lock().unwrap();
+
+        vec.iter_mut().rev().find_map(|item| item.get_colorbar())
+    }
 }
 
 struct ContainerArc<M: Coord>(Arc<Mutex<Vec<ArtistItem<M>>>>);
@@ -223,6 +240,11 @@ impl<M: Coord> ArtistItem<M> {
         self.handle.get_legend(&mut self.any)
     }
 
+    #[inline]
+    pub fn get_colorbar(&mut self) -> Option<ColorbarHandler> {
+        self.handle.get_colorbar(&mut self.any)
+    }
+
     #[inline]
     pub fn draw(
         &mut self, 
@@ -237,6 +259,7 @@ impl<M: Coord> ArtistItem<M> {
 trait ArtistHandleTrait<M: Coord> : Send {
     fn get_bounds(&self, any: &mut Box<dyn Any + Send>) -> Bounds<M>;
     fn get_legend(&self, any: &mut Box<dyn Any + Send>) -> Option<LegendHandler>;
+    fn get_colorbar(&self, any: &mut Box<dyn Any + Send>) -> Option<ColorbarHandler>;
 
     fn draw(
         &self, 
@@ -282,6 +305,11 @@ where
         let artist = any.downcast_mut::<A>().unwrap();
         artist.get_legend()
     }
+
+    fn get_colorbar(&self, any: &mut Box<dyn Any + Send>) -> Option<ColorbarHandler> {
+        let artist = any.downcast_mut::<A>().unwrap();
+        artist.get_colorbar()
+    }
 }
 
 
@@ -1,13 +1,22 @@
-use essay_graphics::api::{renderer::{Canvas, Renderer, Result}, Bounds, Path, PathOpt};
+use essay_graphics::api::{
+    renderer::{Canvas, Renderer, Result}, 
+    Bounds, HorizAlign, Path, PathOpt, Point, TextStyle, VertAlign
+};
 use essay_tensor::{tensor::Tensor, init::linspace};
 
 use crate::{
-    chart::{Data, LegendHandler}, 
+    chart::{Data, Formatter, LegendHandler, TickFormatter}, 
     config::{ConfigArc, PathStyle},
     data_artist_option_struct, path_style_options, transform::ToCanvas
 };
 
-use super::{paths, Artist, ArtistDraw, ArtistView};
+use super::{math_text, paths, Artist, ArtistDraw, ArtistView};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Orientation {
+    Vertical,
+    Horizontal,
+}
 
 pub struct Bar {
     height: Tensor,
@@ -16,7 +25,14 @@ pub struct Bar {
     width: Option<Tensor>,
     bottom: Option<Tensor>,
 
+    orientation: Orientation,
+
     style: PathStyle,
+    label: Option<String>,
+
+    is_bar_label: bool,
+    bar_label_style: TextStyle,
+    bar_label_format: Box<dyn TickFormatter>,
 
     extent: Bounds<Data>,
     paths: Vec<Path<Data>>,
@@ -38,6 +54,14 @@ impl Bar {
             width: None,
             bottom: None,
 
+            orientation: Orientation::Vertical,
+
+            label: None,
+
+            is_bar_label: false,
+            bar_label_style: TextStyle::new(),
+            bar_label_format: Box::new(Formatter::StrMethod(String::from("{x:g}"))),
+
             extent: Bounds::<Data>::none(),
             paths: Vec::new(),
             is_stale: true,
@@ -67,6 +91,7 @@ impl Bar {
                 x.shape(), self.height.shape());
 
         self.x = Some(x);
+        self.is_stale = true;
         self.update_bounds();
     }
 
@@ -82,6 +107,7 @@ impl Bar {
 
             self.width = Some(width);
         }
+        self.is_stale = true;
         self.update_bounds();
     }
 
@@ -97,9 +123,92 @@ impl Bar {
 
             self.bottom = Some(bottom);
         }
+        self.is_stale = true;
         self.update_bounds();
     }
 
+    pub(crate) fn orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.is_stale = true;
+        self.update_bounds();
+    }
+
+    ///
+    /// Bar tops as the data position of each value label.
+    ///
+    fn bar_ends(&self) -> Vec<(Point, f32)> {
+        let len = self.height.len();
+
+        let mut ends = Vec::new();
+
+        for i in 0..len {
+            let x = match &self.x {
+                Some(x) => x[i],
+                None => i as f32,
+            };
+
+            let bottom = match &self.bottom {
+                Some(bottom) => bottom[i],
+                None => 0.,
+            };
+
+            let value = self.height[i];
+
+            let end = match self.orientation {
+                Orientation::Vertical => Point(x, bottom + value),
+                Orientation::Horizontal => Point(bottom + value, x),
+            };
+
+            ends.push((end, value));
+        }
+
+        ends
+    }
+
+    fn draw_bar_labels(
+        &mut self, 
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let pad = renderer.to_px(3.);
+
+        for (end, value) in self.bar_ends() {
+            let Point(x, y) = to_canvas.transform_point(end);
+
+            let mut text_style = self.bar_label_style.clone();
+
+            let pos = match (self.orientation, value >= 0.) {
+                (Orientation::Vertical, true) => {
+                    text_style.halign(HorizAlign::Center);
+                    text_style.valign(VertAlign::Bottom);
+                    Point(x, y + pad)
+                }
+                (Orientation::Vertical, false) => {
+                    text_style.halign(HorizAlign::Center);
+                    text_style.valign(VertAlign::Top);
+                    Point(x, y - pad)
+                }
+                (Orientation::Horizontal, true) => {
+                    text_style.halign(HorizAlign::Left);
+                    text_style.valign(VertAlign::Center);
+                    Point(x + pad, y)
+                }
+                (Orientation::Horizontal, false) => {
+                    text_style.halign(HorizAlign::Right);
+                    text_style.valign(VertAlign::Center);
+                    Point(x - pad, y)
+                }
+            };
+
+            let label = self.bar_label_format.format(value, value.abs());
+
+            math_text::draw_text(renderer, pos, &label, 0., style, &text_style)?;
+        }
+
+        Ok(())
+    }
+
     fn update_bounds(&mut self) {
         if self.is_stale {
             self.is_stale = false;
@@ -127,17 +236,36 @@ impl Bar {
             let y_min = bottom.reduce_min()[0];
             let y_max = (&bottom + &self.height).reduce_max()[0];
 
-            self.extent = Bounds::new([min - w2[0], y_min], [max + w2[len - 1], y_max]);
+            // negative bars extend below their bottom
+            let y_min = y_min.min((&bottom + &self.height).reduce_min()[0]);
+            let y_max = y_max.max(bottom.reduce_max()[0]);
+
+            self.extent = match self.orientation {
+                Orientation::Vertical => {
+                    Bounds::new([min - w2[0], y_min], [max + w2[len - 1], y_max])
+                }
+                Orientation::Horizontal => {
+                    Bounds::new([y_min, min - w2[0]], [y_max, max + w2[len - 1]])
+                }
+            };
 
             let mut paths = Vec::<Path<Data>>::new();
 
             for i in 0..self.height.len() {
                 let x = x[i];
 
-                paths.push(paths::rect(
-                    [x - w2[i], bottom[i]], 
-                    [x + w2[i], bottom[i] + self.height[i]]
-                ));
+                let path = match self.orientation {
+                    Orientation::Vertical => paths::rect(
+                        [x - w2[i], bottom[i]], 
+                        [x + w2[i], bottom[i] + self.height[i]]
+                    ),
+                    Orientation::Horizontal => paths::rect(
+                        [bottom[i], x - w2[i]], 
+                        [bottom[i] + self.height[i], x + w2[i]]
+                    ),
+                };
+
+                paths.push(path);
             }
 
             self.paths = paths;
@@ -163,6 +291,10 @@ impl ArtistDraw<Data> for Bar {
             renderer.draw_path(&c_path, &style)?;
         }
 
+        if self.is_bar_label {
+            self.draw_bar_labels(renderer, to_canvas, &style)?;
+        }
+
         Ok(())
     }
 }
@@ -179,7 +311,21 @@ impl Artist<Data> for Bar {
     }
 
     fn get_legend(&self) -> Option<LegendHandler> {
-        None
+        match &self.label {
+            Some(label) => {
+                let style = self.style.clone();
+                Some(LegendHandler::new(label.clone(), 
+                move |renderer, top_style, bounds| {
+                    let rect: Path<Canvas> = paths::rect(
+                        [bounds.xmin(), bounds.ymin()],
+                        [bounds.xmax(), bounds.ymax()],
+                    );
+
+                    renderer.draw_path(&rect, &style.push(top_style))
+                }))
+            },
+            None => None,
+        }
     }
 }
 
@@ -219,4 +365,47 @@ impl BarOpt {
 
         self
     }
+
+    pub fn orientation(&mut self, orientation: Orientation) -> &mut Self {
+        self.write(|artist| {
+            artist.orientation(orientation);
+        });
+
+        self
+    }
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.write(|artist| {
+            if label.len() > 0 {
+                artist.label = Some(label.to_string());
+            } else {
+                artist.label = None;
+            }
+        });
+
+        self
+    }
+
+    ///
+    /// Shows each bar's value at its end.
+    ///
+    pub fn bar_label(&mut self, is_bar_label: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_bar_label = is_bar_label;
+        });
+
+        self
+    }
+
+    ///
+    /// Formatter for the bar value labels, `"{x:g}"` by default, e.g.
+    /// `Formatter::StrMethod("{x:.1f} kg".into())`.
+    ///
+    pub fn bar_label_format(&mut self, formatter: impl TickFormatter + 'static) -> &mut Self {
+        self.write(|artist| {
+            artist.bar_label_format = Box::new(formatter);
+        });
+
+        self
+    }
 }
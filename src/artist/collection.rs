@@ -14,7 +14,7 @@ use super::ArtistDraw;
 pub struct PathCollection {
     path: Path<Canvas>,
     xy: Tensor, // 2d tensor representing a graph
-    color: Tensor<u32>,
+    colors: Vec<Color>,
     scale: Tensor,
     style: PathStyle,
     affine: Vec<Affine2d>,
@@ -35,7 +35,7 @@ impl PathCollection {
             path,
             bounds: Bounds::from(&xy),
             xy,
-            color: Tensor::from(None),
+            colors: Vec::new(),
             scale: Tensor::from(None),
             style: PathStyle::new(), // needs to be loop
             affine,
@@ -45,6 +45,15 @@ impl PathCollection {
     pub fn style_mut(&mut self) -> &mut PathStyle {
         &mut self.style
     }
+
+    ///
+    /// Fill color for each path, overriding the style's face color.
+    ///
+    pub fn set_colors(&mut self, colors: Vec<Color>) {
+        assert!(colors.len() == self.affine.len(), "Collection colors must match the data length");
+
+        self.colors = colors;
+    }
 }
 
 impl ArtistDraw<Data> for PathCollection {
@@ -61,7 +70,16 @@ impl ArtistDraw<Data> for PathCollection {
         let style = self.style.push(style);
 
         // TODO: rework with markers
-        if style.get_face_color().is_some() && ! style.get_face_color().unwrap().is_none() {
+        if self.colors.len() > 0 {
+            let markers: Vec<MeshStyle> = self.affine.iter().zip(&self.colors).map(|(affine, color)| {
+                MeshStyle {
+                    color: color.clone(),
+                    affine: affine.clone(),
+                }
+            }).collect();
+
+            ui.draw_markers(&self.path, &style, markers.as_slice())
+        } else if style.get_face_color().is_some() && ! style.get_face_color().unwrap().is_none() {
             let markers: Vec<MeshStyle> = self.affine.iter().map(|affine| {
                 MeshStyle {
                     color: style.get_face_color().unwrap_or(Color::black()),
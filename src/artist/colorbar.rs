@@ -1,21 +1,73 @@
 use essay_graphics::api::{
-    renderer::{Canvas, Renderer, Result}, 
-    Bounds, CapStyle, Color, PathOpt, Point
+    renderer::{Canvas, Renderer, Result},
+    Bounds, CapStyle, Color, HorizAlign, Path, PathOpt, Point, TextStyle, VertAlign
 };
-use essay_tensor::{init::linspace, ten, tensor::Tensor};
+use essay_tensor::{ten, tensor::Tensor};
 
 use crate::{
     chart::Data,
-    config::PathStyle, transform::{ToCanvas, TransformAffine},
+    config::PathStyle, palette::ColorMap, transform::{ToCanvas, TransformAffine},
 };
 
-use super::{ArtistDraw, grid_color::GridColor, paths};
+use super::{ArtistDraw, grid_color::GridColor, math_text, paths, text_layout, Norm, Norms};
+
+///
+/// Color map and value range of a color-mapped artist, displayed by the
+/// chart's colorbar. For a log norm, `min` and `max` are scaled values
+/// and `norm` maps them back to data values for the tick labels.
+///
+#[derive(Clone)]
+pub struct ColorbarHandler {
+    color_map: ColorMap,
+    min: f32,
+    max: f32,
+    norm: Norms,
+}
+
+impl ColorbarHandler {
+    pub fn new(color_map: ColorMap, min: f32, max: f32) -> Self {
+        Self {
+            color_map,
+            min,
+            max,
+            norm: Norms::Linear,
+        }
+    }
+
+    ///
+    /// Handler for the norm's scaled range. A custom norm function is
+    /// labeled with the scaled values.
+    ///
+    pub fn from_norm(color_map: ColorMap, norm: &Norm) -> Self {
+        Self {
+            color_map,
+            min: norm.min(),
+            max: norm.max(),
+            norm: norm.kind().cloned().unwrap_or(Norms::Linear),
+        }
+    }
+
+    pub fn norm(&self) -> &Norms {
+        &self.norm
+    }
+
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+}
 
 pub struct Colorbar {
     bounds: Bounds<Data>,
     pos: Bounds<Canvas>,
     mesh: GridColor,
     data: Tensor,
+
+    handler: Option<ColorbarHandler>,
+    ticks: Vec<(f32, String)>,
 }
 
 impl Colorbar {
@@ -25,6 +77,9 @@ impl Colorbar {
             pos: Bounds::zero(),
             data: ten![0., 1.],
             mesh: GridColor::new(ten![[0.]]),
+
+            handler: None,
+            ticks: Vec::new(),
         }
     }
 
@@ -32,42 +87,142 @@ impl Colorbar {
         self.pos = pos.clone();
     }
 
-    pub(crate) fn resize(&mut self, _renderer: &mut dyn Renderer, pos: &Bounds<Canvas>) {
-        // let pos = renderer.pos().clone();
+    ///
+    /// Updates the colors and the labeled tick values from the chart's
+    /// color-mapped artist.
+    ///
+    pub(crate) fn set_handler(&mut self, handler: ColorbarHandler, ticks: Vec<(f32, String)>) {
+        self.mesh.color_map(handler.color_map.clone());
+        self.handler = Some(handler);
+        self.ticks = ticks;
+    }
 
+    ///
+    /// Places the bar to the right of the data bounds.
+    ///
+    pub(crate) fn resize(&mut self, renderer: &mut dyn Renderer, pos: &Bounds<Canvas>) {
         let is_triangle = false;
         if is_triangle {
             self.bounds = Bounds::new(Point(0., 0.), Point(2., 100.));
         } else {
             self.bounds = Bounds::new(Point(0., 0.), Point(2., 101.));
         }
-        let x = linspace(0., 1., 101);//.reshape([101, 1]);
+        // grid rows are drawn top down, so the first row holds the maximum
+        let x: Vec<f32> = (0..101).map(|i| 1. - i as f32 / 100.).collect();
+        let x = Tensor::from(x);
         self.data = x.stack([x.clone()], -1);
         self.mesh.set_data(self.data.clone());
-        // self.mesh.resize(renderer, pos.clone());
-        self.pos = pos.clone();
+
+        let x0 = pos.xmax() + renderer.to_px(10.);
+
+        self.pos = Bounds::new(
+            Point(x0, pos.ymin()),
+            Point(x0 + renderer.to_px(12.), pos.ymax()),
+        );
+    }
+
+    fn label_style() -> TextStyle {
+        let mut text_style = TextStyle::new();
+        text_style.halign(HorizAlign::Left);
+        text_style.valign(VertAlign::Center);
+
+        text_style
     }
 
+    ///
+    /// Canvas bounds of the bar, its ticks and the measured tick labels.
+    ///
+    pub(crate) fn extent(&self, renderer: &dyn Renderer) -> Bounds<Canvas> {
+        let pos = &self.pos;
+        let mut extent = pos.clone();
+
+        let (min, max) = match &self.handler {
+            Some(handler) if handler.max > handler.min => (handler.min, handler.max),
+            _ => return extent,
+        };
+
+        let x = pos.xmax() + renderer.to_px(3.5) + renderer.to_px(2.);
+        let text_style = Self::label_style();
+
+        for (value, label) in &self.ticks {
+            if min <= *value && *value <= max {
+                let y = pos.ymin() + (value - min) / (max - min) * pos.height();
+
+                let label_extent = text_layout::text_extent(renderer, Point(x, y), label, &text_style);
+                extent = extent.union(&label_extent);
+            }
+        }
+
+        extent
+    }
+
+    fn draw_ticks(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let handler = match &self.handler {
+            Some(handler) => handler,
+            None => return Ok(()),
+        };
+
+        let (min, max) = (handler.min, handler.max);
+
+        if ! (max > min) {
+            return Ok(());
+        }
+
+        let pos = &self.pos;
+        let tick_len = renderer.to_px(3.5);
+        let pad = renderer.to_px(2.);
+
+        let text_style = Self::label_style();
+
+        for (value, label) in &self.ticks {
+            if *value < min || *value > max {
+                continue;
+            }
+
+            let y = pos.ymin() + (value - min) / (max - min) * pos.height();
+
+            let tick = Path::<Canvas>::from([
+                [pos.xmax(), y],
+                [pos.xmax() + tick_len, y],
+            ]);
+
+            renderer.draw_path(&tick, style)?;
+
+            math_text::draw_text(
+                renderer,
+                Point(pos.xmax() + tick_len + pad, y),
+                label,
+                0.,
+                style,
+                &text_style
+            )?;
+        }
+
+        Ok(())
+    }
 }
 
-impl ArtistDraw<Data> for Colorbar {
-    fn bounds(&mut self) -> Bounds<Data> {
-        self.bounds.clone()
+impl ArtistDraw<Canvas> for Colorbar {
+    fn bounds(&mut self) -> Bounds<Canvas> {
+        self.pos.clone()
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         renderer: &mut dyn Renderer,
-        to_canvas: &ToCanvas<Data>,
+        to_canvas: &ToCanvas<Canvas>,
         style: &dyn PathOpt,
     ) -> Result<()> {
         let transform = TransformAffine::new(self.bounds.affine_to(&self.pos));
-        let to_canvas = ToCanvas::new(
+        let data_to_canvas = ToCanvas::new(
             to_canvas.stale(),
             Bounds::from([0., 1.]),
-            &transform,    
+            &transform,
         );
-        // self.mesh.draw(renderer, &to_canvas, clip, style);
 
         let path = paths::bounds(&self.pos);
         let mut pstyle = PathStyle::new();
@@ -76,7 +231,9 @@ impl ArtistDraw<Data> for Colorbar {
         pstyle.cap_style(CapStyle::Projecting);
         pstyle.line_width(0.7);
 
-        self.mesh.draw(renderer, &to_canvas, style)?;
-        renderer.draw_path(&path, &pstyle)
+        self.mesh.draw(renderer, &data_to_canvas, style)?;
+        renderer.draw_path(&path, &pstyle)?;
+
+        self.draw_ticks(renderer, style)
     }
-}
\ No newline at end of file
+}
@@ -1,34 +1,68 @@
 use essay_graphics::api::{
-    renderer::{Canvas, Renderer, Result}, 
-    Bounds, Path, PathOpt, Point
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Color, HorizAlign, Mesh2dColor, Path, PathCode, PathOpt, Point, TextStyle, VertAlign
 };
 use essay_tensor::tensor::Tensor;
 
 use crate::{
-    chart::Data, config::PathStyle, contour::ContourGenerator, palette::{ColorMap, EssayColors}, transform::ToCanvas
+    chart::{Data, LegendHandler, MaxNLocator, TickLocator},
+    config::{ConfigArc, PathStyle},
+    contour::ContourGenerator,
+    data_artist_option_struct, path_style_options,
+    palette::{ColorMap, EssayColors},
+    transform::ToCanvas
 };
 
-use super::ArtistDraw;
+use super::{mesh_coords::MeshCoords, text_layout, Artist, ArtistDraw, ArtistView, ColorbarHandler};
+
+///
+/// Filled regions beyond the outermost levels.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Extend {
+    Neither,
+    Min,
+    Max,
+    Both,
+}
 
 pub struct Level {
+    value: f32,
     paths: Vec<Path<Data>>,
 }
 
 impl Level {
-    fn new(paths: Vec<Path<Data>>) -> Self {
+    fn new(value: f32, paths: Vec<Path<Data>>) -> Self {
         Self {
+            value,
             paths,
         }
     }
 }
 
+pub(super) struct Band {
+    pub(super) color: Color,
+    pub(super) polygons: Vec<Vec<[f32; 2]>>,
+}
+
 pub struct Contour {
     data: Tensor,
+    coords: Option<MeshCoords>,
     color_map: ColorMap,
+    style: PathStyle,
 
-    _xy: Tensor,
-    norm: Tensor,
+    thresholds: Option<Vec<f32>>,
+    n_levels: usize,
+    extend: Extend,
+    is_filled: bool,
+    is_label: bool,
+    label_style: TextStyle,
+
+    level_values: Vec<f32>,
     levels: Vec<Level>,
+    bands: Vec<Band>,
+
+    is_stale: bool,
 }
 
 impl Contour {
@@ -37,12 +71,28 @@ impl Contour {
 
         assert!(data.rank() == 2, "contour requires 2d value {:?}", data.shape());
 
+        let mut label_style = TextStyle::new();
+        label_style.halign(HorizAlign::Center);
+        label_style.valign(VertAlign::Center);
+
         Self {
             data,
-            _xy: Tensor::from(None),
-            norm: Tensor::from(None),
+            coords: None,
             color_map: EssayColors::Default.into(),
+            style: PathStyle::new(),
+
+            thresholds: None,
+            n_levels: 7,
+            extend: Extend::Neither,
+            is_filled: false,
+            is_label: false,
+            label_style,
+
+            level_values: Vec::new(),
             levels: Vec::new(),
+            bands: Vec::new(),
+
+            is_stale: true,
         }
     }
 
@@ -50,100 +100,532 @@ impl Contour {
         assert!(data.rank() == 2, "contour requires 2d value {:?}", data.shape());
 
         self.data = data;
+        self.is_stale = true;
+    }
+
+    ///
+    /// Data coordinates of the grid points, as 1D `x` and `y` vectors or 2D
+    /// grids matching the data's shape. Without coordinates, grid points are
+    /// at their integer column and row.
+    ///
+    pub(crate) fn set_xy(&mut self, x: &Tensor, y: &Tensor) {
+        let coords = MeshCoords::new(x, y);
+
+        assert!(
+            coords.shape() == (self.data.rows(), self.data.cols()),
+            "contour x and y must match the data shape {:?} data={:?}",
+            coords.shape(), self.data.shape()
+        );
+
+        self.coords = Some(coords);
+        self.is_stale = true;
+    }
+
+    ///
+    /// Maps an index-space contour vertex to data coordinates.
+    ///
+    fn to_data(&self, point: [f32; 2]) -> [f32; 2] {
+        match &self.coords {
+            Some(coords) => coords.interp(point[0], point[1]),
+            None => point,
+        }
+    }
+
+    pub(crate) fn filled(&mut self, is_filled: bool) {
+        self.is_filled = is_filled;
+        self.is_stale = true;
     }
 
     fn resize(&mut self) {
-        let mut xy = Vec::<[f32; 2]>::new();
-        let (rows, cols) = (self.data.rows(), self.data.cols());
+        if ! self.is_stale {
+            return;
+        }
+
+        self.is_stale = false;
 
-        for j in 0..rows {
-            for i in 0..cols {
-                xy.push([i as f32, j as f32]);
+        let (min, max) = finite_range(self.data.as_slice());
+
+        self.level_values = match &self.thresholds {
+            Some(thresholds) => thresholds.clone(),
+            None => auto_levels(min, max, self.n_levels, self.is_filled),
+        };
+
+        let mut cg = ContourGenerator::new(self.data.clone());
+
+        if self.is_filled {
+            let bands = band_ranges(&self.level_values, self.extend);
+
+            self.bands = bands.iter().map(|(lower, upper)| {
+                let polygons = cg.contour_bands(*lower, *upper).iter().map(|polygon| {
+                    polygon.iter().map(|p| self.to_data(*p)).collect()
+                }).collect();
+
+                Band {
+                    color: band_color(&self.color_map, &self.level_values, *lower, *upper),
+                    polygons,
+                }
+            }).collect();
+
+            self.levels = Vec::new();
+        } else {
+            let mut levels = Vec::<Level>::new();
+
+            for threshold in &self.level_values {
+                let paths = cg.contour_lines(*threshold);
+
+                let paths: Vec<Path<Data>> = paths.iter().map(|p| {
+                    let points: Vec<[f32; 2]> = p.as_slice().chunks(2)
+                        .map(|xy| self.to_data([xy[0], xy[1]]))
+                        .collect();
+
+                    Path::<Data>::lines(&Tensor::from(points))
+                }).collect();
+
+                levels.push(Level::new(*threshold, paths));
             }
+
+            self.levels = levels;
+            self.bands = Vec::new();
         }
+    }
 
-        self.norm = self.data.normalize_unit();
+    fn draw_lines(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let pad = renderer.to_px(2.);
 
-        let mut cg = ContourGenerator::new(self.data.clone());
+        let step = level_step(&self.level_values);
+
+        for level in &self.levels {
+            let label = format_level(level.value, step);
+            let width = text_layout::text_extent(renderer, Point(0., 0.), &label, &self.label_style)
+                .width() + 2. * pad;
 
-        let level_thresholds = vec![
-            -1.5,
-            -1.,
-            -0.5, 
-            0., 
-            0.5,
-            1.,
-            1.5,
-            ];
-        let mut levels = Vec::<Level>::new();
-
-        for threshold in &level_thresholds {
-            let paths = cg.contour_lines(*threshold);
-
-            let paths: Vec<Path<Data>> = paths.iter()
-                .map(|p| Path::<Data>::lines(p))
-                .collect();
-
-            levels.push(Level::new(paths));
+            for path in &level.paths {
+                let path : Path<Canvas> = to_canvas.transform_path(path);
+
+                if ! self.is_label {
+                    renderer.draw_path(&path, style)?;
+                    continue;
+                }
+
+                let points: Vec<Point> = path.codes().iter().filter_map(|code| match code {
+                    PathCode::MoveTo(p) | PathCode::LineTo(p) | PathCode::ClosePoly(p) => Some(*p),
+                    _ => None,
+                }).collect();
+
+                match label_cut(&points, width) {
+                    Some((pos, angle, pieces)) => {
+                        for piece in pieces {
+                            if piece.len() > 1 {
+                                let piece = Path::<Canvas>::lines(
+                                    &Tensor::from(piece.iter().map(|p| [p.0, p.1]).collect::<Vec<[f32; 2]>>())
+                                );
+
+                                renderer.draw_path(&piece, style)?;
+                            }
+                        }
+
+                        renderer.draw_text(pos, &label, angle, style, &self.label_style)?;
+                    }
+                    None => {
+                        renderer.draw_path(&path, style)?;
+                    }
+                }
+            }
         }
 
-        self.levels = levels;
+        Ok(())
+    }
+}
+
+///
+/// Fills each band's convex polygons with the band color.
+///
+pub(super) fn draw_bands(
+    renderer: &mut dyn Renderer,
+    to_canvas: &ToCanvas<Data>,
+    bands: &[Band],
+) -> Result<()> {
+    let mut mesh = Mesh2dColor::new();
+
+    for band in bands {
+        for polygon in &band.polygons {
+            let points: Vec<[f32; 2]> = polygon.iter().map(|p| {
+                let Point(x, y) = to_canvas.transform_point(Point(p[0], p[1]));
+
+                [x, y]
+            }).collect();
+
+            // polygons are convex, so a fan covers them
+            for k in 1..points.len() - 1 {
+                mesh.triangle(
+                    (points[0], band.color),
+                    (points[k], band.color),
+                    (points[k + 1], band.color),
+                );
+            }
+        }
+    }
+
+    renderer.draw_mesh2d_color(&mesh)
+}
+
+///
+/// Band color at the band's midpoint, with extended bands at the ends of
+/// the color map.
+///
+pub(super) fn band_color(color_map: &ColorMap, levels: &[f32], lower: f32, upper: f32) -> Color {
+    let (l0, l1) = (levels[0], levels[levels.len() - 1]);
+
+    let v = if lower.is_infinite() {
+        0.
+    } else if upper.is_infinite() {
+        1.
+    } else if l1 > l0 {
+        (0.5 * (lower + upper) - l0) / (l1 - l0)
+    } else {
+        0.5
+    };
+
+    color_map.map(v)
+}
+
+///
+/// Minimum and maximum of the finite values.
+///
+pub(super) fn finite_range(values: &[f32]) -> (f32, f32) {
+    let (mut min, mut max) = (f32::MAX, f32::MIN);
+
+    for v in values.iter().filter(|v| v.is_finite()) {
+        (min, max) = (min.min(*v), max.max(*v));
+    }
+
+    if min > max {
+        (0., 1.)
+    } else if min == max {
+        (min - 1., max + 1.)
+    } else {
+        (min, max)
+    }
+}
+
+///
+/// Round level values for about `n_levels` levels. Filled contours extend
+/// the levels to cover the data, while lines keep levels inside the data.
+///
+pub(super) fn auto_levels(min: f32, max: f32, n_levels: usize, is_filled: bool) -> Vec<f32> {
+    let ticks = MaxNLocator::new(Some(n_levels + 1)).tick_values(min, max);
+    let mut levels: Vec<f32> = ticks.iter().map(|v| *v).collect();
+
+    if is_filled {
+        let step = level_step(&levels);
+
+        if levels[0] > min {
+            levels.insert(0, levels[0] - step);
+        }
+
+        if levels[levels.len() - 1] < max {
+            levels.push(levels[levels.len() - 1] + step);
+        }
+
+        levels
+    } else {
+        levels.into_iter().filter(|v| min < *v && *v < max).collect()
+    }
+}
+
+///
+/// Lower and upper bounds of each filled band, including the open bands
+/// beyond the outermost levels when extended.
+///
+pub(super) fn band_ranges(levels: &[f32], extend: Extend) -> Vec<(f32, f32)> {
+    let mut bands = Vec::<(f32, f32)>::new();
+
+    if levels.len() == 0 {
+        return bands;
+    }
+
+    if extend == Extend::Min || extend == Extend::Both {
+        bands.push((f32::NEG_INFINITY, levels[0]));
+    }
+
+    for pair in levels.windows(2) {
+        bands.push((pair[0], pair[1]));
+    }
+
+    if extend == Extend::Max || extend == Extend::Both {
+        bands.push((levels[levels.len() - 1], f32::INFINITY));
+    }
+
+    bands
+}
+
+fn level_step(levels: &[f32]) -> f32 {
+    if levels.len() < 2 {
+        1.
+    } else {
+        levels.windows(2)
+            .map(|w| (w[1] - w[0]).abs())
+            .fold(f32::MAX, f32::min)
+    }
+}
+
+///
+/// Level label with enough decimals to distinguish levels `step` apart.
+///
+fn format_level(value: f32, step: f32) -> String {
+    let decimals = if step > 0. {
+        (-step.log10().floor()).max(0.) as usize
+    } else {
+        0
+    };
+
+    let value = if value == 0. { 0. } else { value };
+
+    format!("{:.*}", decimals, value)
+}
+
+///
+/// Cuts a gap of `width` at the middle of a canvas path for an inline label.
+/// Returns the label position, the text angle along the path and the pieces
+/// left on either side, or None when the path is too short for the label.
+///
+fn label_cut(points: &[Point], width: f32) -> Option<(Point, f32, Vec<Vec<Point>>)> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let mut dist = vec![0f32];
+
+    for pair in points.windows(2) {
+        let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+
+        dist.push(dist[dist.len() - 1] + dx.hypot(dy));
+    }
+
+    let length = dist[dist.len() - 1];
+
+    if length < 2. * width {
+        return None;
+    }
+
+    let mid = 0.5 * length;
+    let (s0, s1) = (mid - 0.5 * width, mid + 0.5 * width);
+
+    let point_at = |s: f32| -> Point {
+        let i = dist.partition_point(|d| *d < s).clamp(1, points.len() - 1);
+        let (a, b) = (points[i - 1], points[i]);
+        let seg = dist[i] - dist[i - 1];
+        let t = if seg > 0. { (s - dist[i - 1]) / seg } else { 0. };
+
+        Point(a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1))
+    };
+
+    let (p0, p1) = (point_at(s0), point_at(s1));
 
-        self._xy = Tensor::from(xy);
+    let mut before: Vec<Point> = points.iter().zip(&dist)
+        .filter(|(_, d)| **d < s0)
+        .map(|(p, _)| *p)
+        .collect();
+    before.push(p0);
+
+    let mut after = vec![p1];
+    after.extend(points.iter().zip(&dist).filter(|(_, d)| **d > s1).map(|(p, _)| *p));
+
+    // keep text upright
+    let mut angle = (p1.1 - p0.1).atan2(p1.0 - p0.0);
+    if angle > 0.5 * std::f32::consts::PI {
+        angle -= std::f32::consts::PI;
+    } else if angle < -0.5 * std::f32::consts::PI {
+        angle += std::f32::consts::PI;
     }
+
+    Some((point_at(mid), angle, vec![before, after]))
 }
 
 impl ArtistDraw<Data> for Contour {
     fn bounds(&mut self) -> Bounds<Data> {
+        // the colorbar reads the levels before the first draw
+        self.resize();
+
+        if let Some(coords) = &self.coords {
+            return coords.bounds();
+        }
+
         let (rows, cols) = (self.data.rows(), self.data.cols());
 
+        // grid points are at integer positions
         Bounds::new(
-            Point(0.0, 0.0), 
-            Point(cols as f32, rows as f32)
+            Point(0.0, 0.0),
+            Point((cols - 1).max(1) as f32, (rows - 1).max(1) as f32)
         )
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         renderer: &mut dyn Renderer,
         to_canvas: &ToCanvas<Data>,
-        _style: &dyn PathOpt,
+        style: &dyn PathOpt,
     ) -> Result<()> {
         self.resize();
 
-        //let path = Path::<Data>::closed_poly(tf32!([
-        //    [0.0, 0.0], [1.0, 0.0], [1.0, 1.0],
-        //    [0.0, 1.0]
-        //    ]));
-            
-        // let scale_canvas = to_canvas.strip_translation();
-        // let path: Path<Canvas> = path.transform(&scale_canvas);
-        // let xy = to_canvas.transform(&self.xy);
+        if self.is_filled {
+            draw_bands(renderer, to_canvas, &self.bands)
+        } else {
+            let style = self.style.push(style);
 
-        let colormap = &self.color_map;
+            self.draw_lines(renderer, to_canvas, &style)
+        }
+    }
+}
 
-        let mut colors = Vec::<u32>::new();
-        for v in self.norm.iter() {
-            colors.push(colormap.map(*v).to_rgba());
+impl Artist<Data> for Contour {
+    type Opt = ContourOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.style = PathStyle::from_config(cfg, "contour");
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, Contour>) -> Self::Opt {
+        ContourOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        let n = self.level_values.len();
+
+        if n > 1 {
+            Some(ColorbarHandler::new(
+                self.color_map.clone(),
+                self.level_values[0],
+                self.level_values[n - 1]
+            ))
+        } else {
+            None
         }
+    }
+}
 
-        // let colors = colors.into_tensor();
+data_artist_option_struct!(ContourOpt, Contour);
 
-        let mut style = PathStyle::new();
+impl ContourOpt {
+    path_style_options!(style);
 
-        style.edge_color("k");
-        style.line_width(1.);
+    ///
+    /// Explicit, increasing level values.
+    ///
+    pub fn levels(&mut self, levels: &[f32]) -> &mut Self {
+        assert!(levels.len() > 0, "contour levels must not be empty");
+        assert!(
+            levels.windows(2).all(|w| w[0] < w[1]),
+            "contour levels must be increasing"
+        );
 
-        //renderer.draw_markers(&path, &xy, &tf32!(), &colors, &style, clip).unwrap();
+        let levels = Vec::from(levels);
 
-        for level in &self.levels {
-            for path in &level.paths {
-                let path : Path<Canvas> = to_canvas.transform_path(path);
+        self.write(|artist| {
+            artist.thresholds = Some(levels);
+            artist.is_stale = true;
+        });
 
-                renderer.draw_path(&path, &style)?;
-            }
-        }
+        self
+    }
 
-        Ok(())
+    ///
+    /// Approximate number of automatically chosen levels.
+    ///
+    pub fn n_levels(&mut self, n_levels: usize) -> &mut Self {
+        assert!(n_levels > 0);
+
+        self.write(|artist| {
+            artist.n_levels = n_levels;
+            artist.thresholds = None;
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Also fills values below the lowest or above the highest level.
+    ///
+    pub fn extend(&mut self, extend: Extend) -> &mut Self {
+        self.write(|artist| {
+            artist.extend = extend;
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn color_map(&mut self, cmap: impl Into<ColorMap>) -> &mut Self {
+        self.write(|artist| {
+            artist.color_map = cmap.into();
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Labels each contour line with its level, in a gap cut in the line.
+    ///
+    pub fn clabel(&mut self, is_label: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_label = is_label;
+        });
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::Point;
+
+    use super::{band_ranges, format_level, label_cut, Extend};
+
+    #[test]
+    fn contour_band_ranges() {
+        let inf = f32::INFINITY;
+
+        assert_eq!(band_ranges(&[0., 1., 2.], Extend::Neither), vec![(0., 1.), (1., 2.)]);
+        assert_eq!(band_ranges(&[0., 1.], Extend::Min), vec![(-inf, 0.), (0., 1.)]);
+        assert_eq!(band_ranges(&[0., 1.], Extend::Both), vec![(-inf, 0.), (0., 1.), (1., inf)]);
+    }
+
+    #[test]
+    fn contour_format_level() {
+        assert_eq!(format_level(1., 0.5), "1.0");
+        assert_eq!(format_level(-0.25, 0.05), "-0.25");
+        assert_eq!(format_level(20., 10.), "20");
+    }
+
+    #[test]
+    fn contour_label_cut() {
+        let line = [Point(0., 0.), Point(10., 0.), Point(20., 0.)];
+
+        let (pos, angle, pieces) = label_cut(&line, 4.).unwrap();
+
+        assert_eq!(pos, Point(10., 0.));
+        assert_eq!(angle, 0.);
+        assert_eq!(pieces, vec![
+            vec![Point(0., 0.), Point(8., 0.)],
+            vec![Point(12., 0.), Point(20., 0.)],
+        ]);
+
+        // reversed lines keep the text upright
+        let line = [Point(20., 0.), Point(0., 0.)];
+        let (_, angle, _) = label_cut(&line, 4.).unwrap();
+        assert_eq!(angle, 0.);
+
+        assert!(label_cut(&line, 15.).is_none());
     }
 }
@@ -0,0 +1,460 @@
+use core::fmt;
+
+use essay_graphics::api::{
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Path, PathCode, PathOpt, Point
+};
+use essay_tensor::tensor::Tensor;
+
+use crate::{
+    chart::{Data, LegendHandler},
+    config::{ConfigArc, PathStyle},
+    data_artist_option_struct, path_style_options, transform::ToCanvas
+};
+
+use super::{
+    markers::{IntoMarker, MarkerStyle}, Artist, ArtistDraw, ArtistView, PathCollection,
+};
+
+///
+/// Points with x and/or y error bars, optionally joined by a line.
+///
+/// Errors are either a scalar for all points, one value per point for
+/// symmetric errors, or a `[2, n]` tensor of lower and upper errors.
+///
+pub struct ErrorBar {
+    xy: Tensor,
+    xerr: Option<Vec<(f32, f32)>>,
+    yerr: Option<Vec<(f32, f32)>>,
+
+    line: Path<Data>,
+    bars: Vec<Path<Data>>,
+
+    style: PathStyle,
+    label: Option<String>,
+
+    is_line: bool,
+    cap_size: f32,
+
+    marker: Option<MarkerStyle>,
+    collection: Option<PathCollection>,
+
+    bounds: Bounds<Data>,
+}
+
+impl ErrorBar {
+    pub fn from_xy(x: impl Into<Tensor>, y: impl Into<Tensor>) -> Self {
+        let x = x.into();
+        let y = y.into();
+
+        assert_eq!(x.len(), y.len(), "errorbar data lengths must match x={:?} y={:?}",
+            x.shape(), y.shape()
+        );
+
+        let xy = x.stack([y], -1);
+
+        let mut errorbar = Self {
+            line: build_line(&xy),
+            xy,
+            xerr: None,
+            yerr: None,
+
+            bars: Vec::new(),
+
+            style: PathStyle::new(),
+            label: None,
+
+            is_line: true,
+            cap_size: 0.,
+
+            marker: None,
+            collection: None,
+
+            bounds: Bounds::none(),
+        };
+
+        errorbar.rebuild();
+
+        errorbar
+    }
+
+    pub fn xerr(&mut self, xerr: impl Into<Tensor>) -> &mut Self {
+        self.xerr = Some(error_pairs(&xerr.into(), self.xy.rows()));
+        self.rebuild();
+
+        self
+    }
+
+    pub fn yerr(&mut self, yerr: impl Into<Tensor>) -> &mut Self {
+        self.yerr = Some(error_pairs(&yerr.into(), self.xy.rows()));
+        self.rebuild();
+
+        self
+    }
+
+    pub fn marker(&mut self, marker: impl IntoMarker) -> &mut Self {
+        let marker = marker.into_marker();
+
+        self.collection = Some(PathCollection::new(marker.get_path(), &self.xy));
+        self.marker = Some(marker);
+
+        self
+    }
+
+    fn rebuild(&mut self) {
+        self.bars = build_bars(&self.xy, &self.xerr, &self.yerr);
+        self.bounds = error_bounds(&self.xy, &self.xerr, &self.yerr);
+    }
+
+    ///
+    /// Caps as canvas paths of `cap_size` points across each bar end.
+    ///
+    fn caps(&self, renderer: &mut dyn Renderer, to_canvas: &ToCanvas<Data>) -> Vec<Path<Canvas>> {
+        let mut caps = Vec::new();
+
+        if self.cap_size <= 0. {
+            return caps;
+        }
+
+        let half = 0.5 * renderer.to_px(self.cap_size);
+
+        for (i, xy) in self.xy.iter_row().enumerate() {
+            let (x, y) = (xy[0], xy[1]);
+
+            if let Some(xerr) = &self.xerr {
+                let (lo, hi) = xerr[i];
+
+                for x in [x - lo, x + hi] {
+                    let Point(cx, cy) = to_canvas.transform_point(Point(x, y));
+
+                    caps.push(Path::from([[cx, cy - half], [cx, cy + half]]));
+                }
+            }
+
+            if let Some(yerr) = &self.yerr {
+                let (lo, hi) = yerr[i];
+
+                for y in [y - lo, y + hi] {
+                    let Point(cx, cy) = to_canvas.transform_point(Point(x, y));
+
+                    caps.push(Path::from([[cx - half, cy], [cx + half, cy]]));
+                }
+            }
+        }
+
+        caps
+    }
+}
+
+///
+/// Lower and upper error for each of `n` points.
+///
+fn error_pairs(err: &Tensor, n: usize) -> Vec<(f32, f32)> {
+    let values: Vec<f32> = err.iter().map(|v| *v).collect();
+
+    let pairs: Vec<(f32, f32)> = match err.rank() {
+        0 => vec![(values[0], values[0]); n],
+        1 if values.len() == 1 => vec![(values[0], values[0]); n],
+        1 => values.iter().map(|v| (*v, *v)).collect(),
+        2 => {
+            assert!(err.rows() == 2,
+                "asymmetric errors must have shape [2, n] {:?}", err.shape()
+            );
+
+            (0..values.len() / 2).map(|i| {
+                (values[i], values[values.len() / 2 + i])
+            }).collect()
+        }
+        _ => panic!("errors must be a scalar, [n] or [2, n] {:?}", err.shape()),
+    };
+
+    assert_eq!(pairs.len(), n, "errors must match the data length {:?}", err.shape());
+    assert!(pairs.iter().all(|(lo, hi)| *lo >= 0. && *hi >= 0.),
+        "errors must be non-negative"
+    );
+
+    pairs
+}
+
+fn build_line(xy: &Tensor) -> Path<Data> {
+    let mut codes = Vec::<PathCode>::new();
+
+    for (i, xy) in xy.iter_row().enumerate() {
+        if i == 0 {
+            codes.push(PathCode::MoveTo(Point(xy[0], xy[1])));
+        } else {
+            codes.push(PathCode::LineTo(Point(xy[0], xy[1])));
+        }
+    }
+
+    Path::new(codes)
+}
+
+fn build_bars(
+    xy: &Tensor,
+    xerr: &Option<Vec<(f32, f32)>>,
+    yerr: &Option<Vec<(f32, f32)>>
+) -> Vec<Path<Data>> {
+    let mut bars = Vec::new();
+
+    for (i, xy) in xy.iter_row().enumerate() {
+        let (x, y) = (xy[0], xy[1]);
+
+        if let Some(xerr) = xerr {
+            let (lo, hi) = xerr[i];
+
+            bars.push(Path::new(vec![
+                PathCode::MoveTo(Point(x - lo, y)),
+                PathCode::LineTo(Point(x + hi, y)),
+            ]));
+        }
+
+        if let Some(yerr) = yerr {
+            let (lo, hi) = yerr[i];
+
+            bars.push(Path::new(vec![
+                PathCode::MoveTo(Point(x, y - lo)),
+                PathCode::LineTo(Point(x, y + hi)),
+            ]));
+        }
+    }
+
+    bars
+}
+
+fn error_bounds(
+    xy: &Tensor,
+    xerr: &Option<Vec<(f32, f32)>>,
+    yerr: &Option<Vec<(f32, f32)>>
+) -> Bounds<Data> {
+    let mut bounds = Bounds::<Data>::from(xy);
+
+    for (i, xy) in xy.iter_row().enumerate() {
+        let (x, y) = (xy[0], xy[1]);
+
+        let (x_lo, x_hi) = xerr.as_ref().map_or((0., 0.), |e| e[i]);
+        let (y_lo, y_hi) = yerr.as_ref().map_or((0., 0.), |e| e[i]);
+
+        bounds = Bounds::from([
+            [bounds.xmin().min(x - x_lo), bounds.ymin().min(y - y_lo)],
+            [bounds.xmax().max(x + x_hi), bounds.ymax().max(y + y_hi)],
+        ]);
+    }
+
+    bounds
+}
+
+impl ArtistDraw<Data> for ErrorBar {
+    fn bounds(&mut self) -> Bounds<Data> {
+        self.bounds.clone()
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let style = self.style.push(style);
+
+        if self.is_line {
+            let line = to_canvas.transform_path(&self.line);
+
+            renderer.draw_path(&line, &style)?;
+        }
+
+        for bar in &self.bars {
+            let bar = to_canvas.transform_path(bar);
+
+            renderer.draw_path(&bar, &style)?;
+        }
+
+        for cap in self.caps(renderer, to_canvas) {
+            renderer.draw_path(&cap, &style)?;
+        }
+
+        if let Some(collection) = &mut self.collection {
+            if let Some(marker) = &self.marker {
+                let style = marker.get_style().push(&style);
+
+                collection.draw(renderer, to_canvas, &style)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Artist<Data> for ErrorBar {
+    type Opt = ErrorBarOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.style = PathStyle::from_config(cfg, "errorbar");
+
+        if let Some(cap_size) = cfg.get_as_type("errorbar", "cap_size") {
+            self.cap_size = cap_size;
+        }
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, ErrorBar>) -> Self::Opt {
+        ErrorBarOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        match &self.label {
+            Some(label) => {
+                let style = self.style.clone();
+                let is_line = self.is_line;
+
+                let handler = LegendHandler::new(label.clone(),
+                move |renderer, top_style, bounds| {
+                    let style = style.push(top_style);
+
+                    if is_line {
+                        let line = Path::<Canvas>::from([
+                            [bounds.xmin(), bounds.ymid()],
+                            [bounds.xmax(), bounds.ymid()],
+                        ]);
+
+                        renderer.draw_path(&line, &style)?;
+                    }
+
+                    let bar = Path::<Canvas>::from([
+                        [bounds.xmid(), bounds.ymin()],
+                        [bounds.xmid(), bounds.ymax()],
+                    ]);
+
+                    renderer.draw_path(&bar, &style)
+                }).points(self.xy.clone());
+
+                match &self.marker {
+                    Some(marker) => Some(handler.marker(
+                        marker.get_path(),
+                        marker.get_style().clone()
+                    )),
+                    None => Some(handler),
+                }
+            },
+            None => None,
+        }
+    }
+}
+
+data_artist_option_struct!(ErrorBarOpt, ErrorBar);
+
+impl ErrorBarOpt {
+    path_style_options!(style);
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.write(|artist| {
+            if label.len() > 0 {
+                artist.label = Some(label.to_string());
+            } else {
+                artist.label = None;
+            }
+        });
+
+        self
+    }
+
+    ///
+    /// Horizontal errors: scalar, per-point `[n]`, or lower/upper `[2, n]`.
+    ///
+    pub fn xerr(&mut self, xerr: impl Into<Tensor>) -> &mut Self {
+        self.write(|artist| {
+            artist.xerr(xerr);
+        });
+
+        self
+    }
+
+    ///
+    /// Vertical errors: scalar, per-point `[n]`, or lower/upper `[2, n]`.
+    ///
+    pub fn yerr(&mut self, yerr: impl Into<Tensor>) -> &mut Self {
+        self.write(|artist| {
+            artist.yerr(yerr);
+        });
+
+        self
+    }
+
+    ///
+    /// Length of the caps at the bar ends in points. Zero hides the caps.
+    ///
+    pub fn cap_size(&mut self, cap_size: f32) -> &mut Self {
+        self.write(|artist| {
+            artist.cap_size = cap_size;
+        });
+
+        self
+    }
+
+    pub fn marker(&mut self, marker: impl IntoMarker) -> &mut Self {
+        self.write(|artist| {
+            artist.marker(marker);
+        });
+
+        self
+    }
+
+    ///
+    /// Joins the points with a line.
+    ///
+    pub fn line(&mut self, is_line: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_line = is_line;
+        });
+
+        self
+    }
+}
+
+impl fmt::Debug for ErrorBar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ErrorBar[{}]", self.xy.rows())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_tensor::{ten, tensor::Tensor};
+
+    use crate::artist::{Artist, Markers};
+
+    use super::{error_pairs, ErrorBar};
+
+    #[test]
+    fn errorbar_error_pairs() {
+        assert_eq!(error_pairs(&Tensor::from(0.5), 2), vec![(0.5, 0.5), (0.5, 0.5)]);
+        assert_eq!(error_pairs(&ten!([1., 2.]), 2), vec![(1., 1.), (2., 2.)]);
+        assert_eq!(
+            error_pairs(&ten!([[1., 2.], [3., 4.]]), 2),
+            vec![(1., 3.), (2., 4.)]
+        );
+    }
+
+    #[test]
+    fn errorbar_bounds() {
+        let mut errorbar = ErrorBar::from_xy(ten!([1., 2.]), ten!([10., 20.]));
+        errorbar.yerr(ten!([[1., 2.], [3., 4.]]));
+        errorbar.xerr(0.5);
+
+        let bounds = errorbar.bounds;
+        assert_eq!(
+            (bounds.xmin(), bounds.ymin(), bounds.xmax(), bounds.ymax()),
+            (0.5, 9., 2.5, 24.)
+        );
+    }
+
+    #[test]
+    fn errorbar_legend() {
+        let mut errorbar = ErrorBar::from_xy(ten!([1., 2.]), ten!([10., 20.]));
+        errorbar.label = Some("errors".to_string());
+        errorbar.marker(Markers::Circle);
+
+        // the legend's Best placement avoids the error bar's points
+        let handler = errorbar.get_legend().unwrap();
+        assert_eq!(handler.get_points(), Some(&ten!([[1., 10.], [2., 20.]])));
+    }
+}
@@ -5,7 +5,7 @@ use essay_graphics::api::{
 use essay_tensor::tensor::Tensor;
 
 use crate::{
-    artist::{Norm, Norms}, 
+    artist::{ColorbarHandler, Norm, Norms}, 
     chart::{Data, LegendHandler}, 
     config::ConfigArc, 
     data_artist_option_struct, 
@@ -13,7 +13,7 @@ use crate::{
     transform::ToCanvas
 };
 
-use super::{Artist, ArtistDraw, ArtistView};
+use super::{mesh_coords::MeshCoords, Artist, ArtistDraw, ArtistView};
 
 pub enum Shading {
     Flat,
@@ -22,7 +22,9 @@ pub enum Shading {
 
 pub struct GridColor {
     data: Tensor,
+    coords: Option<MeshCoords>,
     xy: Tensor,
+    xy_cols: usize,
     color_map: ColorMap,
     shading: Shading,
     norm: Norm,
@@ -38,7 +40,9 @@ impl GridColor {
         
         Self {
             data,
+            coords: None,
             xy: Tensor::from(None),
+            xy_cols: 0,
             color_map: EssayColors::Default.into(),
             shading: Shading::Flat,
             norm: Norm::from(Norms::Linear),
@@ -53,16 +57,111 @@ impl GridColor {
         self.is_stale = true;
     }
 
+    ///
+    /// Mesh coordinates in data space, as 1D or 2D `x` and `y`. With flat
+    /// shading, coordinates one larger than the data are cell corners and
+    /// coordinates the same size as the data are cell centers. Gouraud
+    /// shading requires coordinates the same size as the data. Without
+    /// coordinates, cells are unit squares with the first row at the top.
+    ///
+    pub(crate) fn set_xy(&mut self, x: &Tensor, y: &Tensor) {
+        let coords = MeshCoords::new(x, y);
+
+        self.check_coords(&coords);
+
+        self.coords = Some(coords);
+        self.is_stale = true;
+    }
+
+    ///
+    /// Panics unless the coordinates fit the data shape for the shading.
+    ///
+    fn check_coords(&self, coords: &MeshCoords) {
+        let (rows, cols) = (self.data.rows(), self.data.cols());
+
+        match self.shading {
+            Shading::Flat => {
+                assert!(
+                    coords.shape() == (rows + 1, cols + 1) || coords.shape() == (rows, cols),
+                    "grid x and y must match the data shape or be one larger {:?} data={:?}",
+                    coords.shape(), (rows, cols)
+                );
+            }
+            Shading::Gouraud => {
+                assert!(
+                    coords.shape() == (rows, cols),
+                    "gouraud shading requires x and y to match the data shape {:?} data={:?}",
+                    coords.shape(), (rows, cols)
+                );
+            }
+        }
+    }
+
     pub(crate) fn norm(&mut self, norm: impl Into<Norm>) {
         self.norm = norm.into();
+        self.is_stale = true;
     }
 
     pub(crate) fn color_map(&mut self, color_map: impl Into<ColorMap>) {
         self.color_map = color_map.into();
     }
 
+    fn update(&mut self) {
+        if self.is_stale {
+            self.is_stale = false;
+
+            let (rows, cols) = match self.shading {
+                Shading::Flat => (self.data.rows() + 1, self.data.cols() + 1),
+                Shading::Gouraud => (self.data.rows(), self.data.cols()),
+            };
+
+            let coords = match &self.coords {
+                Some(coords) => Some(self.mesh(coords)),
+                None => None,
+            };
+
+            let mut xy = Vec::<[f32; 2]>::new();
+
+            for j in 0..rows {
+                for i in 0..cols {
+                    match &coords {
+                        Some(coords) => xy.push(coords.point(j, i)),
+                        None => xy.push([i as f32, j as f32]),
+                    }
+                }
+            }
+
+            self.xy = Tensor::from(xy);
+            self.xy_cols = cols;
+            self.norm.set_bounds(&self.data);
+        }
+    }
+
+    ///
+    /// Coordinates of the mesh vertices for the current shading.
+    ///
+    fn mesh(&self, coords: &MeshCoords) -> MeshCoords {
+        let (rows, cols) = (self.data.rows(), self.data.cols());
+
+        match self.shading {
+            Shading::Flat if coords.shape() == (rows, cols) => coords.corners(),
+            _ => {
+                // the data may have changed shape since the coordinates were set
+                self.check_coords(coords);
+
+                coords.clone()
+            }
+        }
+    }
+
     pub(crate) fn shading(&mut self, shading: impl Into<Shading>) {
         self.shading = shading.into();
+
+        if let Some(coords) = &self.coords {
+            self.check_coords(coords);
+        }
+
+        self.is_stale = true;
     }
 
     fn draw_solid_shading(
@@ -71,17 +170,15 @@ impl GridColor {
         to_canvas: &ToCanvas<Data>,
         _style: &dyn PathOpt,
     ) -> Result<()> {
-        let pos = ui.pos();
         let xy = to_canvas.transform_tensor(&self.xy);
 
-        let norm = self.data.normalize_unit();
+        let data = &self.data;
         
         let cmap = &self.color_map;
 
-        let (rows, cols) = (norm.rows(), norm.cols());
+        let (rows, cols) = (data.rows(), data.cols());
 
-        let j_stride = cols + 1;
-        let ymax = pos.ymax();
+        let j_stride = self.xy_cols;
 
         let mut mesh = Mesh2dColor::new();
 
@@ -90,8 +187,16 @@ impl GridColor {
                 let index = j * j_stride + i;
                 let x00 = xy[(index, 0)];
                 let y00 = xy[(index, 1)];
-                // reverse y 
-                let c00 = cmap.map(norm[((rows - 1 - j), i)]);
+                // reverse y unless the rows have explicit coordinates
+                let row = if self.coords.is_some() { j } else { rows - 1 - j };
+                let value = self.norm.norm(data[(row, i)]);
+
+                // masked cells, such as zero counts on a log scale
+                if ! value.is_finite() {
+                    continue;
+                }
+
+                let c00 = cmap.map(value.clamp(0., 1.));
 
                 let index = j * j_stride + i + 1;
                 let x01 = xy[(index, 0)];
@@ -130,13 +235,14 @@ impl GridColor {
     ) -> Result<()> {
         let xy = to_canvas.transform_tensor(&self.xy);
 
-        let norm = self.data.normalize_unit();
+        let norm = &self.data;
+        let unit = |v: f32| self.norm.norm(v).clamp(0., 1.);
         
         let cmap = &self.color_map;
 
         let (rows, cols) = (norm.rows(), norm.cols());
 
-        let j_stride = cols + 1;
+        let j_stride = self.xy_cols;
 
         let mut mesh = Mesh2dColor::new();
 
@@ -145,22 +251,22 @@ impl GridColor {
                 let index = j * j_stride + i;
                 let x00 = xy[(index, 0)];
                 let y00 = xy[(index, 1)];
-                let c00 = cmap.map(norm[(j, i)]);
+                let c00 = cmap.map(unit(norm[(j, i)]));
                 
                 let index = j * j_stride + i + 1;
                 let x01 = xy[(index, 0)];
                 let y01 = xy[(index, 1)];
-                let c01 = cmap.map(norm[(j, i + 1)]);
+                let c01 = cmap.map(unit(norm[(j, i + 1)]));
 
                 let index = (j + 1) * j_stride + i;
                 let x10 = xy[(index, 0)];
                 let y10 = xy[(index, 1)];
-                let c10 = cmap.map(norm[(j + 1, i)]);
+                let c10 = cmap.map(unit(norm[(j + 1, i)]));
 
                 let index = (j + 1) * j_stride + i + 1;
                 let x11 = xy[(index, 0)];
                 let y11 = xy[(index, 1)];
-                let c11 = cmap.map(norm[(j + 1, i + 1)]);
+                let c11 = cmap.map(unit(norm[(j + 1, i + 1)]));
 
                 mesh.triangle(
                     ([x00, y00], c00),
@@ -182,11 +288,18 @@ impl GridColor {
     pub(crate) fn set_norm(&mut self, min: f32, max: f32) {
         self.norm.set_vmin(min);
         self.norm.set_vmax(max);
+        self.is_stale = true;
     }
 }
 
 impl ArtistDraw<Data> for GridColor {
     fn bounds(&mut self) -> Bounds<Data> {
+        self.update();
+
+        if self.coords.is_some() {
+            return Bounds::from(&self.xy);
+        }
+
         let (rows, cols) = match self.shading {
             Shading::Gouraud => (self.data.rows() - 1, self.data.cols() - 1),
             Shading::Flat => (self.data.rows(), self.data.cols())
@@ -204,21 +317,7 @@ impl ArtistDraw<Data> for GridColor {
         to_canvas: &ToCanvas<Data>,
         style: &dyn PathOpt,
     ) -> Result<()> {
-        if self.is_stale {
-            self.is_stale = false;
-
-            let mut xy = Vec::<[f32; 2]>::new();
-            let (rows, cols) = (self.data.rows(), self.data.cols());
-
-            for j in 0..rows + 1 {
-                for i in 0..cols + 1 {
-                    xy.push([i as f32, j as f32]);
-                }
-            }
-
-            self.xy = Tensor::from(xy);
-            self.norm.set_bounds(&self.data);
-        }
+        self.update();
 
         match self.shading {
             Shading::Gouraud => {
@@ -245,6 +344,10 @@ impl Artist<Data> for GridColor {
     fn get_legend(&self) -> Option<LegendHandler> {
         None
     }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        Some(ColorbarHandler::from_norm(self.color_map.clone(), &self.norm))
+    }
 }
 
 data_artist_option_struct!(GridColorOpt, GridColor);
@@ -282,10 +385,9 @@ impl GridColorOpt {
 
     pub fn shading(&mut self, shading: Shading) -> &mut Self {
         self.write(|artist| {
-        artist.shading = shading;
-        artist.is_stale = true;
-    });
+            artist.shading(shading);
+        });
 
-    self
-}
+        self
+    }
 }
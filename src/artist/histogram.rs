@@ -1,21 +1,45 @@
-use essay_graphics::api::{renderer::{Canvas, Renderer, Result}, Bounds, Path, PathOpt};
-use essay_tensor::{stats::HistArgs, tensor::Tensor};
+use essay_graphics::api::{renderer::{Canvas, Renderer, Result}, Bounds, Path, PathCode, PathOpt, Point};
+use essay_tensor::tensor::Tensor;
 
 use crate::{
-    artist::Stale, chart::{Data, LegendHandler}, config::{ConfigArc, PathStyle}, data_artist_option_struct, path_style_options, transform::ToCanvas
+    artist::Stale, chart::{Data, LegendHandler}, config::{ConfigArc, PathStyle, StyleCycle}, data_artist_option_struct, path_style_options, transform::ToCanvas
 };
 
-use super::{paths, Artist, ArtistDraw, ArtistView};
+use super::{paths, Artist, ArtistDraw, ArtistView, Orientation};
+
+///
+/// How the histogram's bins are drawn.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HistType {
+    /// Filled rectangle per bin
+    Bar,
+    /// Unfilled outline of the bin tops
+    Step,
+    /// Filled outline of the bin tops
+    StepFilled,
+}
 
 pub struct Histogram {
-    data: Tensor,
+    data: Vec<Tensor>,
+    weights: Option<Tensor>,
+
     style: PathStyle,
+    cycle: StyleCycle,
 
     n_bins: Option<usize>,
-    bins: Tensor,
-    count: Tensor,
+    edges: Option<Tensor>,
+    range: Option<(f32, f32)>,
+    is_density: bool,
+    is_cumulative: bool,
+    is_stacked: bool,
+    hist_type: HistType,
+    orientation: Orientation,
+
+    bins: Vec<f32>,
+    count: Vec<Vec<f32>>,
     extent: Bounds<Data>,
-    paths: Vec<Path<Data>>,
+    paths: Vec<Vec<Path<Data>>>,
 
     stale: Stale,
 }
@@ -26,12 +50,32 @@ impl Histogram {
 
         assert!(data.rank() == 1, "histogram requires 1D value {:?}", data.shape());
 
+        Self::from_list(vec![data])
+    }
+
+    ///
+    /// Histogram of several datasets sharing the same bins.
+    ///
+    pub fn from_list(data: Vec<Tensor>) -> Self {
+        for item in &data {
+            assert!(item.rank() == 1, "histogram requires 1D value {:?}", item.shape());
+        }
+
         let mut histogram = Self {
             data,
+            weights: None,
             style: PathStyle::new(),
+            cycle: StyleCycle::new(),
             n_bins: None,
-            bins: Tensor::zeros([1]),
-            count: Tensor::zeros([1]),
+            edges: None,
+            range: None,
+            is_density: false,
+            is_cumulative: false,
+            is_stacked: false,
+            hist_type: HistType::Bar,
+            orientation: Orientation::Vertical,
+            bins: Vec::new(),
+            count: Vec::new(),
             extent: Bounds::<Data>::none(),
             paths: Vec::new(),
             stale: Stale::stale(),
@@ -42,46 +86,271 @@ impl Histogram {
         histogram
     }
 
-    pub(crate) fn set_data(&mut self, data: Tensor) {
-        assert!(data.rank() == 1, "histogram requires 1D value {:?}", data.shape());
+    pub(crate) fn set_data(&mut self, data: Vec<Tensor>) {
+        for item in &data {
+            assert!(item.rank() == 1, "histogram requires 1D value {:?}", item.shape());
+        }
+
+        if let Some(weights) = &self.weights {
+            check_weights(&data, weights);
+        }
 
         self.data = data;
         self.stale = Stale::stale();
     }
 
+    pub(crate) fn set_weights(&mut self, weights: Tensor) {
+        check_weights(&self.data, &weights);
+
+        self.weights = Some(weights);
+        self.stale = Stale::stale();
+    }
+
+    fn weights(&self, i: usize) -> Option<&[f32]> {
+        match &self.weights {
+            None => None,
+            Some(weights) if weights.rank() == 1 => Some(weights.as_slice()),
+            Some(weights) => {
+                let cols = weights.cols();
+
+                Some(&weights.as_slice()[i * cols..(i + 1) * cols])
+            }
+        }
+    }
+
     fn update_bounds(&mut self) {
         if self.stale.is_stale() {
             self.stale = Stale::new_for_update();
 
-            let hist_args: HistArgs = if let Some(n_bins) = self.n_bins {
-                n_bins.into()
+            self.bins = match &self.edges {
+                Some(edges) => Vec::from(edges.as_slice()),
+                None => {
+                    let data: Vec<&[f32]> = self.data.iter().map(|d| d.as_slice()).collect();
+
+                    bin_edges(&data, self.n_bins.unwrap_or(10), self.range)
+                }
+            };
+
+            let mut counts = Vec::<Vec<f32>>::new();
+
+            for (i, item) in self.data.iter().enumerate() {
+                counts.push(bin_counts(item.as_slice(), self.weights(i), &self.bins));
+            }
+
+            self.count = bin_values(
+                counts,
+                &self.bins,
+                self.is_density,
+                self.is_cumulative,
+                self.is_stacked
+            );
+
+            self.update_paths();
+        }
+    }
+
+    fn update_paths(&mut self) {
+        let n_data = self.count.len();
+        let n_bins = self.bins.len() - 1;
+
+        let mut bottom = vec![0.; n_bins];
+        let mut paths = Vec::<Vec<Path<Data>>>::new();
+        let mut c_max = 0f32;
+
+        for (i, count) in self.count.iter().enumerate() {
+            let mut data_paths = Vec::<Path<Data>>::new();
+
+            let top: Vec<f32> = if self.is_stacked {
+                bottom.iter().zip(count).map(|(b, c)| b + c).collect()
             } else {
-                ().into()
+                count.clone()
             };
 
-            let (count, bins) = essay_tensor::stats::histogram(&self.data, hist_args);
+            match self.hist_type {
+                HistType::Bar => {
+                    for j in 0..n_bins {
+                        let (mut x0, mut x1) = (self.bins[j], self.bins[j + 1]);
+
+                        if ! self.is_stacked && n_data > 1 {
+                            // side-by-side bars within each bin
+                            let w = (x1 - x0) / n_data as f32;
+                            x0 = x0 + i as f32 * w;
+                            x1 = x0 + w;
+                        }
+
+                        data_paths.push(paths::rect(
+                            self.orient(x0, bottom[j]),
+                            self.orient(x1, top[j]),
+                        ));
+                    }
+                }
+                HistType::Step | HistType::StepFilled => {
+                    data_paths.push(self.step_path(&bottom, &top));
+                }
+            }
+
+            c_max = top.iter().fold(c_max, |a, b| a.max(*b));
+
+            if self.is_stacked {
+                bottom = top;
+            }
+
+            paths.push(data_paths);
+        }
+
+        let (min, max) = (self.bins[0], self.bins[n_bins]);
+
+        self.extent = match self.orientation {
+            Orientation::Vertical => Bounds::new([min, 0.], [max, c_max]),
+            Orientation::Horizontal => Bounds::new([0., min], [c_max, max]),
+        };
+
+        self.paths = paths;
+    }
+
+    ///
+    /// Outline along the bin tops. A filled step closes back along the
+    /// bottom, which is the previous dataset's top when stacked.
+    ///
+    fn step_path(&self, bottom: &[f32], top: &[f32]) -> Path<Data> {
+        let n = top.len();
+        let bins = &self.bins;
+
+        let mut codes = vec![PathCode::MoveTo(self.orient(bins[0], bottom[0]))];
+
+        for j in 0..n {
+            codes.push(PathCode::LineTo(self.orient(bins[j], top[j])));
+            codes.push(PathCode::LineTo(self.orient(bins[j + 1], top[j])));
+        }
+
+        if self.hist_type == HistType::Step {
+            codes.push(PathCode::LineTo(self.orient(bins[n], bottom[n - 1])));
+        } else {
+            for j in (0..n).rev() {
+                codes.push(PathCode::LineTo(self.orient(bins[j + 1], bottom[j])));
+                codes.push(PathCode::LineTo(self.orient(bins[j], bottom[j])));
+            }
+
+            if let Some(PathCode::LineTo(point)) = codes.pop() {
+                codes.push(PathCode::ClosePoly(point));
+            }
+        }
+
+        Path::new(codes)
+    }
+
+    fn orient(&self, x: f32, y: f32) -> Point {
+        match self.orientation {
+            Orientation::Vertical => Point(x, y),
+            Orientation::Horizontal => Point(y, x),
+        }
+    }
+}
+
+///
+/// `n_bins` equal-width bin edges covering the range or the data.
+///
+pub(crate) fn bin_edges(data: &[&[f32]], n_bins: usize, range: Option<(f32, f32)>) -> Vec<f32> {
+    let (mut min, mut max) = match range {
+        Some(range) => range,
+        None => {
+            let mut min = f32::MAX;
+            let mut max = f32::MIN;
+
+            for v in data.iter().flat_map(|d| d.iter()).filter(|v| v.is_finite()) {
+                min = min.min(*v);
+                max = max.max(*v);
+            }
 
-            self.count = count;
-            self.bins = bins;
+            if min > max { (0., 1.) } else { (min, max) }
+        }
+    };
+
+    if min == max {
+        min -= 0.5;
+        max += 0.5;
+    }
+
+    let step = (max - min) / n_bins as f32;
+
+    (0..=n_bins).map(|i| {
+        if i == n_bins { max } else { min + i as f32 * step }
+    }).collect()
+}
+
+///
+/// Weighted count for each bin. Bins are half-open except the last, which
+/// includes its right edge. Values outside the edges are dropped.
+///
+fn bin_counts(data: &[f32], weights: Option<&[f32]>, edges: &[f32]) -> Vec<f32> {
+    assert!(edges.len() >= 2, "histogram requires at least two bin edges");
+
+    let n = edges.len() - 1;
+    let mut counts = vec![0.; n];
+
+    for (i, v) in data.iter().enumerate() {
+        if let Some(bin) = bin_index(edges, *v) {
+            counts[bin] += weights.map_or(1., |w| w[i]);
+        }
+    }
 
-            let (min, max) = (self.bins[0], self.bins[self.bins.len() - 1]);
-            let c_max = self.count.reduce_max()[0];
+    counts
+}
 
-            self.extent = Bounds::new([min, 0.], [max, c_max]);
+///
+/// Bin containing the value, using the same edge rules as `bin_counts`.
+///
+pub(crate) fn bin_index(edges: &[f32], value: f32) -> Option<usize> {
+    let n = edges.len() - 1;
 
-            let mut paths = Vec::<Path<Data>>::new();
+    if ! value.is_finite() || value < edges[0] || value > edges[n] {
+        None
+    } else {
+        Some(edges.partition_point(|e| *e <= value).saturating_sub(1).min(n - 1))
+    }
+}
 
-            for i in 0..self.count.len() {
-                paths.push(paths::rect(
-                    [self.bins[i], 0.], 
-                    [self.bins[i + 1], self.count[i]]
-                ));
+///
+/// Converts raw counts to densities and cumulative sums. Stacked densities
+/// are normalized by the combined total so the stack integrates to one.
+///
+fn bin_values(
+    mut counts: Vec<Vec<f32>>,
+    edges: &[f32],
+    is_density: bool,
+    is_cumulative: bool,
+    is_stacked: bool,
+) -> Vec<Vec<f32>> {
+    if is_density {
+        let all_total: f32 = counts.iter().flat_map(|c| c.iter()).sum();
+
+        for count in counts.iter_mut() {
+            let total: f32 = if is_stacked { all_total } else { count.iter().sum() };
+
+            if total > 0. {
+                for (j, c) in count.iter_mut().enumerate() {
+                    *c /= total;
+
+                    if ! is_cumulative {
+                        *c /= edges[j + 1] - edges[j];
+                    }
+                }
             }
+        }
+    }
+
+    if is_cumulative {
+        for count in counts.iter_mut() {
+            let mut sum = 0.;
 
-            self.paths = paths;
+            for c in count.iter_mut() {
+                sum += *c;
+                *c = sum;
+            }
         }
     }
-    
+
+    counts
 }
 
 impl ArtistDraw<Data> for Histogram {
@@ -92,7 +361,7 @@ impl ArtistDraw<Data> for Histogram {
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         renderer: &mut dyn Renderer,
         to_canvas: &ToCanvas<Data>,
         style: &dyn PathOpt,
@@ -100,10 +369,17 @@ impl ArtistDraw<Data> for Histogram {
         self.update_bounds();
 
         let style = self.style.push(style);
+        let n = self.paths.len();
 
-        for path in &self.paths {
-            let c_path: Path<Canvas> = to_canvas.transform_path(path);
-            renderer.draw_path(&c_path, &style)?;
+        for (i, paths) in self.paths.iter().enumerate() {
+            // a single dataset keeps the chart's color
+            let cycle_style = self.cycle.push(&style, i, n);
+            let style: &dyn PathOpt = if n > 1 { &cycle_style } else { &style };
+
+            for path in paths {
+                let c_path: Path<Canvas> = to_canvas.transform_path(path);
+                renderer.draw_path(&c_path, style)?;
+            }
         }
 
         Ok(())
@@ -115,6 +391,7 @@ impl Artist<Data> for Histogram {
 
     fn config(&mut self, cfg: &ConfigArc) {
         self.style = PathStyle::from_config(cfg, "histogram");
+        self.cycle = StyleCycle::from_config(cfg, "histogram.cycle");
     }
 
     fn opt(&mut self, view: ArtistView<Data, Histogram>) -> Self::Opt {
@@ -136,6 +413,107 @@ impl HistogramOpt {
 
         self.write(|artist| {
             artist.n_bins = Some(n_bins);
+            artist.edges = None;
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    ///
+    /// Explicit, increasing bin edges. Overrides `n_bins` and `range`.
+    ///
+    pub fn bins(&mut self, edges: impl Into<Tensor>) -> &mut Self {
+        let edges = edges.into();
+
+        assert!(edges.rank() == 1 && edges.len() >= 2, "bins requires 1D edges {:?}", edges.shape());
+        assert!(
+            edges.as_slice().windows(2).all(|w| w[0] < w[1]),
+            "bin edges must be increasing"
+        );
+
+        self.write(|artist| {
+            artist.edges = Some(edges);
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    ///
+    /// Lower and upper range of the bins. Values outside are ignored.
+    ///
+    pub fn range(&mut self, min: f32, max: f32) -> &mut Self {
+        assert!(min < max, "histogram range requires min < max ({}, {})", min, max);
+
+        self.write(|artist| {
+            artist.range = Some((min, max));
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    ///
+    /// Weight of each value. 1D weights match a single dataset; 2D weights
+    /// have a row for each dataset.
+    ///
+    pub fn weights(&mut self, weights: impl Into<Tensor>) -> &mut Self {
+        let weights = weights.into();
+
+        self.write(|artist| {
+            artist.set_weights(weights);
+        });
+
+        self
+    }
+
+    ///
+    /// Normalizes the counts so the histogram integrates to one.
+    ///
+    pub fn density(&mut self, is_density: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_density = is_density;
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    pub fn cumulative(&mut self, is_cumulative: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_cumulative = is_cumulative;
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    ///
+    /// Stacks each dataset on the previous ones instead of placing their
+    /// bars side by side.
+    ///
+    pub fn stacked(&mut self, is_stacked: bool) -> &mut Self {
+        self.write(|artist| {
+            artist.is_stacked = is_stacked;
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    pub fn hist_type(&mut self, hist_type: HistType) -> &mut Self {
+        self.write(|artist| {
+            artist.hist_type = hist_type;
+            artist.stale = Stale::stale();
+        });
+
+        self
+    }
+
+    pub fn orientation(&mut self, orientation: Orientation) -> &mut Self {
+        self.write(|artist| {
+            artist.orientation = orientation;
             artist.stale = Stale::stale();
         });
 
@@ -144,12 +522,71 @@ impl HistogramOpt {
 
     pub fn data(&mut self, data: impl Into<Tensor>) -> &mut Self {
         let data = data.into();
-        assert!(data.rank() == 2, "Histogram data must be 1D. Shape={:?}", data.shape());
+        assert!(data.rank() == 1, "Histogram data must be 1D. Shape={:?}", data.shape());
 
         self.write(|artist| {
-            artist.set_data(data);
+            artist.set_data(vec![data]);
         });
 
         self
     }
 }
+
+///
+/// Panics unless the weights match the data: 1D weights for a single
+/// dataset, or one row per dataset.
+///
+fn check_weights(data: &[Tensor], weights: &Tensor) {
+    match weights.rank() {
+        1 => {
+            assert!(data.len() == 1, "1D weights require a single dataset");
+            assert_eq!(weights.len(), data[0].len(), "weights must match the data length");
+        }
+        2 => {
+            assert!(weights.rows() == data.len(), "weights rows must match the datasets");
+
+            for item in data {
+                assert_eq!(weights.cols(), item.len(), "weights must match the data length");
+            }
+        }
+        _ => panic!("weights must be 1D or 2D {:?}", weights.shape()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bin_counts, bin_edges, bin_values};
+
+    #[test]
+    fn histogram_bin_edges() {
+        assert_eq!(bin_edges(&[&[0., 4., 2.]], 4, None), vec![0., 1., 2., 3., 4.]);
+        assert_eq!(bin_edges(&[&[0., 4.]], 2, Some((-2., 2.))), vec![-2., 0., 2.]);
+        assert_eq!(bin_edges(&[&[1., 1.]], 1, None), vec![0.5, 1.5]);
+        assert_eq!(bin_edges(&[&[0.], &[2.]], 2, None), vec![0., 1., 2.]);
+    }
+
+    #[test]
+    fn histogram_bin_counts() {
+        let edges = [0., 1., 2., 4.];
+
+        assert_eq!(bin_counts(&[0., 0.5, 1., 3., 4., 5., -1.], None, &edges), vec![2., 1., 2.]);
+        assert_eq!(bin_counts(&[0.5, 1.5, 1.5], Some(&[2., 1., 3.]), &edges), vec![2., 4., 0.]);
+        assert_eq!(bin_counts(&[f32::NAN, 1.], None, &edges), vec![0., 1., 0.]);
+    }
+
+    #[test]
+    fn histogram_bin_values() {
+        let edges = [0., 1., 2., 4.];
+
+        let counts = vec![vec![2., 2., 4.]];
+        assert_eq!(bin_values(counts.clone(), &edges, true, false, false), vec![vec![0.25, 0.25, 0.25]]);
+        assert_eq!(bin_values(counts.clone(), &edges, false, true, false), vec![vec![2., 4., 8.]]);
+        assert_eq!(bin_values(counts, &edges, true, true, false), vec![vec![0.25, 0.5, 1.]]);
+
+        let counts = vec![vec![1., 1., 0.], vec![0., 1., 1.]];
+        assert_eq!(
+            bin_values(counts, &edges, true, false, true),
+            vec![vec![0.25, 0.25, 0.], vec![0., 0.25, 0.125]]
+        );
+    }
+}
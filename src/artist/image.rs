@@ -1,5 +1,5 @@
 use essay_graphics::api::{
-    renderer::{Canvas, Renderer, Result}, Bounds, PathOpt, Point
+    renderer::{Renderer, Result}, Bounds, Mesh2dColor, PathOpt, Point
 };
 use essay_tensor::tensor::Tensor;
 
@@ -78,34 +78,63 @@ impl ArtistDraw<Data> for Image {
         to_canvas: &ToCanvas<Data>,
         _style: &dyn PathOpt,
     ) -> Result<()> {
-        //let to_canvas = to_canvas.translate(0., self.).scale(1., -1.);
         let extent = self.bounds();
-        let bounds = Bounds::<Canvas>::new(
-            to_canvas.transform_point(extent.p0()),
-            to_canvas.transform_point(extent.p1()),
-        );
-    
+        let p0 = to_canvas.transform_point(extent.p0());
+        let p1 = to_canvas.transform_point(extent.p1());
+
+        let (rows, cols) = (self.data.rows(), self.data.cols());
+
+        // the renderer has no image call, so each cell is two triangles,
+        // but never more cells than the device pixels the image covers
+        let (n_rows, n_cols) = sample_size(rows, cols, p1.y() - p0.y(), p1.x() - p0.x());
+        let (w, h) = ((p1.x() - p0.x()) / n_cols as f32, (p1.y() - p0.y()) / n_rows as f32);
+
         let norm = &self.norm;
         let colormap = &self.color_map;
-    
-        let mut colors = Vec::<u8>::new();
-        for v in self.data.iter() {
-            let v = norm.norm(*v);
-            let color = colormap.map(v);
-            colors.push(color.r8());
-            colors.push(color.g8());
-            colors.push(color.b8());
-            colors.push(color.a8());
+
+        // a regular grid, which the vector backends embed as one image
+        let mut mesh = Mesh2dColor::new();
+
+        for j in 0..n_rows {
+            // nearest data cell to the sample's center
+            let row = (2 * j + 1) * rows / (2 * n_rows);
+
+            for i in 0..n_cols {
+                let col = (2 * i + 1) * cols / (2 * n_cols);
+
+                let value = norm.norm(self.data[(row, col)]);
+
+                if ! value.is_finite() {
+                    continue;
+                }
+
+                let color = colormap.map(value.clamp(0., 1.));
+
+                // the first row is at the top
+                let (x0, x1) = (p0.x() + i as f32 * w, p0.x() + (i + 1) as f32 * w);
+                let (y0, y1) = (p1.y() - (j + 1) as f32 * h, p1.y() - j as f32 * h);
+
+                mesh.triangle(([x0, y0], color), ([x1, y0], color), ([x1, y1], color));
+                mesh.triangle(([x0, y0], color), ([x1, y1], color), ([x0, y1], color));
+            }
         }
-    
-        // todo [width, height, 4]
-        let colors = Tensor::from(colors).reshape([self.data.rows(), self.data.cols(), 4]);
-    
-        // renderer.draw_image(bounds, &colors)
-        todo!()
+
+        renderer.draw_mesh2d_color(&mesh)
     }
 }
 
+///
+/// Rows and columns to sample from a `rows` by `cols` image drawn
+/// `height` by `width` device pixels: the data's, or one per pixel for
+/// larger images.
+///
+fn sample_size(rows: usize, cols: usize, height: f32, width: f32) -> (usize, usize) {
+    let max_rows = height.abs().ceil().max(1.) as usize;
+    let max_cols = width.abs().ceil().max(1.) as usize;
+
+    (rows.min(max_rows), cols.min(max_cols))
+}
+
 impl Artist<Data> for Image {
     type Opt = ImageOpt;
 
@@ -159,3 +188,18 @@ impl ImageOpt {
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::sample_size;
+
+    #[test]
+    fn image_sample_size() {
+        // small images keep their cells
+        assert_eq!(sample_size(10, 20, 300., 400.), (10, 20));
+
+        // large images draw at most one cell per device pixel
+        assert_eq!(sample_size(4000, 3000, 300., 400.5), (300, 401));
+        assert_eq!(sample_size(4000, 3000, -300., 0.), (300, 1));
+    }
+}
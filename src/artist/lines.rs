@@ -235,7 +235,7 @@ impl Artist<Data> for Lines2d {
         match &self.label {
             Some(label) => {
                 let style = self.style.clone();
-                Some(LegendHandler::new(label.clone(), 
+                let handler = LegendHandler::new(label.clone(), 
                 move |renderer, top_style, bounds| {
                     let line = Path::<Canvas>::from([
                         [bounds.xmin(), bounds.ymid()],
@@ -245,7 +245,15 @@ impl Artist<Data> for Lines2d {
                         &line, 
                         &style.push(top_style), 
                     )
-                }))
+                }).points(self.xy.clone());
+
+                match &self.marker {
+                    Some(marker) => Some(handler.marker(
+                        marker.get_path(),
+                        marker.get_style().clone()
+                    )),
+                    None => Some(handler),
+                }
             },
             None => None,
         }
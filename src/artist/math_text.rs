@@ -0,0 +1,938 @@
+use essay_graphics::api::{
+    renderer::{Renderer, Result},
+    PathOpt, Point, TextStyle
+};
+
+use crate::backend;
+
+use super::text_layout::{RotationMode, TextBBox, TextLayout};
+
+///
+/// Draws text that may contain several lines and TeX-style math between
+/// `$` pairs, such as `"$\omega$ (rad/s)"` or `"$10^{-3}$"`. A single
+/// line without math is passed directly to the renderer.
+///
+/// The math is a TeX subset: sub- and superscripts, `\frac`, `\sqrt`,
+/// Greek letters and common symbols, spacing commands, and the font
+/// switches `\mathrm`, `\mathit`, `\mathbf`, `\mathsf`, `\mathtt`,
+/// `\mathcal` and `\mathbb`, which map letters to the Unicode math
+/// alphabets. Letters are upright unless switched.
+///
+pub(crate) fn draw_text(
+    renderer: &mut dyn Renderer,
+    pos: Point,
+    text: &str,
+    angle: f32,
+    style: &dyn PathOpt,
+    text_style: &TextStyle,
+) -> Result<()> {
+    if ! text.contains('$') && ! text.contains('\n') {
+        return renderer.draw_text(pos, text, angle, style, text_style);
+    }
+
+    let layout = TextLayout::new(renderer, text, text_style, TextLayout::LINE_SPACING);
+
+    layout.draw(
+        renderer,
+        pos,
+        angle,
+        RotationMode::Anchor,
+        style,
+        text_style,
+        &TextBBox::new(),
+    )
+}
+
+///
+/// Laid out width of the widest line in em, measured with the font the
+/// backends draw with.
+///
+pub(crate) fn text_width(text: &str) -> f32 {
+    text.split('\n')
+        .map(|line| layout_line(line).width)
+        .fold(0., f32::max)
+}
+
+///
+/// A single line of text and math in em, relative to its baseline origin.
+///
+pub(super) fn layout_line(text: &str) -> Layout {
+    if text.contains('$') {
+        layout_node(&parse(text), 1.)
+    } else {
+        layout_node(&Node::Glyphs(text.to_string()), 1.)
+    }
+}
+
+const ASCENT: f32 = 0.72;
+const DESCENT: f32 = 0.21;
+const SCRIPT_SCALE: f32 = 0.7;
+const FRAC_SCALE: f32 = 0.85;
+const MIN_SCALE: f32 = 0.5;
+const AXIS: f32 = 0.3;
+const RULE: f32 = 0.06;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MathFont {
+    Roman,
+    Italic,
+    Bold,
+    Sans,
+    Mono,
+    Cal,
+    BlackBoard,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Glyphs(String),
+    Space(f32),
+    Group(Vec<Node>),
+    Scripts {
+        base: Box<Node>,
+        sub: Option<Box<Node>>,
+        sup: Option<Box<Node>>,
+    },
+    Frac(Box<Node>, Box<Node>),
+    Sqrt(Box<Node>, Option<Box<Node>>),
+}
+
+///
+/// Plain text with math segments between unescaped `$` pairs. An unpaired
+/// `$` is kept as text.
+///
+fn parse(text: &str) -> Node {
+    let chars: Vec<char> = text.chars().collect();
+
+    let mut nodes = Vec::<Node>::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if chars.get(i + 1) == Some(&'$') => {
+                plain.push('$');
+                i += 2;
+            }
+            '$' => match math_end(&chars, i + 1) {
+                Some(end) => {
+                    if plain.len() > 0 {
+                        nodes.push(Node::Glyphs(plain.clone()));
+                        plain.clear();
+                    }
+
+                    let mut parser = MathParser::new(&chars[i + 1..end]);
+                    nodes.push(parser.parse_list(MathFont::Roman, None));
+
+                    i = end + 1;
+                }
+                None => {
+                    plain.push('$');
+                    i += 1;
+                }
+            }
+            ch => {
+                plain.push(ch);
+                i += 1;
+            }
+        }
+    }
+
+    if plain.len() > 0 {
+        nodes.push(Node::Glyphs(plain));
+    }
+
+    group(nodes)
+}
+
+fn math_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut i = start;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '$' => return Some(i),
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+fn group(mut nodes: Vec<Node>) -> Node {
+    if nodes.len() == 1 {
+        nodes.pop().unwrap()
+    } else {
+        Node::Group(nodes)
+    }
+}
+
+enum Command {
+    Atom(Node),
+    Font(MathFont),
+}
+
+struct MathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl MathParser {
+    fn new(chars: &[char]) -> Self {
+        Self {
+            chars: Vec::from(chars),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|ch| *ch)
+    }
+
+    fn skip_space(&mut self) {
+        while let Some(ch) = self.peek() {
+            if ! ch.is_whitespace() {
+                break;
+            }
+
+            self.pos += 1;
+        }
+    }
+
+    ///
+    /// Atoms up to the `end` character or the end of the math, with TeX's
+    /// spacing around binary operators and relations.
+    ///
+    fn parse_list(&mut self, font: MathFont, end: Option<char>) -> Node {
+        let mut font = font;
+        let mut nodes = Vec::<Node>::new();
+
+        while let Some(ch) = self.peek() {
+            if Some(ch) == end {
+                self.pos += 1;
+                break;
+            }
+
+            match ch {
+                '}' => {
+                    // unbalanced close brace
+                    self.pos += 1;
+                }
+                '^' | '_' => {
+                    self.pos += 1;
+
+                    let arg = self.parse_arg(font);
+                    let base = nodes.pop().unwrap_or(Node::Group(Vec::new()));
+
+                    nodes.push(attach_script(base, ch == '^', arg));
+                }
+                ch if ch.is_whitespace() => {
+                    self.pos += 1;
+                }
+                '\\' => {
+                    let after_operand = nodes.last().map_or(false, is_operand);
+
+                    match self.parse_command(font, after_operand) {
+                        Command::Atom(node) => nodes.push(node),
+                        Command::Font(new_font) => font = new_font,
+                    }
+                }
+                _ => {
+                    let after_operand = nodes.last().map_or(false, is_operand);
+
+                    nodes.push(self.parse_char(font, after_operand));
+                }
+            }
+        }
+
+        group(nodes)
+    }
+
+    ///
+    /// A script, fraction or root argument: a braced group or a single atom.
+    ///
+    fn parse_arg(&mut self, font: MathFont) -> Node {
+        self.skip_space();
+
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                self.parse_list(font, Some('}'))
+            }
+            Some('\\') => match self.parse_command(font, false) {
+                Command::Atom(node) => node,
+                Command::Font(_) => Node::Group(Vec::new()),
+            }
+            Some(_) => self.parse_char(font, false),
+            None => Node::Group(Vec::new()),
+        }
+    }
+
+    fn parse_char(&mut self, font: MathFont, is_operand: bool) -> Node {
+        let ch = self.peek().unwrap();
+        self.pos += 1;
+
+        match ch {
+            '{' => self.parse_list(font, Some('}')),
+            '+' => binary("+", is_operand),
+            '-' => binary("\u{2212}", is_operand),
+            '*' => binary("\u{2217}", is_operand),
+            '=' | '<' | '>' => relation(&ch.to_string()),
+            ',' | ';' => Node::Group(vec![
+                Node::Glyphs(ch.to_string()),
+                Node::Space(3. / 18.),
+            ]),
+            '\'' => Node::Glyphs("\u{2032}".to_string()),
+            _ => Node::Glyphs(font_char(ch, font).to_string()),
+        }
+    }
+
+    fn parse_name(&mut self) -> String {
+        let mut name = String::new();
+
+        match self.peek() {
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                while let Some(ch) = self.peek() {
+                    if ! ch.is_ascii_alphabetic() {
+                        break;
+                    }
+
+                    name.push(ch);
+                    self.pos += 1;
+                }
+            }
+            Some(ch) => {
+                name.push(ch);
+                self.pos += 1;
+            }
+            None => {}
+        }
+
+        name
+    }
+
+    ///
+    /// Braced text mode content, with spaces kept.
+    ///
+    fn parse_text(&mut self) -> Node {
+        self.skip_space();
+
+        if self.peek() != Some('{') {
+            return Node::Group(Vec::new());
+        }
+
+        self.pos += 1;
+
+        let mut text = String::new();
+        let mut depth = 0;
+
+        while let Some(ch) = self.peek() {
+            self.pos += 1;
+
+            match ch {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => text.push(ch),
+            }
+        }
+
+        Node::Glyphs(text)
+    }
+
+    fn parse_command(&mut self, font: MathFont, is_operand: bool) -> Command {
+        // skip the backslash
+        self.pos += 1;
+
+        let name = self.parse_name();
+
+        let node = match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let num = self.parse_arg(font);
+                let den = self.parse_arg(font);
+
+                Node::Frac(Box::new(num), Box::new(den))
+            }
+            "sqrt" => {
+                self.skip_space();
+
+                let index = if self.peek() == Some('[') {
+                    self.pos += 1;
+                    Some(Box::new(self.parse_list(font, Some(']'))))
+                } else {
+                    None
+                };
+
+                Node::Sqrt(Box::new(self.parse_arg(font)), index)
+            }
+            "rm" => return Command::Font(MathFont::Roman),
+            "it" => return Command::Font(MathFont::Italic),
+            "bf" => return Command::Font(MathFont::Bold),
+            "sf" => return Command::Font(MathFont::Sans),
+            "tt" => return Command::Font(MathFont::Mono),
+            "cal" => return Command::Font(MathFont::Cal),
+            "mathrm" | "operatorname" => self.parse_arg(MathFont::Roman),
+            "mathit" => self.parse_arg(MathFont::Italic),
+            "mathbf" | "boldsymbol" => self.parse_arg(MathFont::Bold),
+            "mathsf" => self.parse_arg(MathFont::Sans),
+            "mathtt" => self.parse_arg(MathFont::Mono),
+            "mathcal" => self.parse_arg(MathFont::Cal),
+            "mathbb" => self.parse_arg(MathFont::BlackBoard),
+            "text" | "textrm" | "mbox" => self.parse_text(),
+            "left" | "right" => {
+                self.skip_space();
+
+                match self.peek() {
+                    Some('.') => {
+                        self.pos += 1;
+                        Node::Group(Vec::new())
+                    }
+                    Some('\\') => match self.parse_command(font, false) {
+                        Command::Atom(node) => node,
+                        Command::Font(_) => Node::Group(Vec::new()),
+                    }
+                    Some(ch) => {
+                        self.pos += 1;
+                        Node::Glyphs(ch.to_string())
+                    }
+                    None => Node::Group(Vec::new()),
+                }
+            }
+            "big" | "Big" | "bigg" | "Bigg" | "displaystyle" | "textstyle" => {
+                Node::Group(Vec::new())
+            }
+            "," => Node::Space(3. / 18.),
+            ":" | ">" => Node::Space(4. / 18.),
+            ";" => Node::Space(5. / 18.),
+            "!" => Node::Space(-3. / 18.),
+            " " => Node::Space(1. / 3.),
+            "quad" => Node::Space(1.),
+            "qquad" => Node::Space(2.),
+            "{" | "}" | "$" | "%" | "&" | "#" | "_" | "|" => Node::Glyphs(name.clone()),
+            "\\" => Node::Group(Vec::new()),
+            "sin" | "cos" | "tan" | "sec" | "csc" | "cot" | "arcsin" | "arccos" | "arctan"
+            | "sinh" | "cosh" | "tanh" | "exp" | "log" | "ln" | "lg" | "lim" | "max" | "min"
+            | "sup" | "inf" | "det" | "arg" | "deg" | "dim" | "gcd" | "Pr" => {
+                self.skip_space();
+
+                match self.peek() {
+                    Some('(') | Some('^') | Some('_') | None => Node::Glyphs(name.clone()),
+                    _ => Node::Group(vec![Node::Glyphs(name.clone()), Node::Space(3. / 18.)]),
+                }
+            }
+            _ => {
+                if let Some(ch) = greek(&name) {
+                    Node::Glyphs(ch.to_string())
+                } else if let Some(ch) = relation_symbol(&name) {
+                    relation(ch)
+                } else if let Some(ch) = binary_symbol(&name) {
+                    binary(ch, is_operand)
+                } else if let Some(ch) = symbol(&name) {
+                    Node::Glyphs(ch.to_string())
+                } else {
+                    Node::Glyphs(format!("\\{}", name))
+                }
+            }
+        };
+
+        Command::Atom(node)
+    }
+}
+
+fn attach_script(base: Node, is_sup: bool, arg: Node) -> Node {
+    match base {
+        Node::Scripts { base, sub, sup } if is_sup && sup.is_none() => {
+            Node::Scripts { base, sub, sup: Some(Box::new(arg)) }
+        }
+        Node::Scripts { base, sub, sup } if ! is_sup && sub.is_none() => {
+            Node::Scripts { base, sub: Some(Box::new(arg)), sup }
+        }
+        base => {
+            let arg = Some(Box::new(arg));
+
+            if is_sup {
+                Node::Scripts { base: Box::new(base), sub: None, sup: arg }
+            } else {
+                Node::Scripts { base: Box::new(base), sub: arg, sup: None }
+            }
+        }
+    }
+}
+
+///
+/// True if the node ends with an operand, so a following `-` is binary.
+///
+fn is_operand(node: &Node) -> bool {
+    match node {
+        Node::Glyphs(text) => match text.chars().last() {
+            Some(ch) => ! "([{=+\u{2212}<>,;\u{2217}".contains(ch),
+            None => false,
+        },
+        Node::Space(_) => false,
+        Node::Group(nodes) => nodes.last().map_or(false, is_operand),
+        _ => true,
+    }
+}
+
+fn binary(op: &str, is_operand: bool) -> Node {
+    if is_operand {
+        Node::Group(vec![
+            Node::Space(4. / 18.),
+            Node::Glyphs(op.to_string()),
+            Node::Space(4. / 18.),
+        ])
+    } else {
+        Node::Glyphs(op.to_string())
+    }
+}
+
+fn relation(op: &str) -> Node {
+    Node::Group(vec![
+        Node::Space(5. / 18.),
+        Node::Glyphs(op.to_string()),
+        Node::Space(5. / 18.),
+    ])
+}
+
+fn greek(name: &str) -> Option<char> {
+    let ch = match name {
+        "alpha" => 'α', "beta" => 'β', "gamma" => 'γ', "delta" => 'δ',
+        "epsilon" => 'ϵ', "varepsilon" => 'ε', "zeta" => 'ζ', "eta" => 'η',
+        "theta" => 'θ', "vartheta" => 'ϑ', "iota" => 'ι', "kappa" => 'κ',
+        "lambda" => 'λ', "mu" => 'μ', "nu" => 'ν', "xi" => 'ξ', "pi" => 'π',
+        "varpi" => 'ϖ', "rho" => 'ρ', "varrho" => 'ϱ', "sigma" => 'σ',
+        "varsigma" => 'ς', "tau" => 'τ', "upsilon" => 'υ', "phi" => 'ϕ',
+        "varphi" => 'φ', "chi" => 'χ', "psi" => 'ψ', "omega" => 'ω',
+        "Gamma" => 'Γ', "Delta" => 'Δ', "Theta" => 'Θ', "Lambda" => 'Λ',
+        "Xi" => 'Ξ', "Pi" => 'Π', "Sigma" => 'Σ', "Upsilon" => 'Υ',
+        "Phi" => 'Φ', "Psi" => 'Ψ', "Omega" => 'Ω',
+        _ => return None,
+    };
+
+    Some(ch)
+}
+
+fn relation_symbol(name: &str) -> Option<&'static str> {
+    let op = match name {
+        "leq" | "le" => "≤", "geq" | "ge" => "≥", "neq" | "ne" => "≠",
+        "approx" => "≈", "sim" => "∼", "simeq" => "≃", "equiv" => "≡",
+        "propto" => "∝", "in" => "∈", "notin" => "∉", "ni" => "∋",
+        "subset" => "⊂", "supset" => "⊃", "subseteq" => "⊆", "supseteq" => "⊇",
+        "ll" => "≪", "gg" => "≫", "perp" => "⊥", "parallel" => "∥",
+        "to" | "rightarrow" => "→", "leftarrow" | "gets" => "←",
+        "Rightarrow" => "⇒", "Leftarrow" => "⇐", "leftrightarrow" => "↔",
+        "Leftrightarrow" | "iff" => "⇔", "mapsto" => "↦",
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn binary_symbol(name: &str) -> Option<&'static str> {
+    let op = match name {
+        "pm" => "±", "mp" => "∓", "times" => "×", "div" => "÷", "cdot" => "·",
+        "ast" => "∗", "star" => "⋆", "circ" => "∘", "bullet" => "•",
+        "cup" => "∪", "cap" => "∩", "wedge" | "land" => "∧", "vee" | "lor" => "∨",
+        "oplus" => "⊕", "ominus" => "⊖", "otimes" => "⊗", "setminus" => "∖",
+        _ => return None,
+    };
+
+    Some(op)
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    let sym = match name {
+        "infty" => "∞", "partial" => "∂", "nabla" => "∇", "sum" => "∑",
+        "prod" => "∏", "int" => "∫", "oint" => "∮", "forall" => "∀",
+        "exists" => "∃", "emptyset" => "∅", "neg" | "lnot" => "¬",
+        "hbar" => "ℏ", "ell" => "ℓ", "Re" => "ℜ", "Im" => "ℑ", "aleph" => "ℵ",
+        "degree" => "°", "prime" => "′", "angle" => "∠",
+        "ldots" | "dots" => "…", "cdots" => "⋯", "vdots" => "⋮",
+        "uparrow" => "↑", "downarrow" => "↓",
+        "langle" => "⟨", "rangle" => "⟩", "lbrace" => "{", "rbrace" => "}",
+        "lfloor" => "⌊", "rfloor" => "⌋", "lceil" => "⌈", "rceil" => "⌉",
+        "vert" => "|", "Vert" => "‖",
+        _ => return None,
+    };
+
+    Some(sym)
+}
+
+///
+/// Letters and digits in the Unicode mathematical alphabet for the font.
+///
+fn font_char(ch: char, font: MathFont) -> char {
+    let offset = |base: u32, first: char| {
+        char::from_u32(base + (ch as u32 - first as u32)).unwrap_or(ch)
+    };
+
+    match (font, ch) {
+        (MathFont::Roman, _) => ch,
+
+        (MathFont::Italic, 'h') => 'ℎ',
+        (MathFont::Italic, 'A'..='Z') => offset(0x1D434, 'A'),
+        (MathFont::Italic, 'a'..='z') => offset(0x1D44E, 'a'),
+
+        (MathFont::Bold, 'A'..='Z') => offset(0x1D400, 'A'),
+        (MathFont::Bold, 'a'..='z') => offset(0x1D41A, 'a'),
+        (MathFont::Bold, '0'..='9') => offset(0x1D7CE, '0'),
+
+        (MathFont::Sans, 'A'..='Z') => offset(0x1D5A0, 'A'),
+        (MathFont::Sans, 'a'..='z') => offset(0x1D5BA, 'a'),
+        (MathFont::Sans, '0'..='9') => offset(0x1D7E2, '0'),
+
+        (MathFont::Mono, 'A'..='Z') => offset(0x1D670, 'A'),
+        (MathFont::Mono, 'a'..='z') => offset(0x1D68A, 'a'),
+        (MathFont::Mono, '0'..='9') => offset(0x1D7F6, '0'),
+
+        (MathFont::Cal, _) => match ch {
+            'B' => 'ℬ', 'E' => 'ℰ', 'F' => 'ℱ', 'H' => 'ℋ', 'I' => 'ℐ',
+            'L' => 'ℒ', 'M' => 'ℳ', 'R' => 'ℛ', 'e' => 'ℯ', 'g' => 'ℊ', 'o' => 'ℴ',
+            'A'..='Z' => offset(0x1D49C, 'A'),
+            'a'..='z' => offset(0x1D4B6, 'a'),
+            _ => ch,
+        },
+
+        (MathFont::BlackBoard, _) => match ch {
+            'C' => 'ℂ', 'H' => 'ℍ', 'N' => 'ℕ', 'P' => 'ℙ', 'Q' => 'ℚ',
+            'R' => 'ℝ', 'Z' => 'ℤ',
+            'A'..='Z' => offset(0x1D538, 'A'),
+            'a'..='z' => offset(0x1D552, 'a'),
+            '0'..='9' => offset(0x1D7D8, '0'),
+            _ => ch,
+        },
+
+        _ => ch,
+    }
+}
+
+///
+/// Drawing primitive in em units, relative to the baseline origin.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Item {
+    Glyphs { x: f32, y: f32, scale: f32, text: String },
+    Line { points: Vec<[f32; 2]>, width: f32 },
+}
+
+impl Item {
+    fn translate(self, dx: f32, dy: f32) -> Self {
+        match self {
+            Item::Glyphs { x, y, scale, text } => {
+                Item::Glyphs { x: x + dx, y: y + dy, scale, text }
+            }
+            Item::Line { points, width } => Item::Line {
+                points: points.iter().map(|[x, y]| [x + dx, y + dy]).collect(),
+                width,
+            }
+        }
+    }
+}
+
+///
+/// Box of laid out items, with extents in em relative to the baseline.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Layout {
+    pub(super) items: Vec<Item>,
+    pub(super) width: f32,
+    pub(super) ascent: f32,
+    pub(super) descent: f32,
+}
+
+impl Layout {
+    fn new(width: f32, ascent: f32, descent: f32) -> Self {
+        Self {
+            items: Vec::new(),
+            width,
+            ascent,
+            descent,
+        }
+    }
+
+    fn place(&mut self, other: Layout, dx: f32, dy: f32) {
+        self.ascent = self.ascent.max(other.ascent + dy);
+        self.descent = self.descent.max(other.descent - dy);
+
+        for item in other.items {
+            self.items.push(item.translate(dx, dy));
+        }
+    }
+}
+
+fn layout_node(node: &Node, scale: f32) -> Layout {
+    match node {
+        Node::Glyphs(text) => {
+            let mut layout = Layout::new(
+                backend::text_width(text) * scale,
+                ASCENT * scale,
+                DESCENT * scale
+            );
+
+            layout.items.push(Item::Glyphs { x: 0., y: 0., scale, text: text.clone() });
+
+            layout
+        }
+        Node::Space(em) => Layout::new(em * scale, 0., 0.),
+        Node::Group(nodes) => {
+            let mut layout = Layout::new(0., 0., 0.);
+
+            for node in nodes {
+                let child = layout_node(node, scale);
+                let x = layout.width;
+
+                layout.width += child.width;
+                layout.place(child, x, 0.);
+            }
+
+            layout
+        }
+        Node::Scripts { base, sub, sup } => {
+            let base = layout_node(base, scale);
+            let script_scale = (scale * SCRIPT_SCALE).max(MIN_SCALE);
+
+            let sup = sup.as_ref().map(|sup| layout_node(sup, script_scale));
+            let sub = sub.as_ref().map(|sub| layout_node(sub, script_scale));
+
+            let mut raise = (0.4 * scale).max(base.ascent - 0.3 * scale);
+            let drop = (0.2 * scale).max(base.descent - 0.05 * scale);
+
+            if let (Some(sup), Some(sub)) = (&sup, &sub) {
+                // keep a gap between the scripts
+                let gap = (raise - sup.descent) - (sub.ascent - drop);
+
+                raise += (0.1 * scale - gap).max(0.);
+            }
+
+            let x = base.width;
+            let mut script_width: f32 = 0.;
+
+            let mut layout = Layout::new(0., 0., 0.);
+            layout.place(base, 0., 0.);
+
+            if let Some(sup) = sup {
+                script_width = script_width.max(sup.width);
+                layout.place(sup, x, raise);
+            }
+
+            if let Some(sub) = sub {
+                script_width = script_width.max(sub.width);
+                layout.place(sub, x, -drop);
+            }
+
+            layout.width = x + script_width + 0.05 * scale;
+
+            layout
+        }
+        Node::Frac(num, den) => {
+            let frac_scale = (scale * FRAC_SCALE).max(MIN_SCALE);
+
+            let num = layout_node(num, frac_scale);
+            let den = layout_node(den, frac_scale);
+
+            let (axis, rule) = (AXIS * scale, RULE * scale);
+            let (gap, pad) = (0.15 * scale, 0.1 * scale);
+
+            let width = num.width.max(den.width) + 2. * pad;
+
+            let y_num = axis + 0.5 * rule + gap + num.descent;
+            let y_den = axis - 0.5 * rule - gap - den.ascent;
+
+            let mut layout = Layout::new(width, 0., 0.);
+
+            layout.items.push(Item::Line {
+                points: vec![[0.5 * pad, axis], [width - 0.5 * pad, axis]],
+                width: rule,
+            });
+
+            let (x_num, x_den) = (0.5 * (width - num.width), 0.5 * (width - den.width));
+
+            layout.place(num, x_num, y_num);
+            layout.place(den, x_den, y_den);
+
+            layout
+        }
+        Node::Sqrt(body, index) => {
+            let body = layout_node(body, scale);
+            let index = index.as_ref().map(|index| {
+                layout_node(index, (scale * 0.5).max(MIN_SCALE))
+            });
+
+            let rule = RULE * scale;
+            let top = body.ascent + 0.12 * scale;
+            let bottom = - body.descent;
+            let mid = 0.5 * (top + bottom);
+
+            let x0 = match &index {
+                Some(index) => (index.width - 0.25 * scale).max(0.),
+                None => 0.,
+            };
+
+            let x_body = x0 + 0.5 * scale;
+            let width = x_body + body.width + 0.1 * scale;
+
+            let mut layout = Layout::new(width, top + rule, 0.);
+
+            layout.items.push(Item::Line {
+                points: vec![
+                    [x0, mid],
+                    [x0 + 0.12 * scale, mid + 0.06 * scale],
+                    [x0 + 0.25 * scale, bottom],
+                    [x0 + 0.45 * scale, top],
+                    [width - 0.05 * scale, top],
+                ],
+                width: rule,
+            });
+
+            if let Some(index) = index {
+                let x = x0 + 0.25 * scale - index.width;
+                let y = mid + 0.1 * scale + index.descent;
+
+                layout.place(index, x, y);
+            }
+
+            layout.place(body, x_body, 0.);
+
+            layout
+        }
+    }
+}
+
+///
+/// Joins adjacent glyph runs on the same baseline and scale, so the
+/// renderer's own advances space them.
+///
+pub(super) fn merge_glyphs(items: Vec<Item>) -> Vec<Item> {
+    let mut merged = Vec::<Item>::new();
+
+    for item in items {
+        if let (
+            Some(Item::Glyphs { x: x0, y: y0, scale: s0, text: t0 }),
+            Item::Glyphs { x, y, scale, text },
+        ) = (merged.last_mut(), &item) {
+            let end = *x0 + backend::text_width(t0) * *s0;
+
+            if (*y0 - y).abs() < 1e-4 && *s0 == *scale && (end - x).abs() < 1e-4 {
+                t0.push_str(text);
+                continue;
+            }
+        }
+
+        merged.push(item);
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend;
+
+    use super::{font_char, layout_node, merge_glyphs, parse, text_width, Item, MathFont, Node};
+
+    fn glyphs(text: &str) -> Node {
+        Node::Glyphs(text.to_string())
+    }
+
+    #[test]
+    fn math_text_plain() {
+        assert_eq!(parse("rad/s"), glyphs("rad/s"));
+        assert_eq!(parse("cost \\$5"), glyphs("cost $5"));
+        assert_eq!(parse("a $ b"), glyphs("a $ b"));
+    }
+
+    #[test]
+    fn math_text_symbols() {
+        assert_eq!(parse("$\\omega$ (rad/s)"), Node::Group(vec![
+            glyphs("ω"),
+            glyphs(" (rad/s)"),
+        ]));
+
+        assert_eq!(parse("$\\alpha_i^2$"), Node::Scripts {
+            base: Box::new(glyphs("α")),
+            sub: Some(Box::new(glyphs("i"))),
+            sup: Some(Box::new(glyphs("2"))),
+        });
+
+        assert_eq!(parse("$10^{-3}$"), Node::Group(vec![
+            glyphs("1"),
+            Node::Scripts {
+                base: Box::new(glyphs("0")),
+                sub: None,
+                sup: Some(Box::new(Node::Group(vec![glyphs("\u{2212}"), glyphs("3")]))),
+            },
+        ]));
+    }
+
+    #[test]
+    fn math_text_spacing() {
+        // binary minus is spaced, unary minus isn't
+        match parse("$a-b$") {
+            Node::Group(nodes) => assert_eq!(nodes[1], Node::Group(vec![
+                Node::Space(4. / 18.), glyphs("\u{2212}"), Node::Space(4. / 18.),
+            ])),
+            node => panic!("unexpected {:?}", node),
+        }
+
+        assert_eq!(parse("$-b$"), Node::Group(vec![glyphs("\u{2212}"), glyphs("b")]));
+    }
+
+    #[test]
+    fn math_text_commands() {
+        assert_eq!(parse("$\\frac{a}{b}$"), Node::Frac(
+            Box::new(glyphs("a")),
+            Box::new(glyphs("b")),
+        ));
+
+        assert_eq!(parse("$\\sqrt[3]{x}$"), Node::Sqrt(
+            Box::new(glyphs("x")),
+            Some(Box::new(glyphs("3"))),
+        ));
+
+        assert_eq!(parse("$\\mathbf{x}$"), glyphs("𝐱"));
+        assert_eq!(parse("$\\mathbb{R}$"), glyphs("ℝ"));
+        assert_eq!(parse("${\\bf x}y$"), Node::Group(vec![glyphs("𝐱"), glyphs("y")]));
+        assert_eq!(parse("$\\text{max speed}$"), glyphs("max speed"));
+        assert_eq!(parse("$\\foo$"), glyphs("\\foo"));
+
+        assert_eq!(font_char('h', MathFont::Italic), 'ℎ');
+        assert_eq!(font_char('3', MathFont::Italic), '3');
+    }
+
+    #[test]
+    fn math_text_layout() {
+        let base = layout_node(&glyphs("x"), 1.);
+        let script = layout_node(&parse("$x^2$"), 1.);
+
+        assert!(script.width > base.width);
+        assert!(script.ascent > base.ascent);
+        assert_eq!(script.descent, base.descent);
+
+        let frac = layout_node(&parse("$\\frac{1}{2}$"), 1.);
+        assert!(frac.ascent > base.ascent);
+        assert!(frac.descent > base.descent);
+
+        assert!((text_width("10") - backend::text_width("10")).abs() < 1e-5);
+        assert_eq!(text_width("$10$"), text_width("10"));
+    }
+
+    #[test]
+    fn math_text_merge() {
+        let items = merge_glyphs(layout_node(&parse("$10^{-3}$"), 1.).items);
+
+        assert_eq!(items.len(), 2);
+
+        match (&items[0], &items[1]) {
+            (
+                Item::Glyphs { x: x0, y: y0, scale: s0, text: t0 },
+                Item::Glyphs { x: x1, y: y1, scale: s1, text: t1 },
+            ) => {
+                assert_eq!((*x0, *y0, *s0, t0.as_str()), (0., 0., 1., "10"));
+                assert_eq!((*s1, t1.as_str()), (0.7, "\u{2212}3"));
+                let end = backend::text_width("10");
+                assert!((x1 - end).abs() < 1e-5 && (y1 - 0.42).abs() < 1e-5);
+            }
+            _ => panic!("unexpected {:?}", items),
+        }
+    }
+}
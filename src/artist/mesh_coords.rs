@@ -0,0 +1,215 @@
+use essay_graphics::api::{Bounds, Point};
+use essay_tensor::tensor::Tensor;
+
+use crate::chart::Data;
+
+///
+/// Data coordinates of a rectangular mesh, stored as row-major 2D grids
+/// where `(x[(j, i)], y[(j, i)])` is the point at row `j` and column `i`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MeshCoords {
+    x: Vec<f32>,
+    y: Vec<f32>,
+    rows: usize,
+    cols: usize,
+}
+
+impl MeshCoords {
+    ///
+    /// Either 1D `x` and `y` giving the column and row coordinates, or 2D
+    /// `x` and `y` with the same shape, as from `meshgrid`.
+    ///
+    pub(crate) fn new(x: &Tensor, y: &Tensor) -> Self {
+        match (x.rank(), y.rank()) {
+            (1, 1) => {
+                let (x, y) = (x.as_slice(), y.as_slice());
+                let (rows, cols) = (y.len(), x.len());
+
+                let mut vec_x = Vec::<f32>::new();
+                let mut vec_y = Vec::<f32>::new();
+
+                for j in 0..rows {
+                    vec_x.extend_from_slice(x);
+                    vec_y.extend(std::iter::repeat(y[j]).take(cols));
+                }
+
+                Self { x: vec_x, y: vec_y, rows, cols }
+            }
+            (2, 2) => {
+                assert!(
+                    x.rows() == y.rows() && x.cols() == y.cols(),
+                    "2D mesh x and y must have the same shape x={:?} y={:?}",
+                    x.shape(), y.shape()
+                );
+
+                Self {
+                    x: Vec::from(x.as_slice()),
+                    y: Vec::from(y.as_slice()),
+                    rows: x.rows(),
+                    cols: x.cols(),
+                }
+            }
+            _ => panic!(
+                "mesh x and y must both be 1D or both be 2D x={:?} y={:?}",
+                x.shape(), y.shape()
+            ),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    #[inline]
+    pub(crate) fn point(&self, j: usize, i: usize) -> [f32; 2] {
+        let k = j * self.cols + i;
+
+        [self.x[k], self.y[k]]
+    }
+
+    ///
+    /// Bilinear interpolation at a fractional column `u` and row `v`, mapping
+    /// index space to data coordinates.
+    ///
+    pub(crate) fn interp(&self, u: f32, v: f32) -> [f32; 2] {
+        let i = (u.floor().max(0.) as usize).min(self.cols.max(2) - 2);
+        let j = (v.floor().max(0.) as usize).min(self.rows.max(2) - 2);
+
+        let i1 = (i + 1).min(self.cols - 1);
+        let j1 = (j + 1).min(self.rows - 1);
+
+        let (s, t) = (u - i as f32, v - j as f32);
+
+        let [x00, y00] = self.point(j, i);
+        let [x01, y01] = self.point(j, i1);
+        let [x10, y10] = self.point(j1, i);
+        let [x11, y11] = self.point(j1, i1);
+
+        [
+            (1. - t) * ((1. - s) * x00 + s * x01) + t * ((1. - s) * x10 + s * x11),
+            (1. - t) * ((1. - s) * y00 + s * y01) + t * ((1. - s) * y10 + s * y11),
+        ]
+    }
+
+    ///
+    /// Cell corners for a mesh of cell centers, placed midway between
+    /// neighboring centers and extrapolated at the borders. The result has
+    /// one more row and column.
+    ///
+    pub(crate) fn corners(&self) -> Self {
+        Self {
+            x: grid_corners(&self.x, self.rows, self.cols),
+            y: grid_corners(&self.y, self.rows, self.cols),
+            rows: self.rows + 1,
+            cols: self.cols + 1,
+        }
+    }
+
+    pub(crate) fn bounds(&self) -> Bounds<Data> {
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+        let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+
+        for (x, y) in self.x.iter().zip(&self.y) {
+            if x.is_finite() && y.is_finite() {
+                (x_min, x_max) = (x_min.min(*x), x_max.max(*x));
+                (y_min, y_max) = (y_min.min(*y), y_max.max(*y));
+            }
+        }
+
+        if x_min > x_max {
+            Bounds::none()
+        } else {
+            Bounds::new(Point(x_min, y_min), Point(x_max, y_max))
+        }
+    }
+}
+
+///
+/// Edges between consecutive centers, with the end edges extrapolated by
+/// half a step.
+///
+fn mid_edges(centers: &[f32]) -> Vec<f32> {
+    let n = centers.len();
+
+    if n == 1 {
+        return vec![centers[0] - 0.5, centers[0] + 0.5];
+    }
+
+    let mut edges = vec![centers[0] - 0.5 * (centers[1] - centers[0])];
+
+    for pair in centers.windows(2) {
+        edges.push(0.5 * (pair[0] + pair[1]));
+    }
+
+    edges.push(centers[n - 1] + 0.5 * (centers[n - 1] - centers[n - 2]));
+
+    edges
+}
+
+fn grid_corners(grid: &[f32], rows: usize, cols: usize) -> Vec<f32> {
+    // edges along each row, then along each column of the result
+    let row_edges: Vec<Vec<f32>> = (0..rows)
+        .map(|j| mid_edges(&grid[j * cols..(j + 1) * cols]))
+        .collect();
+
+    let mut corners = vec![0.; (rows + 1) * (cols + 1)];
+
+    for i in 0..cols + 1 {
+        let column: Vec<f32> = row_edges.iter().map(|row| row[i]).collect();
+
+        for (j, v) in mid_edges(&column).iter().enumerate() {
+            corners[j * (cols + 1) + i] = *v;
+        }
+    }
+
+    corners
+}
+
+#[cfg(test)]
+mod test {
+    use essay_tensor::ten;
+
+    use super::{mid_edges, MeshCoords};
+
+    #[test]
+    fn mesh_coords_1d() {
+        let mesh = MeshCoords::new(
+            &ten![0., 1., 3.],
+            &ten![10., 20.],
+        );
+
+        assert_eq!(mesh.shape(), (2, 3));
+        assert_eq!(mesh.point(1, 2), [3., 20.]);
+        assert_eq!(mesh.interp(1.5, 0.5), [2., 15.]);
+        assert_eq!(mesh.interp(2., 1.), [3., 20.]);
+    }
+
+    #[test]
+    fn mesh_coords_2d() {
+        let mesh = MeshCoords::new(
+            &ten![[0., 1.], [1., 2.]],
+            &ten![[0., 0.], [2., 2.]],
+        );
+
+        assert_eq!(mesh.shape(), (2, 2));
+        assert_eq!(mesh.interp(0.5, 0.5), [1., 1.]);
+    }
+
+    #[test]
+    fn mesh_coords_corners() {
+        assert_eq!(mid_edges(&[0., 1., 3.]), vec![-0.5, 0.5, 2., 4.]);
+        assert_eq!(mid_edges(&[2.]), vec![1.5, 2.5]);
+
+        let mesh = MeshCoords::new(
+            &ten![0., 1.],
+            &ten![0., 2.],
+        );
+
+        assert_eq!(mesh.corners(), MeshCoords::new(
+            &ten![-0.5, 0.5, 1.5],
+            &ten![-1., 1., 3.],
+        ));
+    }
+}
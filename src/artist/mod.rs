@@ -1,14 +1,18 @@
+mod annotate;
 mod artist;
 mod bar;
 mod collection;
 mod colorbar;
 mod container;
 mod contour;
+mod errorbar;
 mod grid_color;
 mod histogram;
 mod image;
 mod lines;
 mod markers;
+pub(crate) mod math_text;
+mod mesh_coords;
 mod norm;
 pub mod patch;
 mod triplot;
@@ -17,14 +21,20 @@ mod quiver;
 mod span;
 mod stem;
 mod text;
+pub(crate) mod text_layout;
 mod tricontour;
+mod tripcolor;
+
+pub use annotate::{
+    Annotation, AnnotationOpt, ArrowStyle, ConnectionStyle,
+};
 
 pub use artist::{
     Artist, ArtistDraw, ArtistContainer, ArtistView, IntoArtist, Stale,
 };
 
 pub use bar::{
-    Bar, BarOpt
+    Bar, BarOpt, Orientation,
 };
 
 pub use collection::PathCollection;
@@ -33,27 +43,35 @@ pub use container::{
     Container, ContainerOpt
 };
 
-pub use colorbar::Colorbar;
+pub use colorbar::{Colorbar, ColorbarHandler};
 
 pub use grid_color::{
     GridColor, GridColorOpt, Shading,
 };
 
-pub use contour::Contour;
+pub use contour::{Contour, ContourOpt, Extend};
+
+pub use errorbar::{
+    ErrorBar, ErrorBarOpt,
+};
 
 pub use norm::{
     Norm, Norms,
 };
 
 pub use histogram::{
-    Histogram, HistogramOpt, 
+    Histogram, HistogramOpt, HistType,
 };
 
+pub(crate) use histogram::{bin_edges, bin_index};
+
 pub use image::{
     Image, ImageOpt
 };
 
-pub use tricontour::TriContour;
+pub use tricontour::{TriContour, TriContourOpt};
+
+pub use tripcolor::{TriColor, TriColorOpt};
 
 pub use triplot::TriPlot;
 
@@ -87,3 +105,5 @@ pub use stem::{
 pub use text::{
     Text, TextOpt, TextCoords, TextCanvas, // TextStyle,
 };
+
+pub use text_layout::{BoxStyle, RotationMode};
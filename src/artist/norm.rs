@@ -8,6 +8,8 @@ pub struct Norm {
     max: f32,
 
     scale: Box<dyn Fn(f32) -> f32 + Send + 'static>,
+    // the standard scale, if any, for colorbar ticks
+    kind: Option<Norms>,
 }
 
 pub trait _NormScale : Send + 'static {
@@ -22,9 +24,24 @@ impl Norm {
             min: -1.,
             max: 1.,
             scale: Box::new(fun),
+            kind: None,
         }
     }
 
+    fn with_kind(kind: Norms, fun: impl Fn(f32) -> f32 + Send + 'static) -> Self {
+        let mut norm = Self::new(fun);
+        norm.kind = Some(kind);
+
+        norm
+    }
+
+    ///
+    /// The standard scale of the norm, or None for a custom function.
+    ///
+    pub fn kind(&self) -> Option<&Norms> {
+        self.kind.as_ref()
+    }
+
     #[inline]
     fn scale(&self, value: f32) -> f32 {
         (self.scale)(value)
@@ -35,11 +52,17 @@ impl Norm {
 
         for value in values.iter() {
             let value = self.scale(*value);
-            min = min.min(value);
-            max = max.max(value);
+
+            // log scales map zero counts to -inf
+            if value.is_finite() {
+                min = min.min(value);
+                max = max.max(value);
+            }
         }
 
-        if min == max {
+        if min > max {
+            (min, max) = (0., 1.);
+        } else if min == max {
             min -= 1.;
             max += 1.;
         }
@@ -105,6 +128,27 @@ pub enum Norms {
 }
 
 impl Norms {
+    ///
+    /// Scaled value, as used for the norm's min and max.
+    ///
+    pub fn forward(&self, value: f32) -> f32 {
+        match self {
+            Norms::Linear => scale_linear(value),
+            Norms::Log10 => scale_log10(value),
+            Norms::Log2 => scale_log2(value),
+            Norms::Ln => scale_ln(value),
+        }
+    }
+
+    pub fn inverse(&self, value: f32) -> f32 {
+        match self {
+            Norms::Linear => value,
+            Norms::Log10 => 10f32.powf(value),
+            Norms::Log2 => value.exp2(),
+            Norms::Ln => value.exp(),
+        }
+    }
+
     pub fn vmin(self, value: f32) -> Norm {
         Norm::from(self).vmin(value)
     }
@@ -117,10 +161,10 @@ impl Norms {
 impl From<Norms> for Norm {
     fn from(value: Norms) -> Self {
         match value {
-            Norms::Linear => Norm::new(scale_linear),
-            Norms::Log10 => Norm::new(scale_log10),
-            Norms::Log2 => Norm::new(scale_log2),
-            Norms::Ln => Norm::new(scale_ln),
+            Norms::Linear => Norm::with_kind(value, scale_linear),
+            Norms::Log10 => Norm::with_kind(value, scale_log10),
+            Norms::Log2 => Norm::with_kind(value, scale_log2),
+            Norms::Ln => Norm::with_kind(value, scale_ln),
         }
     }
 }
@@ -181,6 +181,10 @@ impl Arrow {
     }
 
     pub fn to_path(&self) -> Path<Data> {
+        self.arrow_path()
+    }
+
+    pub(crate) fn arrow_path<M: Coord>(&self) -> Path<M> {
         let Point(x, y) = self.xy;
         let Point(dx, dy) = self.dxdy.into();
     
@@ -1,24 +1,30 @@
 use essay_graphics::api::{
     renderer::{Canvas, Renderer, Result}, 
-    Bounds, FontFamily, FontStyle, PathOpt, Point, TextStyle
+    Angle, Bounds, FontFamily, FontStyle, HorizAlign, PathOpt, Point, TextStyle, VertAlign
 };
 
 use crate::{
     chart::{Data, LegendHandler}, 
     config::{ConfigArc, PathStyle},
-    data_artist_option_struct, path_style_options, transform::ToCanvas
+    data_artist_option_struct, path_style_options, text_bbox_options, transform::ToCanvas
 };
 
-use super::{Artist, ArtistDraw, ArtistView};
+use super::{
+    text_layout::{RotationMode, TextBBox, TextLayout},
+    Artist, ArtistDraw, ArtistView
+};
 
 pub struct TextCanvas {
     pos: Bounds<Canvas>,
     extent: Bounds<Canvas>,
+    canvas_extent: Bounds<Canvas>,
 
     text: Option<String>,
 
     path_style: PathStyle,
     text_style: TextStyle,
+    line_spacing: f32,
+    pub(crate) bbox: TextBBox,
 
     angle: f32,
 }
@@ -30,36 +36,54 @@ impl TextCanvas {
         Self {
             pos: Bounds::none(),
             extent: Bounds::zero(),
+            canvas_extent: Bounds::none(),
             text: None,
 
             path_style: PathStyle::new(),
             text_style: TextStyle::new(),
+            line_spacing: TextLayout::LINE_SPACING,
+            bbox: TextBBox::new(),
 
             angle: 0.
         }
     }
 
     pub(crate) fn update_pos(&mut self, renderer: &mut dyn Renderer, pos: Bounds<Canvas>) {
-        self.extent = match &self.text {
-            None => Bounds::zero(),
+        match &self.text {
+            None => {
+                self.extent = Bounds::zero();
+                self.canvas_extent = Bounds::none();
+            }
             Some(text) => {
                 self.pos = pos.clone();
 
-                let size = match self.text_style.get_size() {
-                    Some(size) => *size,
-                    None => TextStyle::SIZE_DEFAULT,
+                let layout = self.layout(renderer, text);
+                let (width, height) = layout.size();
+
+                let pad = if self.bbox.is_visible() {
+                    2. * renderer.to_px(self.bbox.get_pad())
+                } else {
+                    0.
                 };
 
-                let width = text.len() as f32 * size as f32; //  * 0.5;
+                self.extent = Bounds::extent(width + pad, height + pad);
 
-                Bounds::extent(
-                    renderer.to_px(width),
-                    renderer.to_px(size)
-                )
+                self.canvas_extent = layout.extent(
+                    renderer,
+                    Point(pos.xmid(), pos.ymin()),
+                    self.angle,
+                    RotationMode::Anchor,
+                    &self.text_style,
+                    &self.bbox,
+                );
             }
         }
     }
 
+    fn layout(&self, renderer: &dyn Renderer, text: &str) -> TextLayout {
+        TextLayout::new(renderer, text, &self.text_style, self.line_spacing)
+    }
+
     pub fn label(&mut self, text: &str) -> &mut Self {
         if text.len() > 0 {
             self.text = Some(text.to_string());
@@ -74,6 +98,14 @@ impl TextCanvas {
         self.extent.height()
     }
 
+    ///
+    /// Canvas bounds of the placed text including its box, or none before
+    /// the text is positioned.
+    ///
+    pub fn canvas_extent(&self) -> Bounds<Canvas> {
+        self.canvas_extent.clone()
+    }
+
     pub fn text_style(&self) -> &TextStyle {
         &self.text_style
     }
@@ -86,6 +118,13 @@ impl TextCanvas {
         &mut self.path_style
     }
 
+    pub fn line_spacing(&mut self, spacing: f32) -> &mut Self {
+        assert!(spacing > 0., "line spacing must be positive");
+        self.line_spacing = spacing;
+
+        self
+    }
+
     pub fn angle(&mut self, angle: f32) -> &mut Self {
         self.angle = angle;
 
@@ -112,14 +151,15 @@ impl ArtistDraw<Canvas> for TextCanvas {
             let style = self.path_style.push(style);
 
             if ! self.pos.is_none() {
-                //let desc = Self::DESC * self.extent.height();
-
-                renderer.draw_text(
+                // frame text rotates around its anchor, e.g. the y label
+                self.layout(renderer, text).draw(
+                    renderer,
                     Point(self.pos.xmid(), self.pos.ymin()),
-                    text,
                     self.get_angle(),
+                    RotationMode::Anchor,
                     &style,
                     &self.text_style,
+                    &self.bbox,
                 )?;
             }
         }
@@ -136,10 +176,14 @@ pub struct Text {
 
     path_style: PathStyle,
     text_style: TextStyle,
+    line_spacing: f32,
+    rotation_mode: RotationMode,
+    bbox: TextBBox,
 
     family: Option<FontFamily>,
 
     angle: f32,
+    extent: Bounds<Canvas>,
 }
 
 impl Text {
@@ -153,10 +197,14 @@ impl Text {
 
             path_style: PathStyle::new(),
             text_style: TextStyle::new(),
+            line_spacing: TextLayout::LINE_SPACING,
+            rotation_mode: RotationMode::Default,
+            bbox: TextBBox::new(),
 
             family: None,
 
-            angle: 0.
+            angle: 0.,
+            extent: Bounds::none(),
         }
     }
 
@@ -167,8 +215,15 @@ impl Text {
     }
 
     pub fn height(&self) -> f32 {
-        //self.extent.height()
-        0.
+        if self.extent.is_none() { 0. } else { self.extent.height() }
+    }
+
+    ///
+    /// Canvas bounds of the text and its box from the last draw, or none
+    /// before the text is drawn.
+    ///
+    pub fn get_extent(&self) -> Bounds<Canvas> {
+        self.extent.clone()
     }
 
     pub fn text_style(&self) -> &TextStyle {
@@ -205,7 +260,7 @@ impl ArtistDraw<Data> for Text {
         to_canvas: &ToCanvas<Data>,
         style: &dyn PathOpt,
     ) -> Result<()> {
-        let pos = self.coords.to_canvas(self.pos, &to_canvas);
+        let pos = self.coords.to_canvas(self.pos, None, to_canvas, renderer);
         let style = self.path_style.push(style);
 
         if self.text.len() > 0 {
@@ -218,13 +273,28 @@ impl ArtistDraw<Data> for Text {
                 self.text_style.font(font_id);
             }
 
-            renderer.draw_text(
+            let layout = TextLayout::new(renderer, &self.text, &self.text_style, self.line_spacing);
+
+            self.extent = layout.extent(
+                renderer,
+                pos,
+                self.angle,
+                self.rotation_mode,
+                &self.text_style,
+                &self.bbox,
+            );
+
+            layout.draw(
+                renderer,
                 pos,
-                &self.text,
-                0.,
+                self.angle,
+                self.rotation_mode,
                 &style,
                 &self.text_style,
+                &self.bbox,
             )?;
+        } else {
+            self.extent = Bounds::none();
         }
 
         Ok(())
@@ -234,8 +304,9 @@ impl ArtistDraw<Data> for Text {
 impl Artist<Data> for Text {
     type Opt = TextOpt;
 
-    fn config(&mut self, _cfg: &ConfigArc) {
+    fn config(&mut self, cfg: &ConfigArc) {
         // self.style = PathStyle::from_config(cfg, "text");
+        self.bbox = TextBBox::from_config(cfg, "text.bbox");
     }
 
     fn opt(&mut self, view: ArtistView<Data, Text>) -> Self::Opt {
@@ -251,6 +322,7 @@ data_artist_option_struct!(TextOpt, Text);
 
 impl TextOpt {
     path_style_options!(path_style);
+    text_bbox_options!(bbox);
 
     pub fn text(&mut self, label: impl AsRef<str>) -> &mut Self {
         self.write(|artist| {
@@ -291,25 +363,131 @@ impl TextOpt {
 
         self
     }
+
+    pub fn rotation(&mut self, angle: impl Into<Angle>) -> &mut Self {
+        let angle = angle.into().to_radians();
+
+        self.write(|artist| {
+            artist.angle = angle;
+        });
+
+        self
+    }
+
+    ///
+    /// Whether rotated text is aligned before rotating, around its anchor,
+    /// or by the rotated text's bounding box.
+    ///
+    pub fn rotation_mode(&mut self, mode: RotationMode) -> &mut Self {
+        self.write(|artist| {
+            artist.rotation_mode = mode;
+        });
+
+        self
+    }
+
+    pub fn halign(&mut self, align: HorizAlign) -> &mut Self {
+        self.write(|artist| {
+            artist.text_style.halign(align);
+        });
+
+        self
+    }
+
+    pub fn valign(&mut self, align: VertAlign) -> &mut Self {
+        self.write(|artist| {
+            artist.text_style.valign(align);
+        });
+
+        self
+    }
+
+    ///
+    /// Distance between baselines as a multiple of the font size.
+    ///
+    pub fn line_spacing(&mut self, spacing: f32) -> &mut Self {
+        assert!(spacing > 0., "line spacing must be positive");
+
+        self.write(|artist| {
+            artist.line_spacing = spacing;
+        });
+
+        self
+    }
 }
 
+///
+/// Coordinate system of a text or annotation position.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum TextCoords {
+    /// Data coordinates of the chart.
     Data,
+    /// Fraction of the frame, where `(0, 0)` is the lower left.
     FrameFraction,
+    /// Fraction of the figure, where `(0, 0)` is the lower left.
+    FigureFraction,
+    /// Offset in points from an origin, which is the annotated point for
+    /// an annotation's text and the frame's lower left otherwise.
+    OffsetPoints,
 }
 
 impl TextCoords {
-    fn to_canvas(&self, pos: Point, to_canvas: &ToCanvas<Data>) -> Point {
+    pub(crate) fn to_canvas(
+        &self,
+        pos: Point,
+        origin: Option<Point>,
+        to_canvas: &ToCanvas<Data>,
+        renderer: &dyn Renderer,
+    ) -> Point {
         match self {
             TextCoords::Data => to_canvas.transform_point(pos),
             TextCoords::FrameFraction => {
-                let bounds = to_canvas.bounds();
+                fraction_point(&frame_canvas(to_canvas), pos)
+            }
+            TextCoords::FigureFraction => {
+                let figure = to_canvas.figure_bounds();
+
+                if figure.is_none() {
+                    fraction_point(&renderer.pos(), pos)
+                } else {
+                    fraction_point(&figure, pos)
+                }
+            }
+            TextCoords::OffsetPoints => {
+                let origin = match origin {
+                    Some(origin) => origin,
+                    None => {
+                        let frame = frame_canvas(to_canvas);
+
+                        Point(frame.xmin(), frame.ymin())
+                    }
+                };
 
                 Point(
-                    bounds.xmin() + pos.x() * bounds.width(),
-                    bounds.ymin() + pos.y() * bounds.height(),
+                    origin.x() + renderer.to_px(pos.x()),
+                    origin.y() + renderer.to_px(pos.y()),
                 )
             }
         }
     }
-}
\ No newline at end of file
+}
+
+///
+/// The frame's canvas position, which is the canvas image of the data view.
+///
+fn frame_canvas(to_canvas: &ToCanvas<Data>) -> Bounds<Canvas> {
+    let view = to_canvas.bounds();
+
+    Bounds::new(
+        to_canvas.transform_point(Point(view.xmin(), view.ymin())),
+        to_canvas.transform_point(Point(view.xmax(), view.ymax())),
+    )
+}
+
+fn fraction_point(bounds: &Bounds<Canvas>, pos: Point) -> Point {
+    Point(
+        bounds.xmin() + pos.x() * bounds.width(),
+        bounds.ymin() + pos.y() * bounds.height(),
+    )
+}
@@ -0,0 +1,450 @@
+use essay_graphics::api::{
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Color, HorizAlign, Path, PathCode, PathOpt, Point, TextStyle, VertAlign
+};
+
+use crate::config::{Config, PathStyle};
+
+use super::math_text::{layout_line, merge_glyphs, Item, Layout};
+
+///
+/// How rotated text is aligned to its position.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RotationMode {
+    /// Rotates the text, then aligns the rotated text's bounding box.
+    Default,
+    /// Aligns the unrotated text, then rotates it around the position.
+    Anchor,
+}
+
+///
+/// Background box shape behind text.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoxStyle {
+    Square,
+    Round,
+}
+
+impl BoxStyle {
+    ///
+    /// Box path over the local rectangle, with each point mapped to the
+    /// canvas by `p`.
+    ///
+    fn to_path(
+        &self,
+        [x0, y0, x1, y1]: [f32; 4],
+        radius: f32,
+        p: impl Fn(f32, f32) -> Point,
+    ) -> Path<Canvas> {
+        match self {
+            BoxStyle::Square => Path::new(vec![
+                PathCode::MoveTo(p(x0, y0)),
+                PathCode::LineTo(p(x1, y0)),
+                PathCode::LineTo(p(x1, y1)),
+                PathCode::ClosePoly(p(x0, y1)),
+            ]),
+            BoxStyle::Round => {
+                let r = radius.min(0.5 * (x1 - x0)).min(0.5 * (y1 - y0));
+
+                Path::new(vec![
+                    PathCode::MoveTo(p(x0 + r, y0)),
+                    PathCode::LineTo(p(x1 - r, y0)),
+                    PathCode::Bezier2(p(x1, y0), p(x1, y0 + r)),
+                    PathCode::LineTo(p(x1, y1 - r)),
+                    PathCode::Bezier2(p(x1, y1), p(x1 - r, y1)),
+                    PathCode::LineTo(p(x0 + r, y1)),
+                    PathCode::Bezier2(p(x0, y1), p(x0, y1 - r)),
+                    PathCode::LineTo(p(x0, y0 + r)),
+                    PathCode::Bezier2(p(x0, y0), p(x0 + r, y0)),
+                    PathCode::ClosePoly(p(x0 + r, y0)),
+                ])
+            }
+        }
+    }
+}
+
+///
+/// Optional background box for text, padded around the text extent.
+///
+#[derive(Clone)]
+pub(crate) struct TextBBox {
+    box_style: Option<BoxStyle>,
+    pad: f32,
+    path_style: PathStyle,
+}
+
+impl TextBBox {
+    pub(crate) fn new() -> Self {
+        let mut path_style = PathStyle::new();
+        path_style.face_color("white").edge_color("black");
+
+        Self {
+            box_style: None,
+            pad: 4.,
+            path_style,
+        }
+    }
+
+    pub(crate) fn from_config(cfg: &Config, prefix: &str) -> Self {
+        Self {
+            path_style: PathStyle::from_config(cfg, prefix),
+            .. Self::new()
+        }
+    }
+
+    pub(crate) fn box_style(&mut self, box_style: BoxStyle) -> &mut Self {
+        self.box_style = Some(box_style);
+
+        self
+    }
+
+    ///
+    /// Padding in points between the text and the box.
+    ///
+    pub(crate) fn pad(&mut self, pad: f32) -> &mut Self {
+        assert!(pad >= 0., "text bbox pad must be non-negative");
+        self.pad = pad;
+
+        self
+    }
+
+    pub(crate) fn get_pad(&self) -> f32 {
+        self.pad
+    }
+
+    pub(crate) fn path_style_mut(&mut self) -> &mut PathStyle {
+        &mut self.path_style
+    }
+
+    #[inline]
+    pub(crate) fn is_visible(&self) -> bool {
+        self.box_style.is_some()
+    }
+
+    fn pad_px(&self, renderer: &dyn Renderer) -> f32 {
+        if self.is_visible() { renderer.to_px(self.pad) } else { 0. }
+    }
+}
+
+///
+/// Lines of text and math laid out in em units, with the first line's
+/// baseline at `y = 0` and each line placed by the horizontal alignment.
+///
+pub(crate) struct TextLayout {
+    lines: Vec<(f32, f32, Layout)>,
+    line_spacing: f32,
+    size: f32,
+    em: f32,
+    width: f32,
+    top: f32,
+    bottom: f32,
+}
+
+impl TextLayout {
+    /// Baseline distance between lines as a multiple of the font size.
+    pub(crate) const LINE_SPACING: f32 = 1.2;
+
+    pub(crate) fn new(
+        renderer: &dyn Renderer,
+        text: &str,
+        text_style: &TextStyle,
+        line_spacing: f32,
+    ) -> Self {
+        let size = match text_style.get_size() {
+            Some(size) => *size,
+            None => TextStyle::SIZE_DEFAULT,
+        };
+
+        let layouts: Vec<Layout> = text.split('\n').map(layout_line).collect();
+        let width = layouts.iter().map(|layout| layout.width).fold(0., f32::max);
+
+        let n = layouts.len();
+        let top = layouts[0].ascent;
+        let bottom = - ((n - 1) as f32 * line_spacing) - layouts[n - 1].descent;
+
+        let lines = layouts.into_iter().enumerate().map(|(i, layout)| {
+            let x = match text_style.get_halign() {
+                Some(HorizAlign::Left) => 0.,
+                Some(HorizAlign::Right) => width - layout.width,
+                _ => 0.5 * (width - layout.width),
+            };
+
+            (x, - (i as f32) * line_spacing, layout)
+        }).collect();
+
+        Self {
+            lines,
+            line_spacing,
+            size,
+            em: renderer.to_px(size),
+            width,
+            top,
+            bottom,
+        }
+    }
+
+    ///
+    /// Width and height of the text block in canvas units before rotation,
+    /// where the height is one font size per line plus the line spacing.
+    ///
+    pub(crate) fn size(&self) -> (f32, f32) {
+        let n = self.lines.len() as f32;
+
+        (self.width * self.em, (1. + (n - 1.) * self.line_spacing) * self.em)
+    }
+
+    fn last_baseline(&self) -> f32 {
+        self.lines.last().map_or(0., |(_, y, _)| *y)
+    }
+
+    fn placement(
+        &self,
+        pos: Point,
+        angle: f32,
+        mode: RotationMode,
+        text_style: &TextStyle,
+    ) -> Placement {
+        let (sin, cos) = angle.sin_cos();
+        let rotate = |x: f32, y: f32| (x * cos - y * sin, x * sin + y * cos);
+
+        let (dx, dy) = match mode {
+            RotationMode::Anchor => {
+                let ax = halign_offset(text_style, 0., self.width);
+                let ay = match text_style.get_valign() {
+                    Some(VertAlign::BaselineBottom) => - self.last_baseline(),
+                    _ => valign_offset(text_style, self.bottom, self.top),
+                };
+
+                rotate(ax, ay)
+            }
+            RotationMode::Default => {
+                let corners = [
+                    rotate(0., self.bottom), rotate(self.width, self.bottom),
+                    rotate(self.width, self.top), rotate(0., self.top),
+                ];
+
+                let (x0, x1) = min_max(corners.iter().map(|c| c.0));
+                let (y0, y1) = min_max(corners.iter().map(|c| c.1));
+
+                let ay = match text_style.get_valign() {
+                    Some(VertAlign::BaselineBottom) => - rotate(0., self.last_baseline()).1,
+                    _ => valign_offset(text_style, y0, y1),
+                };
+
+                (halign_offset(text_style, x0, x1), ay)
+            }
+        };
+
+        Placement {
+            origin: Point(pos.x() + dx * self.em, pos.y() + dy * self.em),
+            sin,
+            cos,
+            em: self.em,
+        }
+    }
+
+    ///
+    /// Canvas bounds of the rotated text block placed at `pos`, including
+    /// the box padding when the box is visible.
+    ///
+    pub(crate) fn extent(
+        &self,
+        renderer: &dyn Renderer,
+        pos: Point,
+        angle: f32,
+        mode: RotationMode,
+        text_style: &TextStyle,
+        bbox: &TextBBox,
+    ) -> Bounds<Canvas> {
+        let place = self.placement(pos, angle, mode, text_style);
+        let pad = bbox.pad_px(renderer) / self.em;
+
+        let corners = [
+            place.point(- pad, self.bottom - pad),
+            place.point(self.width + pad, self.bottom - pad),
+            place.point(self.width + pad, self.top + pad),
+            place.point(- pad, self.top + pad),
+        ];
+
+        let (x0, x1) = min_max(corners.iter().map(|p| p.x()));
+        let (y0, y1) = min_max(corners.iter().map(|p| p.y()));
+
+        Bounds::new(Point(x0, y0), Point(x1, y1))
+    }
+
+    pub(crate) fn draw(
+        &self,
+        renderer: &mut dyn Renderer,
+        pos: Point,
+        angle: f32,
+        mode: RotationMode,
+        style: &dyn PathOpt,
+        text_style: &TextStyle,
+        bbox: &TextBBox,
+    ) -> Result<()> {
+        let place = self.placement(pos, angle, mode, text_style);
+
+        if let Some(box_style) = &bbox.box_style {
+            let pad = bbox.pad_px(renderer) / self.em;
+            let rect = [- pad, self.bottom - pad, self.width + pad, self.top + pad];
+
+            let path = box_style.to_path(rect, pad, |x, y| place.point(x, y));
+
+            renderer.draw_path(&path, &bbox.path_style.push(style))?;
+        }
+
+        let color = style.get_face_color()
+            .or(style.get_edge_color())
+            .unwrap_or(Color::black());
+
+        let size = self.size;
+
+        // glyphs are placed by their baseline origin
+        let mut glyph_style = text_style.clone();
+        glyph_style.valign(VertAlign::BaselineBottom);
+
+        for (x_line, y_line, layout) in &self.lines {
+            if let [Item::Glyphs { scale, text, .. }] = layout.items.as_slice() {
+                if *scale == 1. {
+                    // a plain line is centered by the renderer's own advances
+                    glyph_style.halign(HorizAlign::Center);
+
+                    let xy = place.point(x_line + 0.5 * layout.width, *y_line);
+                    renderer.draw_text(xy, text, angle, style, &glyph_style)?;
+
+                    continue;
+                }
+            }
+
+            for item in merge_glyphs(layout.items.clone()) {
+                match item {
+                    Item::Glyphs { x, y, scale, text } => {
+                        glyph_style.halign(HorizAlign::Left);
+                        glyph_style.size(size * scale);
+
+                        let xy = place.point(x_line + x, y_line + y);
+                        renderer.draw_text(xy, &text, angle, style, &glyph_style)?;
+
+                        glyph_style.size(size);
+                    }
+                    Item::Line { points, width } => {
+                        let codes: Vec<PathCode> = points.iter().enumerate().map(|(i, [x, y])| {
+                            let xy = place.point(x_line + x, y_line + y);
+
+                            if i == 0 { PathCode::MoveTo(xy) } else { PathCode::LineTo(xy) }
+                        }).collect();
+
+                        let mut line_style = PathStyle::new();
+                        line_style.edge_color(color);
+                        line_style.line_width(width * size);
+
+                        renderer.draw_path(&Path::<Canvas>::new(codes), &line_style.push(style))?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Canvas bounds of unrotated text drawn at `pos` without a box, for
+/// layout code that needs the measured size of labels.
+///
+pub(crate) fn text_extent(
+    renderer: &dyn Renderer,
+    pos: Point,
+    text: &str,
+    text_style: &TextStyle,
+) -> Bounds<Canvas> {
+    TextLayout::new(renderer, text, text_style, TextLayout::LINE_SPACING).extent(
+        renderer,
+        pos,
+        0.,
+        RotationMode::Anchor,
+        text_style,
+        &TextBBox::new(),
+    )
+}
+
+///
+/// Canvas placement of the layout's em coordinates.
+///
+struct Placement {
+    origin: Point,
+    sin: f32,
+    cos: f32,
+    em: f32,
+}
+
+impl Placement {
+    #[inline]
+    fn point(&self, x: f32, y: f32) -> Point {
+        let (x, y) = (x * self.em, y * self.em);
+
+        Point(
+            self.origin.x() + x * self.cos - y * self.sin,
+            self.origin.y() + x * self.sin + y * self.cos,
+        )
+    }
+}
+
+///
+/// Offset moving the `[x0, x1]` span to the horizontal alignment.
+///
+fn halign_offset(text_style: &TextStyle, x0: f32, x1: f32) -> f32 {
+    match text_style.get_halign() {
+        Some(HorizAlign::Left) => - x0,
+        Some(HorizAlign::Right) => - x1,
+        _ => -0.5 * (x0 + x1),
+    }
+}
+
+///
+/// Offset moving the `[y0, y1]` span to the vertical alignment, with the
+/// baseline at zero.
+///
+fn valign_offset(text_style: &TextStyle, y0: f32, y1: f32) -> f32 {
+    match text_style.get_valign() {
+        Some(VertAlign::Top) => - y1,
+        Some(VertAlign::Center) => -0.5 * (y0 + y1),
+        Some(VertAlign::Bottom) => - y0,
+        _ => 0.,
+    }
+}
+
+fn min_max(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    values.fold((f32::MAX, f32::MIN), |(min, max), v| (min.min(v), max.max(v)))
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{HorizAlign, TextStyle, VertAlign};
+
+    use super::{halign_offset, min_max, valign_offset};
+
+    #[test]
+    fn text_layout_align() {
+        let mut style = TextStyle::new();
+        assert_eq!(halign_offset(&style, 1., 3.), -2.);
+        assert_eq!(valign_offset(&style, -0.2, 0.7), 0.);
+
+        style.halign(HorizAlign::Left);
+        style.valign(VertAlign::Top);
+        assert_eq!(halign_offset(&style, 1., 3.), -1.);
+        assert_eq!(valign_offset(&style, -0.2, 0.7), -0.7);
+
+        style.halign(HorizAlign::Right);
+        style.valign(VertAlign::Bottom);
+        assert_eq!(halign_offset(&style, 1., 3.), -3.);
+        assert_eq!(valign_offset(&style, -0.2, 0.7), 0.2);
+    }
+
+    #[test]
+    fn text_layout_min_max() {
+        assert_eq!(min_max([2., -1., 4.].into_iter()), (-1., 4.));
+    }
+}
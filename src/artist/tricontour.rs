@@ -1,30 +1,38 @@
 use essay_graphics::api::{renderer::{Canvas, Renderer, Result}, Bounds, Path, PathOpt};
 use essay_tensor::tensor::Tensor;
 
-use crate::{chart::Data, config::PathStyle, contour::TriContourGenerator, transform::ToCanvas, tri::Triangulation};
-
-use super::ArtistDraw;
-
-pub struct Level {
-    paths: Vec<Path<Data>>,
-}
-
-impl Level {
-    fn new(paths: Vec<Path<Data>>) -> Self {
-        Self {
-            paths,
-        }
-    }
-}
+use crate::{
+    chart::{Data, LegendHandler},
+    config::{ConfigArc, PathStyle},
+    contour::TriContourGenerator,
+    data_artist_option_struct, path_style_options,
+    palette::{ColorMap, EssayColors},
+    transform::ToCanvas,
+    tri::Triangulation
+};
+
+use super::{
+    contour::{auto_levels, band_color, band_ranges, draw_bands, finite_range, Band},
+    Artist, ArtistDraw, ArtistView, ColorbarHandler, Extend
+};
 
 pub struct TriContour {
     data: Tensor,
-    // color_map: ColorMap,
-
     tri: Triangulation,
-    norm: Tensor,
-    levels: Vec<Level>,
+    color_map: ColorMap,
+    style: PathStyle,
+
+    thresholds: Option<Vec<f32>>,
+    n_levels: usize,
+    extend: Extend,
+    is_filled: bool,
+
+    level_values: Vec<f32>,
+    paths: Vec<Path<Data>>,
+    bands: Vec<Band>,
     bounds: Bounds<Data>,
+
+    is_stale: bool,
 }
 
 impl TriContour {
@@ -36,88 +44,195 @@ impl TriContour {
 
         Self {
             data,
+            bounds: Bounds::<Data>::from(tri.vertices()),
             tri,
-            norm: Tensor::from(None),
-            // color_map: ColorMaps::Default.into(),
-            bounds: Bounds::zero(),
-            levels: Vec::new(),
+            color_map: EssayColors::Default.into(),
+            style: PathStyle::new(),
+
+            thresholds: None,
+            n_levels: 7,
+            extend: Extend::Neither,
+            is_filled: false,
+
+            level_values: Vec::new(),
+            paths: Vec::new(),
+            bands: Vec::new(),
+
+            is_stale: true,
         }
     }
 
     pub(crate) fn _set_data(&mut self, data: Tensor) {
-        assert!(data.rank() == 2, "contour requires 2d value {:?}", data.shape());
+        assert!(data.rank() == 1, "contour requires 1d value {:?}", data.shape());
 
         self.data = data;
+        self.is_stale = true;
+    }
+
+    pub(crate) fn filled(&mut self, is_filled: bool) {
+        self.is_filled = is_filled;
+        self.is_stale = true;
     }
-    fn resize(&mut self, _renderer: &mut dyn Renderer) {
-        //let (rows, cols) = (self.data.rows(), self.data.cols());
 
-        //for vert in self.tri.triangles().iter_slice() {
-        //  xy.push([i as f32, j as f32]);
-        //}
+    fn resize(&mut self) {
+        if ! self.is_stale {
+            return;
+        }
+
+        self.is_stale = false;
 
-        self.bounds = Bounds::<Data>::from(self.tri.vertices());
+        let (min, max) = finite_range(self.data.as_slice());
 
-        self.norm = self.data.normalize_unit();
+        self.level_values = match &self.thresholds {
+            Some(thresholds) => thresholds.clone(),
+            None => auto_levels(min, max, self.n_levels, self.is_filled),
+        };
 
         let mut cg = TriContourGenerator::new(&self.tri, self.data.clone());
 
-        let level_thresholds = vec![
-            -1.5,
-            -1.,
-            -0.5, 
-            0., 
-            0.5,
-            1.,
-            1.5,
-            ];
-        let mut levels = Vec::<Level>::new();
-
-        for threshold in &level_thresholds {
-            let paths = cg.contour_lines(*threshold);
-
-            let paths: Vec<Path<Data>> = paths.iter()
-                .map(|p| Path::<Data>::lines(p))
-                .collect();
-
-            levels.push(Level::new(paths));
-        }
+        if self.is_filled {
+            let bands = band_ranges(&self.level_values, self.extend);
 
-        self.levels = levels;
+            self.bands = bands.iter().map(|(lower, upper)| {
+                Band {
+                    color: band_color(&self.color_map, &self.level_values, *lower, *upper),
+                    polygons: cg.contour_bands(*lower, *upper),
+                }
+            }).collect();
 
-        // self.xy = xy.into_tensor();
-    }
+            self.paths = Vec::new();
+        } else {
+            let mut paths = Vec::<Path<Data>>::new();
 
+            for threshold in &self.level_values {
+                for path in cg.contour_lines(*threshold) {
+                    paths.push(Path::<Data>::lines(&path));
+                }
+            }
+
+            self.paths = paths;
+            self.bands = Vec::new();
+        }
+    }
 }
 
 impl ArtistDraw<Data> for TriContour {
     fn bounds(&mut self) -> Bounds<Data> {
+        // the colorbar reads the levels before the first draw
+        self.resize();
+
         self.bounds.clone()
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         renderer: &mut dyn Renderer,
         to_canvas: &ToCanvas<Data>,
-        _style: &dyn PathOpt,
+        style: &dyn PathOpt,
     ) -> Result<()> {
-        self.resize(renderer);
-
-        let mut style = PathStyle::new();
+        self.resize();
 
-        style.edge_color("k");
-        style.line_width(1.);
+        if self.is_filled {
+            return draw_bands(renderer, to_canvas, &self.bands);
+        }
 
-        //renderer.draw_markers(&path, &xy, &tf32!(), &colors, &style, clip).unwrap();
+        let style = self.style.push(style);
 
-        for level in &self.levels {
-            for path in &level.paths {
-                let path : Path<Canvas> = to_canvas.transform_path(path);
+        for path in &self.paths {
+            let path : Path<Canvas> = to_canvas.transform_path(path);
 
-                renderer.draw_path(&path, &style)?;
-            }
+            renderer.draw_path(&path, &style)?;
         }
 
         Ok(())
     }
 }
+
+impl Artist<Data> for TriContour {
+    type Opt = TriContourOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.style = PathStyle::from_config(cfg, "contour");
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, TriContour>) -> Self::Opt {
+        TriContourOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        let n = self.level_values.len();
+
+        if n > 1 {
+            Some(ColorbarHandler::new(
+                self.color_map.clone(),
+                self.level_values[0],
+                self.level_values[n - 1]
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+data_artist_option_struct!(TriContourOpt, TriContour);
+
+impl TriContourOpt {
+    path_style_options!(style);
+
+    ///
+    /// Explicit, increasing level values.
+    ///
+    pub fn levels(&mut self, levels: &[f32]) -> &mut Self {
+        assert!(levels.len() > 0, "contour levels must not be empty");
+        assert!(
+            levels.windows(2).all(|w| w[0] < w[1]),
+            "contour levels must be increasing"
+        );
+
+        let levels = Vec::from(levels);
+
+        self.write(|artist| {
+            artist.thresholds = Some(levels);
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Approximate number of automatically chosen levels.
+    ///
+    pub fn n_levels(&mut self, n_levels: usize) -> &mut Self {
+        assert!(n_levels > 0);
+
+        self.write(|artist| {
+            artist.n_levels = n_levels;
+            artist.thresholds = None;
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn extend(&mut self, extend: Extend) -> &mut Self {
+        self.write(|artist| {
+            artist.extend = extend;
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn color_map(&mut self, cmap: impl Into<ColorMap>) -> &mut Self {
+        self.write(|artist| {
+            artist.color_map = cmap.into();
+            artist.is_stale = true;
+        });
+
+        self
+    }
+}
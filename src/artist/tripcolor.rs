@@ -0,0 +1,150 @@
+use essay_graphics::api::{
+    renderer::{Renderer, Result},
+    Bounds, Mesh2dColor, PathOpt
+};
+use essay_tensor::tensor::Tensor;
+
+use crate::{
+    chart::{Data, LegendHandler},
+    config::ConfigArc,
+    data_artist_option_struct,
+    palette::{ColorMap, EssayColors},
+    transform::ToCanvas,
+    tri::Triangulation
+};
+
+use super::{Artist, ArtistDraw, ArtistView, ColorbarHandler, Norm, Norms};
+
+///
+/// Colored triangles of a triangulation. Values per vertex are gouraud
+/// shaded across each triangle, and values per triangle are flat.
+/// Masked triangles are not drawn.
+///
+pub struct TriColor {
+    data: Tensor,
+    tri: Triangulation,
+    color_map: ColorMap,
+    norm: Norm,
+
+    is_stale: bool,
+}
+
+impl TriColor {
+    pub fn new(tri: impl Into<Triangulation>, data: impl Into<Tensor>) -> Self {
+        let tri: Triangulation = tri.into();
+        let data : Tensor = data.into();
+
+        assert!(data.rank() == 1, "tripcolor requires 1d value {:?}", data.shape());
+        assert!(
+            data.len() == tri.vertices().rows() || data.len() == tri.triangles().rows(),
+            "tripcolor values must match the vertices or the triangles {:?}", data.shape()
+        );
+
+        Self {
+            data,
+            tri,
+            color_map: EssayColors::Default.into(),
+            norm: Norm::from(Norms::Linear),
+            is_stale: true,
+        }
+    }
+
+    fn update(&mut self) {
+        if self.is_stale {
+            self.is_stale = false;
+
+            self.norm.set_bounds(&self.data);
+        }
+    }
+
+    fn is_gouraud(&self) -> bool {
+        self.data.len() == self.tri.vertices().rows()
+    }
+}
+
+impl ArtistDraw<Data> for TriColor {
+    fn bounds(&mut self) -> Bounds<Data> {
+        Bounds::<Data>::from(self.tri.vertices())
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        _style: &dyn PathOpt,
+    ) -> Result<()> {
+        self.update();
+
+        let xy = to_canvas.transform_tensor(self.tri.vertices());
+        let data = &self.data;
+        let is_gouraud = self.is_gouraud();
+
+        let color = |k: usize| {
+            self.color_map.map(self.norm.norm(data[k]).clamp(0., 1.))
+        };
+
+        let mut mesh = Mesh2dColor::new();
+
+        for (i, tri) in self.tri.triangles().iter_row().enumerate() {
+            if self.tri.is_masked(i) {
+                continue;
+            }
+
+            let (a, b, c) = (tri[0], tri[1], tri[2]);
+
+            let (ca, cb, cc) = if is_gouraud {
+                (color(a), color(b), color(c))
+            } else {
+                (color(i), color(i), color(i))
+            };
+
+            mesh.triangle(
+                ([xy[(a, 0)], xy[(a, 1)]], ca),
+                ([xy[(b, 0)], xy[(b, 1)]], cb),
+                ([xy[(c, 0)], xy[(c, 1)]], cc),
+            );
+        }
+
+        renderer.draw_mesh2d_color(&mesh)
+    }
+}
+
+impl Artist<Data> for TriColor {
+    type Opt = TriColorOpt;
+
+    fn config(&mut self, _cfg: &ConfigArc) {
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, TriColor>) -> Self::Opt {
+        TriColorOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        Some(ColorbarHandler::from_norm(self.color_map.clone(), &self.norm))
+    }
+}
+
+data_artist_option_struct!(TriColorOpt, TriColor);
+
+impl TriColorOpt {
+    pub fn norm(&mut self, norm: impl Into<Norm>) -> &mut Self {
+        self.write(|artist| {
+            artist.norm = norm.into();
+            artist.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn color_map(&mut self, cmap: impl Into<ColorMap>) -> &mut Self {
+        self.write(|artist| {
+            artist.color_map = cmap.into();
+        });
+
+        self
+    }
+}
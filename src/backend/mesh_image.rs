@@ -0,0 +1,186 @@
+use essay_graphics::api::{
+    renderer::Canvas, Bounds, Color, Mesh2dColor, Point,
+};
+
+///
+/// RGBA8 image recovered from a color mesh, with rows from the top.
+///
+pub(super) struct MeshImage {
+    pub(super) pos: Bounds<Canvas>,
+    pub(super) width: usize,
+    pub(super) height: usize,
+    pub(super) rgba: Vec<u8>,
+}
+
+pub(super) type Triangle = ([[f32; 2]; 3], [Color; 3]);
+
+pub(super) fn triangles(mesh: &Mesh2dColor) -> Vec<Triangle> {
+    mesh.triangles().map(|([p0, p1, p2], colors)| {
+        ([[p0[0], p0[1]], [p1[0], p1[1]], [p2[0], p2[1]]], colors)
+    }).collect()
+}
+
+///
+/// The image drawn by a mesh of flat-colored, axis-aligned cells of one
+/// size on a regular grid, where each cell is two triangles, as drawn by
+/// images and flat color grids on linear axes. Missing cells are
+/// transparent. Returns None for any other mesh.
+///
+pub(super) fn mesh_image(triangles: &[Triangle]) -> Option<MeshImage> {
+    if triangles.len() < 2 || triangles.len() % 2 != 0 {
+        return None;
+    }
+
+    let mut cells = Vec::<([f32; 4], Color)>::with_capacity(triangles.len() / 2);
+
+    for pair in triangles.chunks(2) {
+        let (p, c) = &pair[0];
+        let (q, d) = &pair[1];
+
+        let color = c[0];
+
+        if c.iter().chain(d.iter()).any(|other| other.0 != color.0) {
+            return None;
+        }
+
+        cells.push((cell_rect(p, q)?, color));
+    }
+
+    let [x0, y0, x1, y1] = cells[0].0;
+    let (w, h) = (x1 - x0, y1 - y0);
+
+    let (xmin, xmax) = cells.iter().fold((f32::MAX, f32::MIN), |(a, b), ([x0, _, x1, _], _)| {
+        (a.min(*x0), b.max(*x1))
+    });
+    let (ymin, ymax) = cells.iter().fold((f32::MAX, f32::MIN), |(a, b), ([_, y0, _, y1], _)| {
+        (a.min(*y0), b.max(*y1))
+    });
+
+    let width = ((xmax - xmin) / w).round() as usize;
+    let height = ((ymax - ymin) / h).round() as usize;
+
+    // a sparse mesh is cheaper as triangles
+    if width * height > 4 * cells.len() {
+        return None;
+    }
+
+    let (eps_x, eps_y) = (1e-2 * w, 1e-2 * h);
+
+    let mut rgba = vec![0u8; 4 * width * height];
+
+    for ([x0, y0, x1, y1], color) in &cells {
+        if ((x1 - x0) - w).abs() > eps_x || ((y1 - y0) - h).abs() > eps_y {
+            return None;
+        }
+
+        let i = ((x0 - xmin) / w).round();
+        let j = ((ymax - y1) / h).round();
+
+        if (xmin + i * w - x0).abs() > eps_x || (ymax - j * h - y1).abs() > eps_y {
+            return None;
+        }
+
+        let k = 4 * (j as usize * width + i as usize);
+
+        rgba[k..k + 4].copy_from_slice(&[color.r8(), color.g8(), color.b8(), color.a8()]);
+    }
+
+    Some(MeshImage {
+        pos: Bounds::new(Point(xmin, ymin), Point(xmax, ymax)),
+        width,
+        height,
+        rgba,
+    })
+}
+
+///
+/// The `[x0, y0, x1, y1]` rectangle covered by two triangles, when each
+/// is half of the rectangle split along the same diagonal.
+///
+fn cell_rect(p: &[[f32; 2]; 3], q: &[[f32; 2]; 3]) -> Option<[f32; 4]> {
+    let points = p.iter().chain(q.iter());
+
+    let (x0, x1) = points.clone().fold((f32::MAX, f32::MIN), |(a, b), pt| (a.min(pt[0]), b.max(pt[0])));
+    let (y0, y1) = points.fold((f32::MAX, f32::MIN), |(a, b), pt| (a.min(pt[1]), b.max(pt[1])));
+
+    if ! (x0 < x1 && y0 < y1) {
+        return None;
+    }
+
+    // corners as bits, 0 = (x0, y0), 1 = (x1, y0), 2 = (x0, y1), 3 = (x1, y1)
+    let corners = |tri: &[[f32; 2]; 3]| -> Option<u32> {
+        let mut mask = 0;
+
+        for [x, y] in tri {
+            let ix = if *x == x0 { 0 } else if *x == x1 { 1 } else { return None };
+            let iy = if *y == y0 { 0 } else if *y == y1 { 2 } else { return None };
+
+            mask |= 1 << (ix + iy);
+        }
+
+        if mask.count_ones() == 3 { Some(mask) } else { None }
+    };
+
+    let missing_p = (! corners(p)? & 0xf).trailing_zeros();
+    let missing_q = (! corners(q)? & 0xf).trailing_zeros();
+
+    // the triangles cover the rectangle when they miss opposite corners
+    if missing_p ^ missing_q == 3 {
+        Some([x0, y0, x1, y1])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::Color;
+
+    use super::{cell_rect, mesh_image, Triangle};
+
+    fn cell(x: f32, y: f32, color: Color) -> [Triangle; 2] {
+        let c = [color; 3];
+
+        [
+            ([[x, y], [x + 1., y], [x + 1., y + 1.]], c),
+            ([[x, y], [x + 1., y + 1.], [x, y + 1.]], c),
+        ]
+    }
+
+    #[test]
+    fn mesh_image_cell_rect() {
+        assert_eq!(
+            cell_rect(&[[0., 0.], [1., 0.], [1., 1.]], &[[0., 0.], [1., 1.], [0., 1.]]),
+            Some([0., 0., 1., 1.])
+        );
+
+        // overlapping halves don't cover the cell
+        assert_eq!(
+            cell_rect(&[[0., 0.], [1., 0.], [1., 1.]], &[[0., 0.], [1., 0.], [0., 1.]]),
+            None
+        );
+    }
+
+    #[test]
+    fn mesh_image_grid() {
+        let (red, blue) = (Color(0xff0000ff), Color(0x0000ffff));
+
+        let mut triangles = Vec::<Triangle>::new();
+        triangles.extend(cell(0., 0., red));
+        triangles.extend(cell(1., 0., blue));
+        triangles.extend(cell(1., 1., red));
+
+        let image = mesh_image(&triangles).unwrap();
+
+        assert_eq!((image.width, image.height), (2, 2));
+        // the top row holds the cell at y = 1 and a missing cell
+        assert_eq!(image.rgba, vec![
+            0, 0, 0, 0,  255, 0, 0, 255,
+            255, 0, 0, 255,  0, 0, 255, 255,
+        ]);
+
+        // gouraud colors aren't an image
+        triangles[0].1[1] = blue;
+        assert!(mesh_image(&triangles).is_none());
+    }
+}
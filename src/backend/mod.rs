@@ -0,0 +1,24 @@
+mod mesh_image;
+mod pdf;
+mod png;
+mod raster;
+mod scanline;
+mod stroke;
+mod svg;
+mod ttf;
+
+pub use pdf::{PageFormat, PdfRenderer};
+pub(crate) use pdf::helvetica_width;
+pub use raster::RasterRenderer;
+pub use svg::SvgRenderer;
+
+///
+/// Advance width of `text` in em, measured with the system font the
+/// raster and PDF backends draw with, or Helvetica without one.
+///
+pub(crate) fn text_width(text: &str) -> f32 {
+    match ttf::default_font() {
+        Some(font) => font.text_width(text) / font.units_per_em(),
+        None => helvetica_width(text),
+    }
+}
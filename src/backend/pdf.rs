@@ -0,0 +1,994 @@
+use std::fmt::Write;
+
+use essay_graphics::api::{
+    renderer::{Canvas, Drawable, FontId, Renderer, Result},
+    Bounds, CapStyle, Color, FontStyle, HorizAlign, JoinStyle, Mesh2dColor,
+    Path, PathCode, PathOpt, Point, TextStyle, VertAlign,
+    path_style::MeshStyle,
+};
+
+use super::{
+    mesh_image::{mesh_image, triangles, Triangle},
+    png::zlib_stored,
+    ttf::Font,
+};
+
+///
+/// Page description language written by `PdfRenderer`.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageFormat {
+    Pdf,
+    Eps,
+}
+
+///
+/// Headless vector renderer writing a single-page PDF or EPS document.
+///
+/// Text is drawn as filled glyph outlines of the system font used by the
+/// raster renderer, so any character the font covers, including Greek and
+/// the math alphabets, is drawn without embedding a font program. Without
+/// an installed font, text falls back to the standard Helvetica font,
+/// which only covers Latin-1.
+///
+/// Color meshes on a regular grid, such as images and color grids, are
+/// written as image XObjects (PDF) or `colorimage` data (EPS). Other
+/// meshes are written as free-form triangle shadings.
+///
+pub struct PdfRenderer {
+    format: PageFormat,
+
+    pos: Bounds<Canvas>,
+    dpi: f32,
+    scale_factor: f32,
+
+    content: String,
+
+    alphas: Vec<f32>,
+    images: Vec<PdfImage>,
+    shadings: Vec<PdfShading>,
+    fonts: Vec<FontStyle>,
+
+    font: Option<Font>,
+}
+
+struct PdfImage {
+    width: usize,
+    height: usize,
+    rgb: Vec<u8>,
+    alpha: Vec<u8>,
+}
+
+struct PdfShading {
+    decode: String,
+    data: Vec<u8>,
+}
+
+impl PdfRenderer {
+    ///
+    /// Creates a renderer for a `width` by `height` pixel canvas at `dpi`
+    /// pixels per inch. The page size is the canvas size in points.
+    ///
+    pub fn new(format: PageFormat, width: f32, height: f32, dpi: f32) -> Self {
+        Self {
+            format,
+
+            pos: Bounds::new(Point(0., 0.), Point(width, height)),
+            dpi,
+            scale_factor: dpi / 100.,
+
+            content: String::new(),
+
+            alphas: Vec::new(),
+            images: Vec::new(),
+            shadings: Vec::new(),
+            fonts: Vec::new(),
+
+            font: Font::load_default(),
+        }
+    }
+
+    pub fn draw(&mut self, drawable: &mut dyn Drawable) -> Result<()> {
+        drawable.draw(self)
+    }
+
+    ///
+    /// Draws an RGBA8 image of `width` by `height` pixels stretched over
+    /// `pos`, with the first row at the top.
+    ///
+    fn draw_image(
+        &mut self,
+        pos: &Bounds<Canvas>,
+        width: usize,
+        height: usize,
+        rgba: &[u8],
+    ) {
+        assert_eq!(rgba.len(), width * height * 4);
+
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        let mut alpha = Vec::with_capacity(width * height);
+
+        for px in rgba.chunks(4) {
+            rgb.extend_from_slice(&px[0..3]);
+            alpha.push(px[3]);
+        }
+
+        match self.format {
+            PageFormat::Pdf => {
+                let id = self.images.len();
+
+                writeln!(self.content, "q {} 0 0 {} {} {} cm /Im{} Do Q",
+                    fmt(pos.width()), fmt(pos.height()),
+                    fmt(pos.xmin()), fmt(pos.ymin()),
+                    id,
+                ).unwrap();
+
+                self.images.push(PdfImage { width, height, rgb, alpha });
+            }
+            PageFormat::Eps => {
+                // PostScript has no image alpha, so composite onto white
+                for (px, a) in rgb.chunks_mut(3).zip(alpha.iter()) {
+                    for c in px.iter_mut() {
+                        *c = ((*c as u32 * *a as u32 + 255 * (255 - *a as u32)) / 255) as u8;
+                    }
+                }
+
+                writeln!(self.content, "gsave {} {} translate {} {} scale",
+                    fmt(pos.xmin()), fmt(pos.ymin()),
+                    fmt(pos.width()), fmt(pos.height()),
+                ).unwrap();
+                writeln!(self.content, "/picstr {} string def", width * 3).unwrap();
+                writeln!(self.content,
+                    "{w} {h} 8 [{w} 0 0 -{h} 0 {h}] {{currentfile picstr readhexstring pop}} false 3 colorimage",
+                    w = width, h = height
+                ).unwrap();
+
+                for row in rgb.chunks(60) {
+                    for c in row {
+                        write!(self.content, "{:02x}", c).unwrap();
+                    }
+                    self.content.push('\n');
+                }
+
+                writeln!(self.content, "grestore").unwrap();
+            }
+        }
+    }
+
+    ///
+    /// Draws the triangles as a free-form (type 4) shading, which
+    /// interpolates the vertex colors without seams between triangles.
+    ///
+    fn draw_shading(&mut self, triangles: &[Triangle]) {
+        if triangles.len() == 0 {
+            return;
+        }
+
+        let (mut x0, mut y0, mut x1, mut y1) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+
+        for (points, _) in triangles {
+            for [x, y] in points {
+                (x0, x1) = (x0.min(*x), x1.max(*x));
+                (y0, y1) = (y0.min(*y), y1.max(*y));
+            }
+        }
+
+        let decode = format!("[{} {} {} {} 0 1 0 1 0 1]", fmt(x0), fmt(x1), fmt(y0), fmt(y1));
+
+        let alpha = triangles.iter()
+            .flat_map(|(_, colors)| colors.iter())
+            .map(|c| c.a8() as f32 / 255.)
+            .sum::<f32>() / (3 * triangles.len()) as f32;
+
+        self.push_state();
+        self.alpha_state(alpha);
+
+        match self.format {
+            PageFormat::Pdf => {
+                let data = shading_data(triangles, [x0, y0, x1, y1]);
+
+                writeln!(self.content, "/Sh{} sh", self.shadings.len()).unwrap();
+
+                self.shadings.push(PdfShading { decode, data });
+            }
+            PageFormat::Eps => {
+                // PostScript strings are limited to 64k bytes
+                for chunk in triangles.chunks(1500) {
+                    let data = shading_data(chunk, [x0, y0, x1, y1]);
+
+                    writeln!(self.content,
+                        "<< /ShadingType 4 /ColorSpace /DeviceRGB /BitsPerCoordinate 32 /BitsPerComponent 8 /BitsPerFlag 8 /Decode {} /DataSource <",
+                        decode
+                    ).unwrap();
+
+                    for row in data.chunks(60) {
+                        for c in row {
+                            write!(self.content, "{:02x}", c).unwrap();
+                        }
+                        self.content.push('\n');
+                    }
+
+                    writeln!(self.content, "> >> shfill").unwrap();
+                }
+            }
+        }
+
+        self.pop_state();
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self.format {
+            PageFormat::Pdf => self.to_pdf(),
+            PageFormat::Eps => self.to_eps(),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    ///
+    /// Page size in points.
+    ///
+    fn page_size(&self) -> (f32, f32) {
+        let scale = 72. / self.dpi;
+
+        (self.pos.width() * scale, self.pos.height() * scale)
+    }
+
+    fn to_pdf(&self) -> Vec<u8> {
+        let mut pdf = PdfWriter::new();
+
+        let (width, height) = self.page_size();
+        let scale = 72. / self.dpi;
+
+        let n_alpha = self.alphas.len();
+        let n_image = self.images.len();
+        let n_shading = self.shadings.len();
+
+        // object ids: fixed objects first, then alpha states, then each
+        // image and its soft mask, then shadings
+        let catalog = 1;
+        let pages = 2;
+        let page = 3;
+        let content = 4;
+        let font = 5;
+        let gs_base = 6;
+        let image_base = gs_base + n_alpha;
+        let shading_base = image_base + 2 * n_image;
+
+        let mut resources = String::new();
+        write!(resources, "/Font << /F1 {} 0 R >>", font).unwrap();
+
+        if n_alpha > 0 {
+            resources.push_str(" /ExtGState <<");
+            for i in 0..n_alpha {
+                write!(resources, " /GS{} {} 0 R", i, gs_base + i).unwrap();
+            }
+            resources.push_str(" >>");
+        }
+
+        if n_image > 0 {
+            resources.push_str(" /XObject <<");
+            for i in 0..n_image {
+                write!(resources, " /Im{} {} 0 R", i, image_base + 2 * i).unwrap();
+            }
+            resources.push_str(" >>");
+        }
+
+        if n_shading > 0 {
+            resources.push_str(" /Shading <<");
+            for i in 0..n_shading {
+                write!(resources, " /Sh{} {} 0 R", i, shading_base + i).unwrap();
+            }
+            resources.push_str(" >>");
+        }
+
+        pdf.object(catalog, format!("<< /Type /Catalog /Pages {} 0 R >>", pages).as_bytes());
+        pdf.object(pages, format!("<< /Type /Pages /Kids [{} 0 R] /Count 1 >>", page).as_bytes());
+        pdf.object(page, format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources << {} >> /Contents {} 0 R >>",
+            pages, fmt(width), fmt(height), resources, content
+        ).as_bytes());
+
+        let mut stream = String::new();
+        writeln!(stream, "{} 0 0 {} 0 0 cm", fmt(scale), fmt(scale)).unwrap();
+        stream.push_str(&self.content);
+
+        pdf.stream(content, "", stream.as_bytes());
+
+        pdf.object(font,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+        );
+
+        for (i, alpha) in self.alphas.iter().enumerate() {
+            pdf.object(gs_base + i, format!(
+                "<< /Type /ExtGState /ca {} /CA {} >>", fmt(*alpha), fmt(*alpha)
+            ).as_bytes());
+        }
+
+        for (i, image) in self.images.iter().enumerate() {
+            let id = image_base + 2 * i;
+
+            pdf.stream(id, &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode /SMask {} 0 R",
+                image.width, image.height, id + 1
+            ), &zlib_stored(&image.rgb));
+
+            pdf.stream(id + 1, &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /FlateDecode",
+                image.width, image.height
+            ), &zlib_stored(&image.alpha));
+        }
+
+        for (i, shading) in self.shadings.iter().enumerate() {
+            pdf.stream(shading_base + i, &format!(
+                "/ShadingType 4 /ColorSpace /DeviceRGB /BitsPerCoordinate 32 /BitsPerComponent 8 /BitsPerFlag 8 /Decode {} /Filter /FlateDecode",
+                shading.decode
+            ), &zlib_stored(&shading.data));
+        }
+
+        pdf.finish(catalog)
+    }
+
+    fn to_eps(&self) -> Vec<u8> {
+        let mut eps = String::new();
+
+        let (width, height) = self.page_size();
+        let scale = 72. / self.dpi;
+
+        writeln!(eps, "%!PS-Adobe-3.0 EPSF-3.0").unwrap();
+        writeln!(eps, "%%BoundingBox: 0 0 {} {}", width.ceil() as u32, height.ceil() as u32).unwrap();
+        writeln!(eps, "%%HiResBoundingBox: 0 0 {} {}", fmt(width), fmt(height)).unwrap();
+        writeln!(eps, "%%Creator: essay-plot").unwrap();
+        writeln!(eps, "%%LanguageLevel: 3").unwrap();
+        writeln!(eps, "%%Pages: 1").unwrap();
+        writeln!(eps, "%%EndComments").unwrap();
+        writeln!(eps, "/F1 /Helvetica findfont def").unwrap();
+        writeln!(eps, "gsave").unwrap();
+        writeln!(eps, "{} {} scale", fmt(scale), fmt(scale)).unwrap();
+        eps.push_str(&self.content);
+        writeln!(eps, "grestore").unwrap();
+        writeln!(eps, "showpage").unwrap();
+        writeln!(eps, "%%EOF").unwrap();
+
+        eps.into_bytes()
+    }
+
+    fn alpha_state(&mut self, alpha: f32) {
+        if self.format != PageFormat::Pdf || alpha >= 1. {
+            return;
+        }
+
+        let id = match self.alphas.iter().position(|a| *a == alpha) {
+            Some(id) => id,
+            None => {
+                self.alphas.push(alpha);
+                self.alphas.len() - 1
+            }
+        };
+
+        writeln!(self.content, "/GS{} gs", id).unwrap();
+    }
+
+    fn set_color(&mut self, color: Color, is_stroke: bool) {
+        let (r, g, b) = (
+            color.r8() as f32 / 255.,
+            color.g8() as f32 / 255.,
+            color.b8() as f32 / 255.,
+        );
+
+        match self.format {
+            PageFormat::Pdf => {
+                let op = if is_stroke { "RG" } else { "rg" };
+                writeln!(self.content, "{} {} {} {}", fmt(r), fmt(g), fmt(b), op).unwrap();
+            }
+            PageFormat::Eps => {
+                writeln!(self.content, "{} {} {} setrgbcolor", fmt(r), fmt(g), fmt(b)).unwrap();
+            }
+        }
+    }
+
+    fn set_line_style(&mut self, style: &dyn PathOpt) {
+        let lw = self.to_px(style.get_line_width().unwrap_or(1.));
+
+        let join = match style.get_join_style() {
+            Some(JoinStyle::Miter) => Some(0),
+            Some(JoinStyle::Round) => Some(1),
+            Some(JoinStyle::Bevel) => Some(2),
+            None => None,
+        };
+
+        let cap = match style.get_cap_style() {
+            Some(CapStyle::Butt) => Some(0),
+            Some(CapStyle::Round) => Some(1),
+            Some(CapStyle::Projecting) => Some(2),
+            None => None,
+        };
+
+        match self.format {
+            PageFormat::Pdf => {
+                writeln!(self.content, "{} w", fmt(lw)).unwrap();
+                if let Some(join) = join {
+                    writeln!(self.content, "{} j", join).unwrap();
+                }
+                if let Some(cap) = cap {
+                    writeln!(self.content, "{} J", cap).unwrap();
+                }
+            }
+            PageFormat::Eps => {
+                writeln!(self.content, "{} setlinewidth", fmt(lw)).unwrap();
+                if let Some(join) = join {
+                    writeln!(self.content, "{} setlinejoin", join).unwrap();
+                }
+                if let Some(cap) = cap {
+                    writeln!(self.content, "{} setlinecap", cap).unwrap();
+                }
+            }
+        }
+    }
+
+    fn write_path(&mut self, path: &Path<Canvas>) {
+        let is_pdf = self.format == PageFormat::Pdf;
+        let (m, l, c, h) = if is_pdf {
+            ("m", "l", "c", "h")
+        } else {
+            ("moveto", "lineto", "curveto", "closepath")
+        };
+
+        if ! is_pdf {
+            writeln!(self.content, "newpath").unwrap();
+        }
+
+        let mut last = Point(0., 0.);
+
+        for code in path.codes() {
+            match code {
+                PathCode::MoveTo(p) => {
+                    writeln!(self.content, "{} {} {}", fmt(p.x()), fmt(p.y()), m).unwrap();
+                    last = *p;
+                }
+                PathCode::LineTo(p) => {
+                    writeln!(self.content, "{} {} {}", fmt(p.x()), fmt(p.y()), l).unwrap();
+                    last = *p;
+                }
+                PathCode::Bezier2(p1, p2) => {
+                    // elevate the quadratic to a cubic
+                    let c1 = Point(
+                        last.x() + 2. / 3. * (p1.x() - last.x()),
+                        last.y() + 2. / 3. * (p1.y() - last.y()),
+                    );
+                    let c2 = Point(
+                        p2.x() + 2. / 3. * (p1.x() - p2.x()),
+                        p2.y() + 2. / 3. * (p1.y() - p2.y()),
+                    );
+
+                    writeln!(self.content, "{} {} {} {} {} {} {}",
+                        fmt(c1.x()), fmt(c1.y()),
+                        fmt(c2.x()), fmt(c2.y()),
+                        fmt(p2.x()), fmt(p2.y()),
+                        c
+                    ).unwrap();
+                    last = *p2;
+                }
+                PathCode::Bezier3(p1, p2, p3) => {
+                    writeln!(self.content, "{} {} {} {} {} {} {}",
+                        fmt(p1.x()), fmt(p1.y()),
+                        fmt(p2.x()), fmt(p2.y()),
+                        fmt(p3.x()), fmt(p3.y()),
+                        c
+                    ).unwrap();
+                    last = *p3;
+                }
+                PathCode::ClosePoly(p) => {
+                    writeln!(self.content, "{} {} {} {}", fmt(p.x()), fmt(p.y()), l, h).unwrap();
+                    last = *p;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Writes the path and paints it. PDF requires the graphics state
+    /// before path construction, PostScript allows it between fill and
+    /// stroke.
+    ///
+    fn paint_path(
+        &mut self,
+        path: &Path<Canvas>,
+        fill: Option<Color>,
+        stroke: Option<(Color, &dyn PathOpt)>,
+    ) {
+        match self.format {
+            PageFormat::Pdf => {
+                if let Some(color) = fill {
+                    self.set_color(color, false);
+                }
+                if let Some((color, style)) = stroke {
+                    self.set_color(color, true);
+                    self.set_line_style(style);
+                }
+
+                self.write_path(path);
+
+                let op = match (fill.is_some(), stroke.is_some()) {
+                    (true, true) => "B",
+                    (true, false) => "f",
+                    (false, true) => "S",
+                    (false, false) => "n",
+                };
+
+                writeln!(self.content, "{}", op).unwrap();
+            }
+            PageFormat::Eps => {
+                self.write_path(path);
+
+                if let Some(color) = fill {
+                    self.set_color(color, false);
+
+                    let op = if stroke.is_some() { "gsave fill grestore" } else { "fill" };
+                    writeln!(self.content, "{}", op).unwrap();
+                }
+                if let Some((color, style)) = stroke {
+                    self.set_color(color, true);
+                    self.set_line_style(style);
+                    writeln!(self.content, "stroke").unwrap();
+                }
+            }
+        }
+    }
+
+    fn push_state(&mut self) {
+        let op = if self.format == PageFormat::Pdf { "q" } else { "gsave" };
+        writeln!(self.content, "{}", op).unwrap();
+    }
+
+    fn pop_state(&mut self) {
+        let op = if self.format == PageFormat::Pdf { "Q" } else { "grestore" };
+        writeln!(self.content, "{}", op).unwrap();
+    }
+}
+
+impl Renderer for PdfRenderer {
+    fn pos(&self) -> Bounds<Canvas> {
+        self.pos.clone()
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn to_px(&self, size: f32) -> f32 {
+        size * self.scale_factor
+    }
+
+    fn flush(&mut self) {
+    }
+
+    fn font(&mut self, style: &FontStyle) -> Result<FontId> {
+        self.fonts.push(style.clone());
+
+        Ok(FontId(self.fonts.len() - 1))
+    }
+
+    fn draw_path(&mut self, path: &Path<Canvas>, style: &dyn PathOpt) -> Result<()> {
+        if path.codes().len() == 0 {
+            return Ok(());
+        }
+
+        let is_closed = is_closed(path);
+
+        let fill = match style.get_face_color() {
+            Some(color) if is_closed && ! color.is_none() => Some(color),
+            _ => None,
+        };
+
+        let stroke = match style.get_edge_color() {
+            Some(color) if ! color.is_none() => Some(color),
+            _ if ! is_closed => Some(style.get_face_color().unwrap_or(Color::black())),
+            _ => None,
+        };
+
+        let alpha = style.get_alpha().unwrap_or(1.)
+            * fill.or(stroke).map_or(1., |c| c.a8() as f32 / 255.);
+
+        self.push_state();
+        self.alpha_state(alpha);
+        self.paint_path(path, fill, stroke.map(|c| (c, style)));
+        self.pop_state();
+
+        Ok(())
+    }
+
+    fn draw_markers(
+        &mut self,
+        path: &Path<Canvas>,
+        style: &dyn PathOpt,
+        markers: &[MeshStyle],
+    ) -> Result<()> {
+        let is_closed = is_closed(path);
+        let edge = match style.get_edge_color() {
+            Some(color) if ! color.is_none() => Some(color),
+            _ => None,
+        };
+
+        for marker in markers {
+            let marker_path = marker.affine.transform_path(path);
+
+            let fill = if is_closed && ! marker.color.is_none() {
+                Some(marker.color)
+            } else {
+                None
+            };
+
+            let stroke = if is_closed { edge } else { edge.or(Some(marker.color)) };
+
+            let alpha = style.get_alpha().unwrap_or(1.) * marker.color.a8() as f32 / 255.;
+
+            self.push_state();
+            self.alpha_state(alpha);
+            self.paint_path(&marker_path, fill, stroke.map(|c| (c, style)));
+            self.pop_state();
+        }
+
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        xy: Point,
+        text: &str,
+        angle: f32,
+        style: &dyn PathOpt,
+        text_style: &TextStyle,
+    ) -> Result<()> {
+        let size = self.to_px(text_style.get_size().unwrap_or(10.));
+
+        let color = style.get_face_color()
+            .or(style.get_edge_color())
+            .unwrap_or(Color::black());
+
+        let alpha = style.get_alpha().unwrap_or(1.) * color.a8() as f32 / 255.;
+        let (sin, cos) = angle.sin_cos();
+
+        if let Some(font) = &mut self.font {
+            let scale = size / font.units_per_em();
+            let [dx, dy] = font.align(font.text_width(text), text_style);
+
+            let path = font.text_path(text, |x, y| {
+                let (x, y) = ((dx + x) * scale, (dy + y) * scale);
+
+                Point(xy.x() + x * cos - y * sin, xy.y() + x * sin + y * cos)
+            });
+
+            if let Some(path) = path {
+                self.push_state();
+                self.alpha_state(alpha);
+                self.paint_path(&path, Some(color), None);
+                self.pop_state();
+            }
+
+            return Ok(());
+        }
+
+        let width = helvetica_width(text) * size;
+
+        let dx = match text_style.get_halign() {
+            Some(HorizAlign::Left) => 0.,
+            Some(HorizAlign::Right) => - width,
+            _ => -0.5 * width,
+        };
+
+        // Helvetica cap height and descender, as a fraction of the size
+        let dy = match text_style.get_valign() {
+            Some(VertAlign::Top) => -0.718 * size,
+            Some(VertAlign::Center) => -0.359 * size,
+            Some(VertAlign::Bottom) => 0.207 * size,
+            _ => 0.,
+        };
+
+        let text = escape(text);
+
+        self.push_state();
+        self.alpha_state(alpha);
+        self.set_color(color, false);
+
+        match self.format {
+            PageFormat::Pdf => {
+                writeln!(self.content, "BT /F1 {} Tf {} {} {} {} {} {} Tm {} {} Td ({}) Tj ET",
+                    fmt(size),
+                    fmt(cos), fmt(sin), fmt(-sin), fmt(cos),
+                    fmt(xy.x()), fmt(xy.y()),
+                    fmt(dx), fmt(dy),
+                    text,
+                ).unwrap();
+            }
+            PageFormat::Eps => {
+                writeln!(self.content, "F1 {} scalefont setfont", fmt(size)).unwrap();
+                writeln!(self.content, "{} {} translate {} rotate {} {} moveto ({}) show",
+                    fmt(xy.x()), fmt(xy.y()),
+                    fmt(angle.to_degrees()),
+                    fmt(dx), fmt(dy),
+                    text,
+                ).unwrap();
+            }
+        }
+
+        self.pop_state();
+
+        Ok(())
+    }
+
+    fn draw_mesh2d_color(&mut self, mesh: &Mesh2dColor) -> Result<()> {
+        let triangles: Vec<Triangle> = triangles(mesh).into_iter()
+            .filter(|(_, colors)| colors.iter().any(|c| c.a8() > 0))
+            .collect();
+
+        if triangles.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(image) = mesh_image(&triangles) {
+            // PostScript images have no alpha, so transparent cells would
+            // cover what's below
+            let is_opaque = image.rgba.chunks(4).all(|px| px[3] == 255);
+
+            if self.format == PageFormat::Pdf || is_opaque {
+                self.draw_image(&image.pos, image.width, image.height, &image.rgba);
+
+                return Ok(());
+            }
+        }
+
+        self.draw_shading(&triangles);
+
+        Ok(())
+    }
+
+    fn draw_with_clip(
+        &mut self,
+        pos: Bounds<Canvas>,
+        f: Box<dyn FnOnce(&mut dyn Renderer) -> Result<()> + '_>,
+    ) -> Result<()> {
+        self.push_state();
+
+        match self.format {
+            PageFormat::Pdf => {
+                writeln!(self.content, "{} {} {} {} re W n",
+                    fmt(pos.xmin()), fmt(pos.ymin()), fmt(pos.width()), fmt(pos.height())
+                ).unwrap();
+            }
+            PageFormat::Eps => {
+                writeln!(self.content, "{} {} {} {} rectclip",
+                    fmt(pos.xmin()), fmt(pos.ymin()), fmt(pos.width()), fmt(pos.height())
+                ).unwrap();
+            }
+        }
+
+        let result = (f)(self);
+
+        self.pop_state();
+
+        result
+    }
+}
+
+///
+/// Writes numbered objects and the cross-reference table.
+///
+struct PdfWriter {
+    data: Vec<u8>,
+    offsets: Vec<(usize, usize)>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        let mut data = Vec::new();
+        data.extend_from_slice(b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n");
+
+        Self {
+            data,
+            offsets: Vec::new(),
+        }
+    }
+
+    fn object(&mut self, id: usize, body: &[u8]) {
+        self.offsets.push((id, self.data.len()));
+
+        self.data.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        self.data.extend_from_slice(body);
+        self.data.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn stream(&mut self, id: usize, dict: &str, data: &[u8]) {
+        self.offsets.push((id, self.data.len()));
+
+        self.data.extend_from_slice(format!(
+            "{} 0 obj\n<< {} /Length {} >>\nstream\n", id, dict, data.len()
+        ).as_bytes());
+        self.data.extend_from_slice(data);
+        self.data.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    fn finish(mut self, root: usize) -> Vec<u8> {
+        self.offsets.sort();
+
+        let n = self.offsets.len() + 1;
+        let xref = self.data.len();
+
+        let mut tail = String::new();
+        writeln!(tail, "xref\n0 {}", n).unwrap();
+        writeln!(tail, "0000000000 65535 f ").unwrap();
+        for (_, offset) in &self.offsets {
+            writeln!(tail, "{:010} 00000 n ", offset).unwrap();
+        }
+        writeln!(tail, "trailer\n<< /Size {} /Root {} 0 R >>", n, root).unwrap();
+        writeln!(tail, "startxref\n{}\n%%EOF", xref).unwrap();
+
+        self.data.extend_from_slice(tail.as_bytes());
+
+        self.data
+    }
+}
+
+fn is_closed(path: &Path<Canvas>) -> bool {
+    match path.codes().last() {
+        Some(PathCode::ClosePoly(_)) => true,
+        _ => false,
+    }
+}
+
+///
+/// Escapes a string literal for the Helvetica fallback. Latin-1
+/// characters are written as octal escapes and other characters are
+/// replaced by `?`.
+///
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '(' | ')' | '\\' => { out.push('\\'); out.push(ch); }
+            ' '..='~' => out.push(ch),
+            '\u{a0}'..='\u{ff}' => { write!(out, "\\{:03o}", ch as u32).unwrap(); }
+            '\u{2212}' => out.push('-'),
+            _ => out.push('?'),
+        }
+    }
+
+    out
+}
+
+// Helvetica advance widths for ' '..='~' in 1/1000 em
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+///
+/// Width of `text` in Helvetica, as a multiple of the font size.
+///
+pub(crate) fn helvetica_width(text: &str) -> f32 {
+    let width: u32 = text.chars().map(|ch| {
+        match ch {
+            ' '..='~' => HELVETICA_WIDTHS[ch as usize - ' ' as usize] as u32,
+            _ => 556,
+        }
+    }).sum();
+
+    width as f32 / 1000.
+}
+
+///
+/// Type 4 shading data: for each vertex a flag byte, 32-bit coordinates
+/// over the `[x0, y0, x1, y1]` decode range and 8-bit RGB.
+///
+fn shading_data(triangles: &[Triangle], [x0, y0, x1, y1]: [f32; 4]) -> Vec<u8> {
+    let coord = |v: f32, min: f32, max: f32| -> [u8; 4] {
+        let t = if max > min { ((v - min) as f64 / (max - min) as f64).clamp(0., 1.) } else { 0. };
+
+        ((t * u32::MAX as f64).round() as u32).to_be_bytes()
+    };
+
+    let mut data = Vec::<u8>::with_capacity(36 * triangles.len());
+
+    for (points, colors) in triangles {
+        for ([x, y], c) in points.iter().zip(colors.iter()) {
+            data.push(0);
+            data.extend_from_slice(&coord(*x, x0, x1));
+            data.extend_from_slice(&coord(*y, y0, y1));
+            data.extend_from_slice(&[c.r8(), c.g8(), c.b8()]);
+        }
+    }
+
+    data
+}
+
+fn fmt(value: f32) -> String {
+    let s = format!("{:.3}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+
+    if s == "-0" || s.is_empty() { "0".to_string() } else { s.to_string() }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{renderer::Renderer, Color, Mesh2dColor};
+
+    use super::{escape, helvetica_width, PageFormat, PdfRenderer};
+
+    #[test]
+    fn pdf_escape() {
+        assert_eq!(escape("f(x)"), "f\\(x\\)");
+        assert_eq!(escape("a\\b"), "a\\\\b");
+        assert_eq!(escape("10°"), "10\\260");
+    }
+
+    #[test]
+    fn pdf_helvetica_width() {
+        assert_eq!(helvetica_width(""), 0.);
+        assert!((helvetica_width("10") - 1.112).abs() < 1e-6);
+    }
+
+    #[test]
+    fn pdf_empty_document() {
+        let pdf = PdfRenderer::new(PageFormat::Pdf, 200., 100., 100.).to_bytes();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/MediaBox [0 0 144 72]"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+
+        let eps = PdfRenderer::new(PageFormat::Eps, 200., 100., 100.).to_bytes();
+        let text = String::from_utf8_lossy(&eps);
+
+        assert!(text.starts_with("%!PS-Adobe-3.0 EPSF-3.0"));
+        assert!(text.contains("%%BoundingBox: 0 0 144 72"));
+    }
+
+    #[test]
+    fn pdf_mesh() {
+        let (red, blue) = (Color(0xff0000ff), Color(0x0000ffff));
+
+        // flat cells are an image
+        let mut mesh = Mesh2dColor::new();
+        mesh.triangle(([0., 0.], red), ([1., 0.], red), ([1., 1.], red));
+        mesh.triangle(([0., 0.], red), ([1., 1.], red), ([0., 1.], red));
+
+        let mut pdf = PdfRenderer::new(PageFormat::Pdf, 200., 100., 100.);
+        pdf.draw_mesh2d_color(&mesh).unwrap();
+        let text = String::from_utf8_lossy(&pdf.to_bytes()).to_string();
+
+        assert!(text.contains("/Im0 Do"));
+        assert!(! text.contains("/ShadingType"));
+
+        // interpolated colors are a shading
+        let mut mesh = Mesh2dColor::new();
+        mesh.triangle(([0., 0.], red), ([1., 0.], blue), ([1., 1.], red));
+
+        let mut pdf = PdfRenderer::new(PageFormat::Pdf, 200., 100., 100.);
+        pdf.draw_mesh2d_color(&mesh).unwrap();
+        let text = String::from_utf8_lossy(&pdf.to_bytes()).to_string();
+
+        assert!(text.contains("/Sh0 sh"));
+        assert!(text.contains("/ShadingType 4"));
+
+        let mut eps = PdfRenderer::new(PageFormat::Eps, 200., 100., 100.);
+        eps.draw_mesh2d_color(&mesh).unwrap();
+        let text = String::from_utf8_lossy(&eps.to_bytes()).to_string();
+
+        assert!(text.contains("shfill"));
+
+        // an empty mesh draws nothing
+        let mut pdf = PdfRenderer::new(PageFormat::Pdf, 200., 100., 100.);
+        pdf.draw_mesh2d_color(&Mesh2dColor::new()).unwrap();
+        let text = String::from_utf8_lossy(&pdf.to_bytes()).to_string();
+
+        assert!(! text.contains("/ShadingType"));
+        assert!(! text.contains("NaN"));
+    }
+}
@@ -0,0 +1,539 @@
+use std::io;
+
+use essay_graphics::api::{
+    renderer::{Canvas, Drawable, FontId, Renderer, Result},
+    Bounds, CapStyle, Color, FontStyle, JoinStyle, Mesh2dColor,
+    Path, PathCode, PathOpt, Point, TextStyle,
+    path_style::MeshStyle,
+};
+
+use super::{
+    png::encode_png,
+    scanline::EdgeList,
+    stroke::{Cap, Join, Stroker},
+    ttf::Font,
+};
+
+///
+/// Headless software renderer producing an RGBA image.
+///
+/// Paths are filled and stroked with anti-aliased scanline coverage,
+/// gouraud meshes are interpolated per pixel and text is drawn from
+/// TrueType outlines. Used for PNG output when no wgpu adapter is
+/// available.
+///
+pub struct RasterRenderer {
+    width: usize,
+    height: usize,
+    scale_factor: f32,
+
+    // straight RGB over an opaque background
+    pixels: Vec<[f32; 3]>,
+    clip: Vec<[usize; 4]>,
+
+    font: Font,
+}
+
+impl RasterRenderer {
+    ///
+    /// Creates a renderer drawing text with the first installed system
+    /// font of a short list of common sans-serif fonts. Fails when none is
+    /// installed, because a chart without text would be silently wrong.
+    ///
+    pub fn new(width: usize, height: usize, scale_factor: f32) -> io::Result<Self> {
+        match Font::load_default() {
+            Some(font) => Ok(Self::with_loaded_font(width, height, scale_factor, font)),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no system TrueType font found, use RasterRenderer::with_font"
+            )),
+        }
+    }
+
+    ///
+    /// Creates a renderer drawing text with the TrueType font in `font`.
+    ///
+    pub fn with_font(
+        width: usize,
+        height: usize,
+        scale_factor: f32,
+        font: Vec<u8>,
+    ) -> io::Result<Self> {
+        match Font::from_bytes(font) {
+            Some(font) => Ok(Self::with_loaded_font(width, height, scale_factor, font)),
+            None => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid TrueType font")),
+        }
+    }
+
+    fn with_loaded_font(width: usize, height: usize, scale_factor: f32, font: Font) -> Self {
+        Self {
+            width,
+            height,
+            scale_factor,
+
+            pixels: vec![[1., 1., 1.]; width * height],
+            clip: Vec::new(),
+
+            font,
+        }
+    }
+
+    pub fn draw(&mut self, drawable: &mut dyn Drawable) -> Result<()> {
+        drawable.draw(self)
+    }
+
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(4 * self.pixels.len());
+
+        for [r, g, b] in &self.pixels {
+            rgba.push((r.clamp(0., 1.) * 255. + 0.5) as u8);
+            rgba.push((g.clamp(0., 1.) * 255. + 0.5) as u8);
+            rgba.push((b.clamp(0., 1.) * 255. + 0.5) as u8);
+            rgba.push(255);
+        }
+
+        rgba
+    }
+
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, encode_png(self.width, self.height, &self.to_rgba8()))
+    }
+
+    fn clip_rect(&self) -> [usize; 4] {
+        match self.clip.last() {
+            Some(clip) => *clip,
+            None => [0, 0, self.width, self.height],
+        }
+    }
+
+    #[inline]
+    fn to_pixel(&self, p: Point) -> [f32; 2] {
+        [p.x(), self.height as f32 - p.y()]
+    }
+
+    #[inline]
+    fn blend(&mut self, x: usize, y: usize, color: Color, coverage: f32) {
+        let a = coverage * color.a8() as f32 / 255.;
+
+        if a <= 0. {
+            return;
+        }
+
+        let px = &mut self.pixels[y * self.width + x];
+        let src = [
+            color.r8() as f32 / 255.,
+            color.g8() as f32 / 255.,
+            color.b8() as f32 / 255.,
+        ];
+
+        for k in 0..3 {
+            px[k] = src[k] * a + px[k] * (1. - a);
+        }
+    }
+
+    fn fill_edges(&mut self, edges: &mut EdgeList, color: Color, alpha: f32) {
+        if edges.is_empty() || color.is_none() {
+            return;
+        }
+
+        let clip = self.clip_rect();
+        let mut spans = Vec::<(usize, usize, f32)>::new();
+
+        edges.fill(clip, |x, y, coverage| spans.push((x, y, coverage)));
+
+        for (x, y, coverage) in spans {
+            self.blend(x, y, color, alpha * coverage);
+        }
+    }
+
+    ///
+    /// Flattens a path into pixel-space polylines with a closed flag.
+    ///
+    fn flatten(&self, path: &Path<Canvas>) -> Vec<(Vec<[f32; 2]>, bool)> {
+        let mut lines = Vec::<(Vec<[f32; 2]>, bool)>::new();
+        let mut line = Vec::<[f32; 2]>::new();
+
+        for code in path.codes() {
+            match code {
+                PathCode::MoveTo(p) => {
+                    if line.len() > 0 {
+                        lines.push((line, false));
+                    }
+                    line = vec![self.to_pixel(*p)];
+                }
+                PathCode::LineTo(p) => {
+                    line.push(self.to_pixel(*p));
+                }
+                PathCode::Bezier2(p1, p2) => {
+                    let p0 = *line.last().unwrap_or(&self.to_pixel(*p1));
+                    let p1 = self.to_pixel(*p1);
+                    let p2 = self.to_pixel(*p2);
+
+                    quad_to(&mut line, p0, p1, p2);
+                }
+                PathCode::Bezier3(p1, p2, p3) => {
+                    let p0 = *line.last().unwrap_or(&self.to_pixel(*p1));
+                    let p1 = self.to_pixel(*p1);
+                    let p2 = self.to_pixel(*p2);
+                    let p3 = self.to_pixel(*p3);
+
+                    cubic_to(&mut line, p0, p1, p2, p3);
+                }
+                PathCode::ClosePoly(p) => {
+                    line.push(self.to_pixel(*p));
+                    lines.push((line, true));
+                    line = Vec::new();
+                }
+            }
+        }
+
+        if line.len() > 0 {
+            lines.push((line, false));
+        }
+
+        lines
+    }
+
+    fn fill_path(&mut self, lines: &[(Vec<[f32; 2]>, bool)], color: Color, alpha: f32) {
+        let mut edges = EdgeList::new();
+
+        for (line, _) in lines {
+            edges.add_poly(line);
+        }
+
+        self.fill_edges(&mut edges, color, alpha);
+    }
+
+    fn stroke_path(
+        &mut self,
+        lines: &[(Vec<[f32; 2]>, bool)],
+        style: &dyn PathOpt,
+        color: Color,
+        alpha: f32,
+    ) {
+        let width = self.to_px(style.get_line_width().unwrap_or(1.));
+
+        let join = match style.get_join_style() {
+            Some(JoinStyle::Round) => Join::Round,
+            Some(JoinStyle::Bevel) => Join::Bevel,
+            _ => Join::Miter,
+        };
+
+        let cap = match style.get_cap_style() {
+            Some(CapStyle::Round) => Cap::Round,
+            Some(CapStyle::Projecting) => Cap::Square,
+            _ => Cap::Butt,
+        };
+
+        let stroker = Stroker::new(width, join, cap);
+        let mut edges = EdgeList::new();
+
+        for (line, is_closed) in lines {
+            stroker.stroke(line, *is_closed, &mut edges);
+        }
+
+        self.fill_edges(&mut edges, color, alpha);
+    }
+
+    fn draw_triangle(&mut self, p: [[f32; 2]; 3], c: [Color; 3]) {
+        let [cx0, cy0, cx1, cy1] = self.clip_rect();
+
+        let x_min = p[0][0].min(p[1][0]).min(p[2][0]);
+        let x_max = p[0][0].max(p[1][0]).max(p[2][0]);
+        let y_min = p[0][1].min(p[1][1]).min(p[2][1]);
+        let y_max = p[0][1].max(p[1][1]).max(p[2][1]);
+
+        let px0 = (x_min.floor().max(0.) as usize).max(cx0);
+        let px1 = (x_max.ceil().max(0.) as usize).min(cx1);
+        let py0 = (y_min.floor().max(0.) as usize).max(cy0);
+        let py1 = (y_max.ceil().max(0.) as usize).min(cy1);
+
+        let area = edge_fn(p[0], p[1], p[2]);
+        if area.abs() < 1e-12 {
+            return;
+        }
+
+        let rgba: Vec<[f32; 4]> = c.iter().map(|c| [
+            c.r8() as f32, c.g8() as f32, c.b8() as f32, c.a8() as f32
+        ]).collect();
+
+        for py in py0..py1 {
+            for px in px0..px1 {
+                let q = [px as f32 + 0.5, py as f32 + 0.5];
+
+                let w0 = edge_fn(p[1], p[2], q) / area;
+                let w1 = edge_fn(p[2], p[0], q) / area;
+                let w2 = edge_fn(p[0], p[1], q) / area;
+
+                // pixel centers on shared edges belong to both triangles;
+                // the second write is identical for matching vertex colors
+                if w0 < 0. || w1 < 0. || w2 < 0. {
+                    continue;
+                }
+
+                let mut v = [0u32; 4];
+                for k in 0..4 {
+                    v[k] = (w0 * rgba[0][k] + w1 * rgba[1][k] + w2 * rgba[2][k])
+                        .round().clamp(0., 255.) as u32;
+                }
+
+                let color = Color(v[0] << 24 | v[1] << 16 | v[2] << 8 | v[3]);
+
+                self.blend(px, py, color, 1.);
+            }
+        }
+    }
+}
+
+impl Renderer for RasterRenderer {
+    fn pos(&self) -> Bounds<Canvas> {
+        Bounds::new(Point(0., 0.), Point(self.width as f32, self.height as f32))
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn to_px(&self, size: f32) -> f32 {
+        size * self.scale_factor
+    }
+
+    fn flush(&mut self) {
+    }
+
+    fn font(&mut self, _style: &FontStyle) -> Result<FontId> {
+        // only the renderer's single font is available
+        Ok(FontId(0))
+    }
+
+    fn draw_path(&mut self, path: &Path<Canvas>, style: &dyn PathOpt) -> Result<()> {
+        let lines = self.flatten(path);
+
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let is_closed = match path.codes().last() {
+            Some(PathCode::ClosePoly(_)) => true,
+            _ => false,
+        };
+
+        let alpha = style.get_alpha().unwrap_or(1.);
+
+        if is_closed {
+            if let Some(color) = style.get_face_color() {
+                self.fill_path(&lines, color, alpha);
+            }
+        }
+
+        match style.get_edge_color() {
+            Some(color) if ! color.is_none() => {
+                self.stroke_path(&lines, style, color, alpha);
+            }
+            _ if ! is_closed => {
+                let color = style.get_face_color().unwrap_or(Color::black());
+                self.stroke_path(&lines, style, color, alpha);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn draw_markers(
+        &mut self,
+        path: &Path<Canvas>,
+        style: &dyn PathOpt,
+        markers: &[MeshStyle],
+    ) -> Result<()> {
+        let is_closed = match path.codes().last() {
+            Some(PathCode::ClosePoly(_)) => true,
+            _ => false,
+        };
+
+        let alpha = style.get_alpha().unwrap_or(1.);
+        let edge = match style.get_edge_color() {
+            Some(color) if ! color.is_none() => Some(color),
+            _ => None,
+        };
+
+        for marker in markers {
+            let marker_path = marker.affine.transform_path(path);
+            let lines = self.flatten(&marker_path);
+
+            if is_closed {
+                self.fill_path(&lines, marker.color, alpha);
+            }
+
+            let stroke = if is_closed { edge } else { edge.or(Some(marker.color)) };
+
+            if let Some(color) = stroke {
+                self.stroke_path(&lines, style, color, alpha);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        xy: Point,
+        text: &str,
+        angle: f32,
+        style: &dyn PathOpt,
+        text_style: &TextStyle,
+    ) -> Result<()> {
+        let size = self.to_px(text_style.get_size().unwrap_or(10.));
+
+        let color = style.get_face_color()
+            .or(style.get_edge_color())
+            .unwrap_or(Color::black());
+
+        let font = &mut self.font;
+        let scale = size / font.units_per_em();
+        let [dx, dy] = font.align(font.text_width(text), text_style);
+        let (sin, cos) = angle.sin_cos();
+
+        let path = font.text_path(text, |x, y| {
+            let (x, y) = ((dx + x) * scale, (dy + y) * scale);
+
+            Point(xy.x() + x * cos - y * sin, xy.y() + x * sin + y * cos)
+        });
+
+        if let Some(path) = path {
+            let lines = self.flatten(&path);
+
+            self.fill_path(&lines, color, style.get_alpha().unwrap_or(1.));
+        }
+
+        Ok(())
+    }
+
+    fn draw_mesh2d_color(&mut self, mesh: &Mesh2dColor) -> Result<()> {
+        let height = self.height as f32;
+
+        for ([p0, p1, p2], colors) in mesh.triangles() {
+            let p = [
+                [p0[0], height - p0[1]],
+                [p1[0], height - p1[1]],
+                [p2[0], height - p2[1]],
+            ];
+
+            self.draw_triangle(p, colors);
+        }
+
+        Ok(())
+    }
+
+    fn draw_with_clip(
+        &mut self,
+        pos: Bounds<Canvas>,
+        f: Box<dyn FnOnce(&mut dyn Renderer) -> Result<()> + '_>,
+    ) -> Result<()> {
+        let [x0, y0, x1, y1] = self.clip_rect();
+        let height = self.height as f32;
+
+        let clip = [
+            (pos.xmin().floor().max(0.) as usize).max(x0),
+            ((height - pos.ymax()).floor().max(0.) as usize).max(y0),
+            (pos.xmax().ceil().max(0.) as usize).min(x1),
+            ((height - pos.ymin()).ceil().max(0.) as usize).min(y1),
+        ];
+
+        self.clip.push(clip);
+        let result = (f)(self);
+        self.clip.pop();
+
+        result
+    }
+}
+
+#[inline]
+fn edge_fn(a: [f32; 2], b: [f32; 2], p: [f32; 2]) -> f32 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+fn n_steps(len: f32) -> usize {
+    // about one segment per 2 pixels of control polygon length
+    (len / 2.).sqrt().ceil().clamp(1., 64.) as usize * 2
+}
+
+fn quad_to(line: &mut Vec<[f32; 2]>, p0: [f32; 2], p1: [f32; 2], p2: [f32; 2]) {
+    let len = dist(p0, p1) + dist(p1, p2);
+    let n = n_steps(len);
+
+    for i in 1..=n {
+        let t = i as f32 / n as f32;
+        let u = 1. - t;
+
+        line.push([
+            u * u * p0[0] + 2. * u * t * p1[0] + t * t * p2[0],
+            u * u * p0[1] + 2. * u * t * p1[1] + t * t * p2[1],
+        ]);
+    }
+}
+
+fn cubic_to(line: &mut Vec<[f32; 2]>, p0: [f32; 2], p1: [f32; 2], p2: [f32; 2], p3: [f32; 2]) {
+    let len = dist(p0, p1) + dist(p1, p2) + dist(p2, p3);
+    let n = n_steps(len);
+
+    for i in 1..=n {
+        let t = i as f32 / n as f32;
+        let u = 1. - t;
+
+        let (a, b, c, d) = (u * u * u, 3. * u * u * t, 3. * u * t * t, t * t * t);
+
+        line.push([
+            a * p0[0] + b * p1[0] + c * p2[0] + d * p3[0],
+            a * p0[1] + b * p1[1] + c * p2[1] + d * p3[1],
+        ]);
+    }
+}
+
+#[inline]
+fn dist(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{renderer::{Canvas, Renderer}, Path, PathCode, Point};
+
+    use crate::config::PathStyle;
+
+    use super::RasterRenderer;
+
+    #[test]
+    fn raster_invalid_font() {
+        let raster = RasterRenderer::with_font(4, 4, 1., vec![0; 16]);
+
+        assert_eq!(raster.err().map(|err| err.kind()), Some(std::io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    #[ignore = "needs one of the DEFAULT_FONTS system fonts"]
+    fn raster_fill_rect() {
+        let mut raster = RasterRenderer::new(8, 8, 1.).unwrap();
+
+        let path = Path::<Canvas>::new(vec![
+            PathCode::MoveTo(Point(2., 2.)),
+            PathCode::LineTo(Point(6., 2.)),
+            PathCode::LineTo(Point(6., 6.)),
+            PathCode::ClosePoly(Point(2., 6.)),
+        ]);
+
+        let mut style = PathStyle::new();
+        style.face_color("red");
+
+        raster.draw_path(&path, &style).unwrap();
+
+        let rgba = raster.to_rgba8();
+        let pixel = |x: usize, y: usize| &rgba[4 * (y * 8 + x)..4 * (y * 8 + x) + 4];
+
+        // pixel rows are top down
+        assert_eq!(pixel(2, 2), &[255, 0, 0, 255]);
+        assert_eq!(pixel(5, 5), &[255, 0, 0, 255]);
+        assert_eq!(pixel(1, 2), &[255, 255, 255, 255]);
+        assert_eq!(pixel(2, 6), &[255, 255, 255, 255]);
+    }
+}
@@ -0,0 +1,289 @@
+///
+/// Anti-aliased polygon filling with the nonzero winding rule.
+///
+/// Each pixel row is sampled by `SUB_SAMPLES` scanlines. Along a scanline
+/// the covered spans are accumulated with exact fractional coverage, so
+/// horizontal anti-aliasing is exact and vertical anti-aliasing uses the
+/// sub-scanlines.
+///
+const SUB_SAMPLES: usize = 4;
+
+#[derive(Clone, Debug)]
+struct Edge {
+    top: f32,
+    bottom: f32,
+    x_top: f32,
+    dx_dy: f32,
+    dir: i32,
+}
+
+impl Edge {
+    #[inline]
+    fn x_at(&self, y: f32) -> f32 {
+        self.x_top + (y - self.top) * self.dx_dy
+    }
+}
+
+///
+/// Edges of polygons in pixel coordinates, y pointing down.
+///
+#[derive(Clone, Debug, Default)]
+pub(super) struct EdgeList {
+    edges: Vec<Edge>,
+}
+
+impl EdgeList {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub(super) fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub(super) fn add_line(&mut self, p0: [f32; 2], p1: [f32; 2]) {
+        let [x0, y0] = p0;
+        let [x1, y1] = p1;
+
+        if y0 == y1 || ! (x0.is_finite() && y0.is_finite() && x1.is_finite() && y1.is_finite()) {
+            return;
+        }
+
+        let (top, bottom, x_top, x_bottom, dir) = if y0 < y1 {
+            (y0, y1, x0, x1, 1)
+        } else {
+            (y1, y0, x1, x0, -1)
+        };
+
+        self.edges.push(Edge {
+            top,
+            bottom,
+            x_top,
+            dx_dy: (x_bottom - x_top) / (bottom - top),
+            dir,
+        });
+    }
+
+    ///
+    /// Adds a polygon, which is implicitly closed.
+    ///
+    pub(super) fn add_poly(&mut self, poly: &[[f32; 2]]) {
+        if poly.len() < 2 {
+            return;
+        }
+
+        for i in 0..poly.len() {
+            self.add_line(poly[i], poly[(i + 1) % poly.len()]);
+        }
+    }
+
+    ///
+    /// Adds a polygon with counter-clockwise (positive area) orientation,
+    /// so overlapping polygons never cancel under the nonzero rule.
+    ///
+    pub(super) fn add_poly_positive(&mut self, poly: &mut Vec<[f32; 2]>) {
+        if signed_area(poly) < 0. {
+            poly.reverse();
+        }
+
+        self.add_poly(poly);
+    }
+
+    ///
+    /// Calls `f(x, y, coverage)` for every pixel in `clip` touched by the
+    /// filled polygons. `clip` is `[x0, y0, x1, y1]` in pixels, exclusive
+    /// of `x1` and `y1`.
+    ///
+    pub(super) fn fill(&mut self, clip: [usize; 4], mut f: impl FnMut(usize, usize, f32)) {
+        if self.edges.is_empty() {
+            return;
+        }
+
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+        let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+
+        for edge in &self.edges {
+            let x_bottom = edge.x_at(edge.bottom);
+
+            x_min = x_min.min(edge.x_top).min(x_bottom);
+            x_max = x_max.max(edge.x_top).max(x_bottom);
+            y_min = y_min.min(edge.top);
+            y_max = y_max.max(edge.bottom);
+        }
+
+        let px0 = (x_min.floor().max(0.) as usize).max(clip[0]);
+        let px1 = (x_max.ceil().max(0.) as usize + 1).min(clip[2]);
+        let py0 = (y_min.floor().max(0.) as usize).max(clip[1]);
+        let py1 = (y_max.ceil().max(0.) as usize).min(clip[3]);
+
+        if px1 <= px0 || py1 <= py0 {
+            return;
+        }
+
+        self.edges.sort_by(|a, b| a.top.total_cmp(&b.top));
+
+        let width = px1 - px0;
+        let mut acc = vec![0f32; width];
+        let mut active = Vec::<usize>::new();
+        let mut crossings = Vec::<(f32, i32)>::new();
+        let mut next = 0;
+
+        let weight = 1. / SUB_SAMPLES as f32;
+
+        for py in py0..py1 {
+            acc.fill(0.);
+
+            for s in 0..SUB_SAMPLES {
+                let sy = py as f32 + (s as f32 + 0.5) * weight;
+
+                while next < self.edges.len() && self.edges[next].top <= sy {
+                    active.push(next);
+                    next += 1;
+                }
+
+                let edges = &self.edges;
+                active.retain(|i| edges[*i].bottom > sy);
+
+                crossings.clear();
+                for i in &active {
+                    let edge = &edges[*i];
+
+                    crossings.push((edge.x_at(sy) - px0 as f32, edge.dir));
+                }
+
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                let mut start = 0.;
+
+                for (x, dir) in &crossings {
+                    let prev = winding;
+                    winding += dir;
+
+                    if prev == 0 && winding != 0 {
+                        start = *x;
+                    } else if prev != 0 && winding == 0 {
+                        add_span(&mut acc, start, *x, weight);
+                    }
+                }
+            }
+
+            for (i, coverage) in acc.iter().enumerate() {
+                if *coverage > 1e-3 {
+                    f(px0 + i, py, coverage.min(1.));
+                }
+            }
+        }
+    }
+}
+
+fn add_span(acc: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let width = acc.len() as f32;
+    let x0 = x0.clamp(0., width);
+    let x1 = x1.clamp(0., width);
+
+    if x1 <= x0 {
+        return;
+    }
+
+    let i0 = x0.floor() as usize;
+    let i1 = x1.floor() as usize;
+
+    if i0 == i1 {
+        acc[i0] += (x1 - x0) * weight;
+    } else {
+        acc[i0] += (i0 as f32 + 1. - x0) * weight;
+
+        for cell in &mut acc[i0 + 1..i1] {
+            *cell += weight;
+        }
+
+        if i1 < acc.len() {
+            acc[i1] += (x1 - i1 as f32) * weight;
+        }
+    }
+}
+
+pub(super) fn signed_area(poly: &[[f32; 2]]) -> f32 {
+    let mut area = 0.;
+
+    for i in 0..poly.len() {
+        let [x0, y0] = poly[i];
+        let [x1, y1] = poly[(i + 1) % poly.len()];
+
+        area += x0 * y1 - x1 * y0;
+    }
+
+    0.5 * area
+}
+
+#[cfg(test)]
+mod test {
+    use super::{signed_area, EdgeList};
+
+    fn coverage(edges: &mut EdgeList, w: usize, h: usize) -> Vec<f32> {
+        let mut image = vec![0.; w * h];
+
+        edges.fill([0, 0, w, h], |x, y, c| image[y * w + x] += c);
+
+        image
+    }
+
+    #[test]
+    fn scanline_square() {
+        let mut edges = EdgeList::new();
+        edges.add_poly(&[[1., 1.], [3., 1.], [3., 3.], [1., 3.]]);
+
+        let image = coverage(&mut edges, 4, 4);
+
+        assert_eq!(image, vec![
+            0., 0., 0., 0.,
+            0., 1., 1., 0.,
+            0., 1., 1., 0.,
+            0., 0., 0., 0.,
+        ]);
+    }
+
+    #[test]
+    fn scanline_half_pixel() {
+        let mut edges = EdgeList::new();
+        edges.add_poly(&[[0.5, 0.], [2., 0.], [2., 1.], [0.5, 1.]]);
+
+        let image = coverage(&mut edges, 2, 1);
+
+        assert!((image[0] - 0.5).abs() < 1e-6);
+        assert!((image[1] - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scanline_nonzero_overlap() {
+        let mut edges = EdgeList::new();
+        let mut a = vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]];
+        let mut b = vec![[1., 0.], [1., 2.], [3., 2.], [3., 0.]];
+
+        edges.add_poly_positive(&mut a);
+        edges.add_poly_positive(&mut b);
+
+        let image = coverage(&mut edges, 3, 2);
+
+        assert_eq!(image, vec![1., 1., 1., 1., 1., 1.]);
+    }
+
+    #[test]
+    fn scanline_clip() {
+        let mut edges = EdgeList::new();
+        edges.add_poly(&[[-5., -5.], [10., -5.], [10., 10.], [-5., 10.]]);
+
+        let mut image = vec![0.; 4];
+        edges.fill([1, 1, 2, 2], |x, y, c| image[y * 2 + x] += c);
+
+        assert_eq!(image, vec![0., 0., 0., 1.]);
+    }
+
+    #[test]
+    fn scanline_signed_area() {
+        assert_eq!(signed_area(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.]]), 1.);
+        assert_eq!(signed_area(&[[0., 0.], [0., 1.], [1., 1.], [1., 0.]]), -1.);
+    }
+}
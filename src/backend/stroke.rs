@@ -0,0 +1,237 @@
+use super::scanline::EdgeList;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Join {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Cap {
+    Butt,
+    Round,
+    Square,
+}
+
+///
+/// Converts polylines into fill polygons for a given line width.
+///
+/// Each segment, join and cap is emitted as its own positively oriented
+/// polygon, so the nonzero fill of the union is the stroke.
+///
+#[derive(Clone, Debug)]
+pub(super) struct Stroker {
+    half_width: f32,
+    join: Join,
+    cap: Cap,
+    miter_limit: f32,
+}
+
+impl Stroker {
+    pub(super) fn new(width: f32, join: Join, cap: Cap) -> Self {
+        Self {
+            half_width: 0.5 * width.max(0.),
+            join,
+            cap,
+            miter_limit: 4.,
+        }
+    }
+
+    pub(super) fn stroke(&self, line: &[[f32; 2]], is_closed: bool, edges: &mut EdgeList) {
+        let hw = self.half_width;
+
+        if hw <= 0. {
+            return;
+        }
+
+        let mut pts: Vec<[f32; 2]> = Vec::with_capacity(line.len());
+        for p in line {
+            if pts.last().map_or(true, |q| dist(*q, *p) > 1e-4) {
+                pts.push(*p);
+            }
+        }
+
+        let is_closed = is_closed && pts.len() > 2;
+        if is_closed && dist(pts[0], pts[pts.len() - 1]) <= 1e-4 {
+            pts.pop();
+        }
+
+        match pts.len() {
+            0 => return,
+            1 => {
+                match self.cap {
+                    Cap::Butt => {},
+                    Cap::Round => edges.add_poly_positive(&mut circle(pts[0], hw)),
+                    Cap::Square => {
+                        let [x, y] = pts[0];
+                        edges.add_poly_positive(&mut vec![
+                            [x - hw, y - hw], [x + hw, y - hw], [x + hw, y + hw], [x - hw, y + hw]
+                        ]);
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let n = pts.len();
+        let n_seg = if is_closed { n } else { n - 1 };
+
+        for i in 0..n_seg {
+            let p0 = pts[i];
+            let p1 = pts[(i + 1) % n];
+            let d = unit(p0, p1);
+            let nv = [- d[1] * hw, d[0] * hw];
+
+            let ext0 = if ! is_closed && i == 0 && self.cap == Cap::Square { hw } else { 0. };
+            let ext1 = if ! is_closed && i == n_seg - 1 && self.cap == Cap::Square { hw } else { 0. };
+
+            let a = [p0[0] - d[0] * ext0, p0[1] - d[1] * ext0];
+            let b = [p1[0] + d[0] * ext1, p1[1] + d[1] * ext1];
+
+            edges.add_poly_positive(&mut vec![
+                [a[0] + nv[0], a[1] + nv[1]],
+                [b[0] + nv[0], b[1] + nv[1]],
+                [b[0] - nv[0], b[1] - nv[1]],
+                [a[0] - nv[0], a[1] - nv[1]],
+            ]);
+        }
+
+        let joins = if is_closed { 0..n } else { 1..n - 1 };
+
+        for i in joins {
+            let prev = pts[(i + n - 1) % n];
+            let v = pts[i];
+            let next = pts[(i + 1) % n];
+
+            self.join(prev, v, next, edges);
+        }
+
+        if ! is_closed && self.cap == Cap::Round {
+            edges.add_poly_positive(&mut circle(pts[0], hw));
+            edges.add_poly_positive(&mut circle(pts[n - 1], hw));
+        }
+    }
+
+    fn join(&self, prev: [f32; 2], v: [f32; 2], next: [f32; 2], edges: &mut EdgeList) {
+        let hw = self.half_width;
+
+        let d0 = unit(prev, v);
+        let d1 = unit(v, next);
+
+        let cross = d0[0] * d1[1] - d0[1] * d1[0];
+        let dot = d0[0] * d1[0] + d0[1] * d1[1];
+
+        if cross.abs() < 1e-6 && dot > 0. {
+            return;
+        }
+
+        if self.join == Join::Round {
+            edges.add_poly_positive(&mut circle(v, hw));
+            return;
+        }
+
+        // the outer side of the turn is opposite to the turn direction
+        let s = if cross > 0. { -1. } else { 1. };
+        let n0 = [- d0[1] * hw * s, d0[0] * hw * s];
+        let n1 = [- d1[1] * hw * s, d1[0] * hw * s];
+
+        let o0 = [v[0] + n0[0], v[1] + n0[1]];
+        let o1 = [v[0] + n1[0], v[1] + n1[1]];
+
+        let miter_ratio = (2. / (1. + dot).max(1e-6)).sqrt();
+
+        if self.join == Join::Miter && miter_ratio <= self.miter_limit {
+            let k = 1. / (1. + dot);
+            let m = [v[0] + (n0[0] + n1[0]) * k, v[1] + (n0[1] + n1[1]) * k];
+
+            edges.add_poly_positive(&mut vec![v, o0, m, o1]);
+        } else {
+            edges.add_poly_positive(&mut vec![v, o0, o1]);
+        }
+    }
+}
+
+fn circle(c: [f32; 2], r: f32) -> Vec<[f32; 2]> {
+    let n = (r * std::f32::consts::PI).ceil().clamp(8., 64.) as usize;
+
+    (0..n).map(|i| {
+        let theta = i as f32 * std::f32::consts::TAU / n as f32;
+
+        [c[0] + r * theta.cos(), c[1] + r * theta.sin()]
+    }).collect()
+}
+
+#[inline]
+fn dist(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+#[inline]
+fn unit(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    let len = dist(a, b).max(1e-12);
+
+    [(b[0] - a[0]) / len, (b[1] - a[1]) / len]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::backend::scanline::EdgeList;
+
+    use super::{Cap, Join, Stroker};
+
+    fn render(line: &[[f32; 2]], is_closed: bool, stroker: Stroker) -> Vec<f32> {
+        let mut edges = EdgeList::new();
+        stroker.stroke(line, is_closed, &mut edges);
+
+        let mut image = vec![0.; 8 * 8];
+        edges.fill([0, 0, 8, 8], |x, y, c| image[y * 8 + x] += c);
+
+        image
+    }
+
+    #[test]
+    fn stroke_horizontal_butt() {
+        let image = render(&[[1., 4.], [7., 4.]], false, Stroker::new(2., Join::Miter, Cap::Butt));
+
+        for x in 0..8 {
+            let expect = if 1 <= x && x < 7 { 1. } else { 0. };
+
+            assert_eq!(image[3 * 8 + x], expect, "x={}", x);
+            assert_eq!(image[4 * 8 + x], expect, "x={}", x);
+            assert_eq!(image[2 * 8 + x], 0.);
+            assert_eq!(image[5 * 8 + x], 0.);
+        }
+    }
+
+    #[test]
+    fn stroke_square_cap() {
+        let image = render(&[[2., 4.], [6., 4.]], false, Stroker::new(2., Join::Miter, Cap::Square));
+
+        assert_eq!(image[3 * 8 + 1], 1.);
+        assert_eq!(image[3 * 8 + 6], 1.);
+        assert_eq!(image[3 * 8 + 0], 0.);
+        assert_eq!(image[3 * 8 + 7], 0.);
+    }
+
+    #[test]
+    fn stroke_miter_corner() {
+        // right angle corner: the miter fills the outer corner pixel
+        let miter = render(&[[1., 2.], [6., 2.], [6., 7.]], false, Stroker::new(2., Join::Miter, Cap::Butt));
+        assert_eq!(miter[1 * 8 + 6], 1.);
+
+        let bevel = render(&[[1., 2.], [6., 2.], [6., 7.]], false, Stroker::new(2., Join::Bevel, Cap::Butt));
+        assert!(bevel[1 * 8 + 6] < 1.);
+        assert!(bevel[1 * 8 + 6] > 0.);
+    }
+
+    #[test]
+    fn stroke_closed_no_holes() {
+        let image = render(&[[2., 2.], [6., 2.], [6., 6.], [2., 6.]], true, Stroker::new(2., Join::Miter, Cap::Butt));
+
+        assert_eq!(image[1 * 8 + 1], 1.);
+        assert_eq!(image[6 * 8 + 6], 1.);
+        assert_eq!(image[4 * 8 + 4], 0.);
+    }
+}
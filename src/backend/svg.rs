@@ -0,0 +1,463 @@
+use std::fmt::Write;
+
+use essay_graphics::api::{
+    renderer::{Canvas, Drawable, FontId, Renderer, Result},
+    Bounds, CapStyle, Color, FontStyle, HorizAlign, JoinStyle, Mesh2dColor,
+    Path, PathCode, PathOpt, Point, TextStyle, VertAlign,
+    path_style::MeshStyle,
+};
+
+use super::{
+    mesh_image::{mesh_image, triangles},
+    png::{base64, encode_png},
+};
+
+///
+/// Headless renderer writing an SVG document.
+///
+/// Paths become `<path>` elements and text becomes `<text>`. Color
+/// meshes on a regular grid, such as images and color grids, are embedded
+/// as PNG data URIs, and other meshes as triangles. Canvas coordinates are y-up, so the
+/// renderer flips y when emitting SVG coordinates.
+///
+pub struct SvgRenderer {
+    pos: Bounds<Canvas>,
+    scale_factor: f32,
+
+    defs: String,
+    body: String,
+    n_clip: usize,
+
+    fonts: Vec<FontStyle>,
+}
+
+impl SvgRenderer {
+    ///
+    /// Creates a renderer for a `width` by `height` pixel canvas, where
+    /// `scale_factor` maps point sizes to pixels.
+    ///
+    pub fn new(width: f32, height: f32, scale_factor: f32) -> Self {
+        Self {
+            pos: Bounds::new(Point(0., 0.), Point(width, height)),
+            scale_factor,
+
+            defs: String::new(),
+            body: String::new(),
+            n_clip: 0,
+
+            fonts: Vec::new(),
+        }
+    }
+
+    pub fn draw(&mut self, drawable: &mut dyn Drawable) -> Result<()> {
+        drawable.draw(self)
+    }
+
+    ///
+    /// Embeds an RGBA8 image of `width` by `height` pixels as a PNG data URI
+    /// stretched over `pos`, with the first row at the top.
+    ///
+    fn draw_image(
+        &mut self,
+        pos: &Bounds<Canvas>,
+        width: usize,
+        height: usize,
+        rgba: &[u8],
+    ) {
+        let png = encode_png(width, height, rgba);
+
+        writeln!(
+            self.body,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" style="image-rendering:pixelated" href="data:image/png;base64,{}"/>"#,
+            fmt(pos.xmin()),
+            fmt(self.y(pos.ymax())),
+            fmt(pos.width()),
+            fmt(pos.height()),
+            base64(&png),
+        ).unwrap();
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+
+        writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#).unwrap();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = fmt(self.pos.width()),
+            h = fmt(self.pos.height()),
+        ).unwrap();
+
+        if self.defs.len() > 0 {
+            writeln!(svg, "<defs>").unwrap();
+            svg.push_str(&self.defs);
+            writeln!(svg, "</defs>").unwrap();
+        }
+
+        svg.push_str(&self.body);
+
+        writeln!(svg, "</svg>").unwrap();
+
+        svg
+    }
+
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_svg())
+    }
+
+    #[inline]
+    fn y(&self, y: f32) -> f32 {
+        self.pos.height() - y
+    }
+
+    fn path_data(&self, path: &Path<Canvas>) -> String {
+        let mut d = String::new();
+
+        for code in path.codes() {
+            match code {
+                PathCode::MoveTo(p) => {
+                    write!(d, "M{} {} ", fmt(p.x()), fmt(self.y(p.y()))).unwrap();
+                }
+                PathCode::LineTo(p) => {
+                    write!(d, "L{} {} ", fmt(p.x()), fmt(self.y(p.y()))).unwrap();
+                }
+                PathCode::Bezier2(p1, p2) => {
+                    write!(d, "Q{} {} {} {} ",
+                        fmt(p1.x()), fmt(self.y(p1.y())),
+                        fmt(p2.x()), fmt(self.y(p2.y())),
+                    ).unwrap();
+                }
+                PathCode::Bezier3(p1, p2, p3) => {
+                    write!(d, "C{} {} {} {} {} {} ",
+                        fmt(p1.x()), fmt(self.y(p1.y())),
+                        fmt(p2.x()), fmt(self.y(p2.y())),
+                        fmt(p3.x()), fmt(self.y(p3.y())),
+                    ).unwrap();
+                }
+                PathCode::ClosePoly(p) => {
+                    write!(d, "L{} {} Z ", fmt(p.x()), fmt(self.y(p.y()))).unwrap();
+                }
+            }
+        }
+
+        d.trim_end().to_string()
+    }
+
+    fn style_attrs(&self, path: &Path<Canvas>, style: &dyn PathOpt) -> String {
+        let mut attrs = String::new();
+
+        let alpha = style.get_alpha().unwrap_or(1.);
+        let is_closed = is_closed(path);
+
+        match style.get_face_color() {
+            Some(color) if is_closed && ! color.is_none() => {
+                write_color(&mut attrs, "fill", color, alpha);
+            }
+            _ => { attrs.push_str(r#" fill="none""#); }
+        }
+
+        match style.get_edge_color() {
+            Some(color) if ! color.is_none() => {
+                write_color(&mut attrs, "stroke", color, alpha);
+
+                let lw = self.to_px(style.get_line_width().unwrap_or(1.));
+                write!(attrs, r#" stroke-width="{}""#, fmt(lw)).unwrap();
+
+                match style.get_join_style() {
+                    Some(JoinStyle::Miter) => attrs.push_str(r#" stroke-linejoin="miter""#),
+                    Some(JoinStyle::Round) => attrs.push_str(r#" stroke-linejoin="round""#),
+                    Some(JoinStyle::Bevel) => attrs.push_str(r#" stroke-linejoin="bevel""#),
+                    None => {}
+                }
+
+                match style.get_cap_style() {
+                    Some(CapStyle::Butt) => attrs.push_str(r#" stroke-linecap="butt""#),
+                    Some(CapStyle::Round) => attrs.push_str(r#" stroke-linecap="round""#),
+                    Some(CapStyle::Projecting) => attrs.push_str(r#" stroke-linecap="square""#),
+                    None => {}
+                }
+            }
+            _ if ! is_closed => {
+                // open paths without an explicit edge color are drawn as lines
+                let color = style.get_face_color().unwrap_or(Color::black());
+                write_color(&mut attrs, "stroke", color, alpha);
+            }
+            _ => {}
+        }
+
+        attrs
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn pos(&self) -> Bounds<Canvas> {
+        self.pos.clone()
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    fn to_px(&self, size: f32) -> f32 {
+        size * self.scale_factor
+    }
+
+    fn flush(&mut self) {
+    }
+
+    fn font(&mut self, style: &FontStyle) -> Result<FontId> {
+        self.fonts.push(style.clone());
+
+        Ok(FontId(self.fonts.len() - 1))
+    }
+
+    fn draw_path(&mut self, path: &Path<Canvas>, style: &dyn PathOpt) -> Result<()> {
+        let d = self.path_data(path);
+
+        if d.len() > 0 {
+            let attrs = self.style_attrs(path, style);
+
+            writeln!(self.body, r#"<path d="{}"{}/>"#, d, attrs).unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn draw_markers(
+        &mut self,
+        path: &Path<Canvas>,
+        style: &dyn PathOpt,
+        markers: &[MeshStyle],
+    ) -> Result<()> {
+        let alpha = style.get_alpha().unwrap_or(1.);
+        let is_closed = is_closed(path);
+
+        for marker in markers {
+            let marker_path = marker.affine.transform_path(path);
+            let d = self.path_data(&marker_path);
+
+            let mut attrs = String::new();
+
+            if is_closed && ! marker.color.is_none() {
+                write_color(&mut attrs, "fill", marker.color, alpha);
+            } else {
+                attrs.push_str(r#" fill="none""#);
+            }
+
+            match style.get_edge_color() {
+                Some(color) if ! color.is_none() => {
+                    write_color(&mut attrs, "stroke", color, alpha);
+
+                    let lw = self.to_px(style.get_line_width().unwrap_or(1.));
+                    write!(attrs, r#" stroke-width="{}""#, fmt(lw)).unwrap();
+                }
+                _ if ! is_closed => {
+                    write_color(&mut attrs, "stroke", marker.color, alpha);
+                }
+                _ => {}
+            }
+
+            writeln!(self.body, r#"<path d="{}"{}/>"#, d, attrs).unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        xy: Point,
+        text: &str,
+        angle: f32,
+        style: &dyn PathOpt,
+        text_style: &TextStyle,
+    ) -> Result<()> {
+        let x = xy.x();
+        let y = self.y(xy.y());
+
+        let size = self.to_px(text_style.get_size().unwrap_or(10.));
+
+        let color = style.get_face_color()
+            .or(style.get_edge_color())
+            .unwrap_or(Color::black());
+
+        let anchor = match text_style.get_halign() {
+            Some(HorizAlign::Left) => "start",
+            Some(HorizAlign::Right) => "end",
+            _ => "middle",
+        };
+
+        let baseline = match text_style.get_valign() {
+            Some(VertAlign::Top) => "text-before-edge",
+            Some(VertAlign::Center) => "central",
+            Some(VertAlign::Bottom) => "text-after-edge",
+            _ => "alphabetic",
+        };
+
+        let mut attrs = String::new();
+        write!(attrs, r#" x="{}" y="{}" font-size="{}""#, fmt(x), fmt(y), fmt(size)).unwrap();
+        write!(attrs, r#" text-anchor="{}" dominant-baseline="{}""#, anchor, baseline).unwrap();
+        write_color(&mut attrs, "fill", color, style.get_alpha().unwrap_or(1.));
+
+        if angle != 0. {
+            // canvas angles are counter-clockwise with y-up
+            write!(attrs, r#" transform="rotate({} {} {})""#,
+                fmt(- angle.to_degrees()), fmt(x), fmt(y)
+            ).unwrap();
+        }
+
+        writeln!(self.body, "<text{}>{}</text>", attrs, escape(text)).unwrap();
+
+        Ok(())
+    }
+
+    fn draw_mesh2d_color(&mut self, mesh: &Mesh2dColor) -> Result<()> {
+        if let Some(image) = mesh_image(&triangles(mesh)) {
+            self.draw_image(&image.pos, image.width, image.height, &image.rgba);
+
+            return Ok(());
+        }
+
+        // SVG has no gouraud shading, so each triangle uses its mean color
+        for ([p0, p1, p2], [c0, c1, c2]) in mesh.triangles() {
+            let color = mean_color(&[c0, c1, c2]);
+
+            let mut attrs = String::new();
+            write_color(&mut attrs, "fill", color, 1.);
+            // a hairline of the same color hides seams between triangles
+            write_color(&mut attrs, "stroke", color, 1.);
+            attrs.push_str(r#" stroke-width="0.5""#);
+
+            writeln!(self.body, r#"<path d="M{} {} L{} {} L{} {} Z"{}/>"#,
+                fmt(p0[0]), fmt(self.y(p0[1])),
+                fmt(p1[0]), fmt(self.y(p1[1])),
+                fmt(p2[0]), fmt(self.y(p2[1])),
+                attrs,
+            ).unwrap();
+        }
+
+        Ok(())
+    }
+
+    fn draw_with_clip(
+        &mut self,
+        pos: Bounds<Canvas>,
+        f: Box<dyn FnOnce(&mut dyn Renderer) -> Result<()> + '_>,
+    ) -> Result<()> {
+        let id = self.n_clip;
+        self.n_clip += 1;
+
+        writeln!(
+            self.defs,
+            r#"<clipPath id="clip{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            id,
+            fmt(pos.xmin()),
+            fmt(self.y(pos.ymax())),
+            fmt(pos.width()),
+            fmt(pos.height()),
+        ).unwrap();
+
+        writeln!(self.body, r#"<g clip-path="url(#clip{})">"#, id).unwrap();
+        let result = (f)(self);
+        writeln!(self.body, "</g>").unwrap();
+
+        result
+    }
+}
+
+fn is_closed(path: &Path<Canvas>) -> bool {
+    match path.codes().last() {
+        Some(PathCode::ClosePoly(_)) => true,
+        _ => false,
+    }
+}
+
+fn write_color(attrs: &mut String, name: &str, color: Color, alpha: f32) {
+    write!(attrs, r##" {}="#{:02x}{:02x}{:02x}""##,
+        name, color.r8(), color.g8(), color.b8()
+    ).unwrap();
+
+    let opacity = alpha * color.a8() as f32 / 255.;
+
+    if opacity < 1. {
+        write!(attrs, r#" {}-opacity="{}""#, name, fmt(opacity)).unwrap();
+    }
+}
+
+fn mean_color(colors: &[Color]) -> Color {
+    let n = colors.len() as u32;
+
+    let (r, g, b, a) = colors.iter().fold((0, 0, 0, 0), |(r, g, b, a), c| {
+        (r + c.r8() as u32, g + c.g8() as u32, b + c.b8() as u32, a + c.a8() as u32)
+    });
+
+    Color((r / n) << 24 | (g / n) << 16 | (b / n) << 8 | (a / n))
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+///
+/// Formats a coordinate with at most two decimals and no trailing zeros.
+///
+fn fmt(value: f32) -> String {
+    let s = format!("{:.2}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+
+    if s == "-0" || s.is_empty() { "0".to_string() } else { s.to_string() }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{renderer::Renderer, Color, Mesh2dColor};
+
+    use super::{escape, fmt, SvgRenderer};
+
+    #[test]
+    fn svg_fmt() {
+        assert_eq!(fmt(0.), "0");
+        assert_eq!(fmt(-0.001), "0");
+        assert_eq!(fmt(1.5), "1.5");
+        assert_eq!(fmt(10.), "10");
+        assert_eq!(fmt(3.14159), "3.14");
+    }
+
+    #[test]
+    fn svg_escape() {
+        assert_eq!(escape("a < b & c"), "a &lt; b &amp; c");
+    }
+
+    #[test]
+    fn svg_mesh() {
+        let (red, blue) = (Color(0xff0000ff), Color(0x0000ffff));
+
+        let mut mesh = Mesh2dColor::new();
+        mesh.triangle(([0., 0.], red), ([1., 0.], red), ([1., 1.], red));
+        mesh.triangle(([0., 0.], red), ([1., 1.], red), ([0., 1.], red));
+
+        let mut svg = SvgRenderer::new(10., 10., 1.);
+        svg.draw_mesh2d_color(&mesh).unwrap();
+
+        assert!(svg.to_svg().contains("<image "));
+
+        let mut mesh = Mesh2dColor::new();
+        mesh.triangle(([0., 0.], red), ([1., 0.], blue), ([1., 1.], red));
+
+        let mut svg = SvgRenderer::new(10., 10., 1.);
+        svg.draw_mesh2d_color(&mesh).unwrap();
+
+        assert!(! svg.to_svg().contains("<image "));
+    }
+}
@@ -0,0 +1,603 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use essay_graphics::api::{
+    renderer::Canvas, HorizAlign, Path, PathCode, Point, TextStyle, VertAlign
+};
+
+///
+/// Minimal TrueType reader for glyph outlines and advances.
+///
+/// Supports `cmap` formats 4 and 12 and simple and composite `glyf`
+/// outlines. Hinting, kerning and CFF outlines are not supported.
+///
+#[derive(Clone)]
+pub(super) struct Font {
+    data: Vec<u8>,
+
+    units_per_em: f32,
+    ascender: f32,
+    descender: f32,
+
+    loca_long: bool,
+    n_glyphs: usize,
+    n_hmetrics: usize,
+
+    cmap: usize,
+    loca: usize,
+    glyf: usize,
+    hmtx: usize,
+
+    cache: HashMap<u16, Vec<Contour>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Segment {
+    Line([f32; 2]),
+    Quad([f32; 2], [f32; 2]),
+}
+
+///
+/// Closed glyph contour in font units, y up.
+///
+#[derive(Clone, Debug)]
+pub(super) struct Contour {
+    pub(super) start: [f32; 2],
+    pub(super) segments: Vec<Segment>,
+}
+
+// fonts tried in order when no font path is given
+const DEFAULT_FONTS: [&str; 6] = [
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+static DEFAULT_FONT: OnceLock<Option<Font>> = OnceLock::new();
+
+///
+/// The first installed font of `DEFAULT_FONTS`, read once per process.
+///
+pub(super) fn default_font() -> Option<&'static Font> {
+    DEFAULT_FONT.get_or_init(|| {
+        DEFAULT_FONTS.iter().find_map(|path| {
+            std::fs::read(path).ok().and_then(Font::from_bytes)
+        })
+    }).as_ref()
+}
+
+impl Font {
+    pub(super) fn load_default() -> Option<Font> {
+        default_font().cloned()
+    }
+
+    pub(super) fn from_bytes(data: Vec<u8>) -> Option<Font> {
+        let n_tables = rd_u16(&data, 4) as usize;
+
+        let mut tables = HashMap::<[u8; 4], usize>::new();
+
+        for i in 0..n_tables {
+            let rec = 12 + 16 * i;
+            let tag = data.get(rec..rec + 4)?;
+
+            tables.insert([tag[0], tag[1], tag[2], tag[3]], rd_u32(&data, rec + 8) as usize);
+        }
+
+        let head = *tables.get(b"head")?;
+        let hhea = *tables.get(b"hhea")?;
+        let maxp = *tables.get(b"maxp")?;
+
+        let mut font = Font {
+            units_per_em: rd_u16(&data, head + 18).max(1) as f32,
+            ascender: rd_i16(&data, hhea + 4) as f32,
+            descender: rd_i16(&data, hhea + 6) as f32,
+
+            loca_long: rd_i16(&data, head + 50) != 0,
+            n_glyphs: rd_u16(&data, maxp + 4) as usize,
+            n_hmetrics: rd_u16(&data, hhea + 34).max(1) as usize,
+
+            cmap: 0,
+            loca: *tables.get(b"loca")?,
+            glyf: *tables.get(b"glyf")?,
+            hmtx: *tables.get(b"hmtx")?,
+
+            data: Vec::new(),
+            cache: HashMap::new(),
+        };
+
+        font.cmap = Self::find_cmap(&data, *tables.get(b"cmap")?)?;
+        font.data = data;
+
+        Some(font)
+    }
+
+    fn find_cmap(data: &[u8], cmap: usize) -> Option<usize> {
+        let n = rd_u16(data, cmap + 2) as usize;
+
+        let mut best: Option<(u32, usize)> = None;
+
+        for i in 0..n {
+            let rec = cmap + 4 + 8 * i;
+            let platform = rd_u16(data, rec);
+            let encoding = rd_u16(data, rec + 2);
+            let offset = cmap + rd_u32(data, rec + 4) as usize;
+            let format = rd_u16(data, offset);
+
+            let rank = match (platform, encoding, format) {
+                (3, 10, 12) => 4,
+                (0, _, 12) => 3,
+                (3, 1, 4) => 2,
+                (0, _, 4) => 1,
+                _ => continue,
+            };
+
+            if best.map_or(true, |(r, _)| r < rank) {
+                best = Some((rank, offset));
+            }
+        }
+
+        best.map(|(_, offset)| offset)
+    }
+
+    #[inline]
+    pub(super) fn units_per_em(&self) -> f32 {
+        self.units_per_em
+    }
+
+    #[inline]
+    pub(super) fn ascender(&self) -> f32 {
+        self.ascender
+    }
+
+    #[inline]
+    pub(super) fn descender(&self) -> f32 {
+        self.descender
+    }
+
+    pub(super) fn glyph_id(&self, ch: char) -> u16 {
+        let data = &self.data;
+        let sub = self.cmap;
+        let c = ch as u32;
+
+        match rd_u16(data, sub) {
+            4 => {
+                if c > 0xffff {
+                    return 0;
+                }
+
+                let seg_count = rd_u16(data, sub + 6) as usize / 2;
+                let end_codes = sub + 14;
+                let start_codes = end_codes + 2 * seg_count + 2;
+                let deltas = start_codes + 2 * seg_count;
+                let range_offsets = deltas + 2 * seg_count;
+
+                for i in 0..seg_count {
+                    let end = rd_u16(data, end_codes + 2 * i) as u32;
+
+                    if c > end {
+                        continue;
+                    }
+
+                    let start = rd_u16(data, start_codes + 2 * i) as u32;
+
+                    if c < start {
+                        return 0;
+                    }
+
+                    let delta = rd_u16(data, deltas + 2 * i) as u32;
+                    let range_addr = range_offsets + 2 * i;
+                    let range_offset = rd_u16(data, range_addr) as usize;
+
+                    if range_offset == 0 {
+                        return ((c + delta) & 0xffff) as u16;
+                    }
+
+                    let addr = range_addr + range_offset + 2 * (c - start) as usize;
+                    let glyph = rd_u16(data, addr) as u32;
+
+                    return if glyph == 0 { 0 } else { ((glyph + delta) & 0xffff) as u16 };
+                }
+
+                0
+            }
+            12 => {
+                let n_groups = rd_u32(data, sub + 12) as usize;
+
+                for i in 0..n_groups {
+                    let group = sub + 16 + 12 * i;
+                    let start = rd_u32(data, group);
+                    let end = rd_u32(data, group + 4);
+
+                    if start <= c && c <= end {
+                        return (rd_u32(data, group + 8) + c - start) as u16;
+                    }
+                }
+
+                0
+            }
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Horizontal advance in font units.
+    ///
+    pub(super) fn advance(&self, glyph: u16) -> f32 {
+        let i = (glyph as usize).min(self.n_hmetrics - 1);
+
+        rd_u16(&self.data, self.hmtx + 4 * i) as f32
+    }
+
+    ///
+    /// Advance width of `text` in font units.
+    ///
+    pub(super) fn text_width(&self, text: &str) -> f32 {
+        text.chars().map(|ch| self.advance(self.glyph_id(ch))).sum()
+    }
+
+    ///
+    /// Offset in font units from the text position to the baseline origin
+    /// of text `width` units wide, for the style's alignment.
+    ///
+    pub(super) fn align(&self, width: f32, text_style: &TextStyle) -> [f32; 2] {
+        let dx = match text_style.get_halign() {
+            Some(HorizAlign::Left) => 0.,
+            Some(HorizAlign::Right) => - width,
+            _ => -0.5 * width,
+        };
+
+        let dy = match text_style.get_valign() {
+            Some(VertAlign::Top) => - self.ascender,
+            Some(VertAlign::Center) => -0.5 * (self.ascender + self.descender),
+            Some(VertAlign::Bottom) => - self.descender,
+            _ => 0.,
+        };
+
+        [dx, dy]
+    }
+
+    ///
+    /// Glyph outlines of `text` as a path to fill with the nonzero rule,
+    /// or None for blank text. `to_canvas` maps font units, with the pen
+    /// starting at the origin, to the canvas.
+    ///
+    pub(super) fn text_path(
+        &mut self,
+        text: &str,
+        to_canvas: impl Fn(f32, f32) -> Point,
+    ) -> Option<Path<Canvas>> {
+        let mut codes = Vec::<PathCode>::new();
+        let mut pen = 0.;
+
+        for ch in text.chars() {
+            let glyph = self.glyph_id(ch);
+            let advance = self.advance(glyph);
+            let p = |[x, y]: [f32; 2]| to_canvas(pen + x, y);
+
+            for contour in self.outline(glyph) {
+                codes.push(PathCode::MoveTo(p(contour.start)));
+
+                for segment in &contour.segments {
+                    match segment {
+                        Segment::Line(q) => codes.push(PathCode::LineTo(p(*q))),
+                        Segment::Quad(c, q) => codes.push(PathCode::Bezier2(p(*c), p(*q))),
+                    }
+                }
+
+                codes.push(PathCode::ClosePoly(p(contour.start)));
+            }
+
+            pen += advance;
+        }
+
+        if codes.len() > 0 { Some(Path::new(codes)) } else { None }
+    }
+
+    pub(super) fn outline(&mut self, glyph: u16) -> &Vec<Contour> {
+        if ! self.cache.contains_key(&glyph) {
+            let mut contours = Vec::new();
+            self.read_glyph(glyph, [1., 0., 0., 1., 0., 0.], 0, &mut contours);
+
+            self.cache.insert(glyph, contours);
+        }
+
+        self.cache.get(&glyph).unwrap()
+    }
+
+    fn glyph_range(&self, glyph: u16) -> Option<(usize, usize)> {
+        let i = glyph as usize;
+
+        if i >= self.n_glyphs {
+            return None;
+        }
+
+        let (start, end) = if self.loca_long {
+            (rd_u32(&self.data, self.loca + 4 * i) as usize,
+             rd_u32(&self.data, self.loca + 4 * i + 4) as usize)
+        } else {
+            (2 * rd_u16(&self.data, self.loca + 2 * i) as usize,
+             2 * rd_u16(&self.data, self.loca + 2 * i + 2) as usize)
+        };
+
+        if start < end {
+            Some((self.glyf + start, self.glyf + end))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Appends the glyph's contours transformed by the affine
+    /// `[a, b, c, d, dx, dy]`, where `x' = a x + c y + dx` and
+    /// `y' = b x + d y + dy`.
+    ///
+    fn read_glyph(&self, glyph: u16, tf: [f32; 6], depth: usize, contours: &mut Vec<Contour>) {
+        let (g, _end) = match self.glyph_range(glyph) {
+            Some(range) => range,
+            None => return,
+        };
+
+        let n_contours = rd_i16(&self.data, g);
+
+        if n_contours >= 0 {
+            self.read_simple(g, n_contours as usize, tf, contours);
+        } else if depth < 8 {
+            self.read_composite(g, tf, depth, contours);
+        }
+    }
+
+    fn read_simple(&self, g: usize, n_contours: usize, tf: [f32; 6], contours: &mut Vec<Contour>) {
+        let data = &self.data;
+
+        let end_pts: Vec<usize> = (0..n_contours)
+            .map(|i| rd_u16(data, g + 10 + 2 * i) as usize)
+            .collect();
+
+        let n_points = match end_pts.last() {
+            Some(last) => last + 1,
+            None => return,
+        };
+
+        let n_instructions = rd_u16(data, g + 10 + 2 * n_contours) as usize;
+        let mut p = g + 12 + 2 * n_contours + n_instructions;
+
+        let mut flags = Vec::<u8>::with_capacity(n_points);
+        while flags.len() < n_points {
+            let flag = rd_u8(data, p);
+            p += 1;
+            flags.push(flag);
+
+            if flag & 0x08 != 0 {
+                let repeat = rd_u8(data, p);
+                p += 1;
+
+                for _ in 0..repeat {
+                    flags.push(flag);
+                }
+            }
+        }
+        flags.truncate(n_points);
+
+        let mut xs = Vec::<f32>::with_capacity(n_points);
+        let mut x = 0i32;
+        for flag in &flags {
+            if flag & 0x02 != 0 {
+                let dx = rd_u8(data, p) as i32;
+                p += 1;
+                x += if flag & 0x10 != 0 { dx } else { -dx };
+            } else if flag & 0x10 == 0 {
+                x += rd_i16(data, p) as i32;
+                p += 2;
+            }
+            xs.push(x as f32);
+        }
+
+        let mut ys = Vec::<f32>::with_capacity(n_points);
+        let mut y = 0i32;
+        for flag in &flags {
+            if flag & 0x04 != 0 {
+                let dy = rd_u8(data, p) as i32;
+                p += 1;
+                y += if flag & 0x20 != 0 { dy } else { -dy };
+            } else if flag & 0x20 == 0 {
+                y += rd_i16(data, p) as i32;
+                p += 2;
+            }
+            ys.push(y as f32);
+        }
+
+        let mut start = 0;
+        for end in end_pts {
+            if end < start || end >= n_points {
+                break;
+            }
+
+            let points: Vec<([f32; 2], bool)> = (start..=end).map(|i| {
+                let (x, y) = (xs[i], ys[i]);
+
+                ([tf[0] * x + tf[2] * y + tf[4], tf[1] * x + tf[3] * y + tf[5]], flags[i] & 1 != 0)
+            }).collect();
+
+            if let Some(contour) = contour(&points) {
+                contours.push(contour);
+            }
+
+            start = end + 1;
+        }
+    }
+
+    fn read_composite(&self, g: usize, tf: [f32; 6], depth: usize, contours: &mut Vec<Contour>) {
+        let data = &self.data;
+        let mut p = g + 10;
+
+        loop {
+            let flags = rd_u16(data, p);
+            let glyph = rd_u16(data, p + 2);
+            p += 4;
+
+            let (arg1, arg2) = if flags & 0x0001 != 0 {
+                let args = (rd_i16(data, p) as f32, rd_i16(data, p + 2) as f32);
+                p += 4;
+                args
+            } else {
+                let args = (rd_u8(data, p) as i8 as f32, rd_u8(data, p + 1) as i8 as f32);
+                p += 2;
+                args
+            };
+
+            // point matching offsets are not supported
+            let (dx, dy) = if flags & 0x0002 != 0 { (arg1, arg2) } else { (0., 0.) };
+
+            let (mut a, mut b, mut c, mut d) = (1., 0., 0., 1.);
+
+            if flags & 0x0008 != 0 {
+                a = rd_f2dot14(data, p);
+                d = a;
+                p += 2;
+            } else if flags & 0x0040 != 0 {
+                a = rd_f2dot14(data, p);
+                d = rd_f2dot14(data, p + 2);
+                p += 4;
+            } else if flags & 0x0080 != 0 {
+                a = rd_f2dot14(data, p);
+                b = rd_f2dot14(data, p + 2);
+                c = rd_f2dot14(data, p + 4);
+                d = rd_f2dot14(data, p + 6);
+                p += 8;
+            }
+
+            // compose the component transform with the parent transform
+            let child = [
+                tf[0] * a + tf[2] * b,
+                tf[1] * a + tf[3] * b,
+                tf[0] * c + tf[2] * d,
+                tf[1] * c + tf[3] * d,
+                tf[0] * dx + tf[2] * dy + tf[4],
+                tf[1] * dx + tf[3] * dy + tf[5],
+            ];
+
+            self.read_glyph(glyph, child, depth + 1, contours);
+
+            if flags & 0x0020 == 0 {
+                break;
+            }
+        }
+    }
+}
+
+///
+/// Builds a contour from TrueType points, inserting the implied on-curve
+/// points between consecutive off-curve points.
+///
+fn contour(points: &[([f32; 2], bool)]) -> Option<Contour> {
+    let n = points.len();
+
+    if n < 2 {
+        return None;
+    }
+
+    let (start, first) = if points[0].1 {
+        (points[0].0, 1)
+    } else if points[n - 1].1 {
+        (points[n - 1].0, 0)
+    } else {
+        (mid(points[0].0, points[n - 1].0), 0)
+    };
+
+    let mut segments = Vec::new();
+    let mut ctrl: Option<[f32; 2]> = None;
+
+    for k in 0..n {
+        let (p, is_on) = points[(k + first) % n];
+
+        if is_on {
+            match ctrl.take() {
+                Some(c) => segments.push(Segment::Quad(c, p)),
+                None => segments.push(Segment::Line(p)),
+            }
+        } else {
+            if let Some(c) = ctrl {
+                segments.push(Segment::Quad(c, mid(c, p)));
+            }
+            ctrl = Some(p);
+        }
+    }
+
+    if let Some(c) = ctrl {
+        segments.push(Segment::Quad(c, start));
+    }
+
+    Some(Contour { start, segments })
+}
+
+#[inline]
+fn mid(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [0.5 * (a[0] + b[0]), 0.5 * (a[1] + b[1])]
+}
+
+#[inline]
+fn rd_u8(data: &[u8], offset: usize) -> u8 {
+    data.get(offset).copied().unwrap_or(0)
+}
+
+#[inline]
+fn rd_u16(data: &[u8], offset: usize) -> u16 {
+    match data.get(offset..offset + 2) {
+        Some(b) => u16::from_be_bytes([b[0], b[1]]),
+        None => 0,
+    }
+}
+
+#[inline]
+fn rd_i16(data: &[u8], offset: usize) -> i16 {
+    rd_u16(data, offset) as i16
+}
+
+#[inline]
+fn rd_u32(data: &[u8], offset: usize) -> u32 {
+    match data.get(offset..offset + 4) {
+        Some(b) => u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+        None => 0,
+    }
+}
+
+#[inline]
+fn rd_f2dot14(data: &[u8], offset: usize) -> f32 {
+    rd_i16(data, offset) as f32 / 16384.
+}
+
+#[cfg(test)]
+mod test {
+    use super::{contour, Font, Segment};
+
+    #[test]
+    fn ttf_implied_points() {
+        let c = contour(&[
+            ([0., 0.], true),
+            ([1., 1.], false),
+            ([3., 1.], false),
+            ([4., 0.], true),
+        ]).unwrap();
+
+        assert_eq!(c.start, [0., 0.]);
+        assert_eq!(c.segments, vec![
+            Segment::Quad([1., 1.], [2., 1.]),
+            Segment::Quad([3., 1.], [4., 0.]),
+            Segment::Line([0., 0.]),
+        ]);
+    }
+
+    #[test]
+    #[ignore = "needs one of the DEFAULT_FONTS system fonts"]
+    fn ttf_default_font() {
+        let mut font = Font::load_default().unwrap();
+        let glyph = font.glyph_id('A');
+
+        assert!(glyph != 0);
+        assert!(font.advance(glyph) > 0.);
+        assert!(font.outline(glyph).len() > 0);
+        assert!(font.ascender() > 0. && font.descender() < 0.);
+    }
+}
@@ -9,7 +9,11 @@ use crate::{
 };
 
 use super::{
-    tick_formatter::{Formatter, TickFormatter}, tick_locator::{MaxNLocator, TickLocator}, CartesianFrame, FrameArtist 
+    tick_formatter::{DateFormatter, FixedFormatter, Formatter, ScalarFormatter, TickFormatter}, 
+    tick_locator::{
+        AutoDateLocator, AutoMinorLocator, IndexLocator, LogLocator, LogitLocator, MaxNLocator, SymLogLocator, TickLocator
+    }, 
+    CartesianFrame, FrameArtist, Scale 
 };
 
 pub struct Axis {
@@ -24,7 +28,10 @@ pub struct Axis {
     pub(super) ticks: Option<Vec<f32>>,
     pub(super) labels: Option<Vec<String>>,
 
+    pub(super) scale: Scale,
+
     pub(super) is_visible: bool,
+    pub(super) is_tick_label_visible: bool,
 }
 
 impl Axis {
@@ -34,13 +41,60 @@ impl Axis {
             major: AxisTicks::new(cfg, &cfg.join(prefix, "major")),
             minor: AxisTicks::new(cfg, &cfg.join(prefix, "minor")),
             locator: Box::new(MaxNLocator::new(None)),
-            formatter: Box::new(Formatter::Plain),
+            formatter: Box::new(ScalarFormatter::new()),
             ticks: None,
             labels: None,
+            scale: Scale::Linear,
             is_visible: true,
+            is_tick_label_visible: true,
+        }
+    }
+
+    ///
+    /// Ticks and labels for the category positions 0, 1, ...
+    ///
+    pub(crate) fn categories(&mut self, names: &[String]) {
+        let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+
+        self.locator = Box::new(IndexLocator::new(1., 0.));
+        self.formatter = Box::new(FixedFormatter::new(&names));
+    }
+
+    ///
+    /// Sets the axis scale, replacing the locators and formatter with the
+    /// scale's defaults.
+    ///
+    pub(crate) fn set_scale(&mut self, scale: Scale) {
+        self.scale = scale;
+
+        match scale {
+            Scale::Linear => {
+                self.locator = Box::new(MaxNLocator::new(None));
+                self.formatter = Box::new(ScalarFormatter::new());
+                self.minor.locator = None;
+            }
+            Scale::Log10 => {
+                self.locator = Box::new(LogLocator::new());
+                self.formatter = Box::new(Formatter::Log10);
+                self.minor.locator = Some(Box::new(LogLocator::minor()));
+            }
+            Scale::SymLog { linthresh } => {
+                self.locator = Box::new(SymLogLocator::new(linthresh));
+                self.formatter = Box::new(Formatter::Log10);
+                self.minor.locator = None;
+            }
+            Scale::Logit => {
+                self.locator = Box::new(LogitLocator::new());
+                self.formatter = Box::new(Formatter::Logit);
+                self.minor.locator = None;
+            }
         }
     }
 
+    pub(crate) fn get_scale(&self) -> Scale {
+        self.scale
+    }
+
     pub(crate) fn major(&self) -> &AxisTicks {
         &self.major
     }
@@ -49,7 +103,7 @@ impl Axis {
         &mut self.major
     }
 
-    pub(crate) fn _minor(&self) -> &AxisTicks {
+    pub(crate) fn minor(&self) -> &AxisTicks {
         &self.minor
     }
 
@@ -72,6 +126,34 @@ impl Axis {
         delta
     }
 
+    ///
+    /// Minor tick values in `min..=max`, excluding the major ticks. Axes
+    /// without a minor locator only have minor ticks when showing the
+    /// minor grid.
+    ///
+    pub(crate) fn minor_values(&mut self, major: &[f32], min: f32, max: f32) -> Vec<f32> {
+        let mut auto_minor;
+
+        let locator: &mut dyn TickLocator = match &mut self.minor.locator {
+            Some(locator) => locator.as_mut(),
+            None if self.show_grid.is_show_minor() => {
+                auto_minor = AutoMinorLocator::new(None);
+                &mut auto_minor
+            }
+            None => return Vec::new(),
+        };
+
+        locator.set_major(major);
+
+        locator.tick_values(min, max).iter()
+            .filter(|v| min <= **v && **v <= max)
+            .filter(|v| ! major.iter().any(|m| {
+                (*m - **v).abs() <= 1e-5 * m.abs().max(v.abs())
+            }))
+            .map(|v| *v)
+            .collect()
+    }
+
     pub(crate) fn get_show_grid(&self) -> &ShowGrid {
         &self.show_grid
     }
@@ -84,10 +166,26 @@ impl Axis {
         self.is_visible
     }
 
+    pub(crate) fn is_tick_label_visible(&self) -> bool {
+        self.is_tick_label_visible
+    }
+
     fn format(&self, value: f32, delta: f32) -> String {
         self.formatter.format(value, delta)
     }
 
+    ///
+    /// Gives the visible major tick values to the formatters before
+    /// labels are formatted.
+    ///
+    pub(crate) fn set_locs(&mut self, locs: &[f32]) {
+        self.formatter.set_locs(locs);
+
+        if let Some(formatter) = &mut self.major.formatter {
+            formatter.set_locs(locs);
+        }
+    }
+
     pub(crate) fn resize(&mut self, renderer: &mut dyn Renderer, pos: Bounds<Canvas>) {
         self.major.update(renderer, pos);
         self.minor.update(renderer, pos);
@@ -162,6 +260,13 @@ impl AxisTicks {
         }
     }
 
+    pub(crate) fn offset_text(&self, axis: &Axis) -> Option<String> {
+        match &self.formatter {
+            Some(formatter) => formatter.offset_text(),
+            None => axis.formatter.offset_text(),
+        }
+    }
+
     pub(crate) fn get_size(&self) -> f32 {
         self.size
     }
@@ -192,6 +297,41 @@ impl AxisOpt {
         self
     }
 
+    ///
+    /// Shows or hides the tick labels while keeping the ticks, e.g. for
+    /// inner charts sharing an axis.
+    ///
+    pub fn tick_labels_visible(&mut self, is_visible: bool) -> &mut Self {
+        self.write(|axis| { axis.is_tick_label_visible = is_visible; });
+        self
+    }
+
+    ///
+    /// Sets the data scale, e.g. `Scale::Log10`. Because the scale replaces
+    /// the locator and formatter, call it before `locator` or `formatter`.
+    ///
+    pub fn scale(&mut self, scale: impl Into<Scale>) -> &mut Self {
+        let scale = scale.into();
+        let artist = self.artist;
+
+        self.view.write(|frame| frame.set_scale(artist, scale));
+
+        self
+    }
+
+    ///
+    /// Treats axis values as epoch seconds, with calendar ticks and concise
+    /// date labels.
+    ///
+    pub fn dates(&mut self) -> &mut Self {
+        self.write(|axis| {
+            axis.locator = Box::new(AutoDateLocator::new());
+            axis.formatter = Box::new(DateFormatter::concise());
+        });
+
+        self
+    }
+
     pub fn locator(&mut self, locator: impl TickLocator + 'static) -> &mut Self {
         self.write(|axis| { 
             axis.locator = Box::new(locator); 
@@ -229,6 +369,18 @@ impl AxisOpt {
         self
     }
 
+    ///
+    /// Returns to locator ticks after `ticks` or `tick_labels`.
+    ///
+    pub fn reset_ticks(&mut self) -> &mut Self {
+        self.write(|axis| {
+            axis.ticks = None;
+            axis.labels = None;
+        });
+
+        self
+    }
+
     pub fn major(&self) -> AxisTicksOpt {
         let artist = match self.artist {
             FrameArtist::X => FrameArtist::XMajor,
@@ -248,6 +400,26 @@ impl AxisOpt {
 
         AxisGridOpt::new(&self.view, artist)
     }
+
+    pub fn minor(&self) -> AxisTicksOpt {
+        let artist = match self.artist {
+            FrameArtist::X => FrameArtist::XMinor,
+            FrameArtist::Y => FrameArtist::YMinor,
+            _ => panic!("invalid minor()")
+        };
+
+        AxisTicksOpt::new(&self.view, artist)
+    }
+
+    pub fn minor_grid(&self) -> AxisGridOpt {
+        let artist = match self.artist {
+            FrameArtist::X => FrameArtist::XMinor,
+            FrameArtist::Y => FrameArtist::YMinor,
+            _ => panic!("invalid minor()")
+        };
+
+        AxisGridOpt::new(&self.view, artist)
+    }
 }
 
 frame_option_struct!(AxisGridOpt, AxisTicks, CartesianFrame, get_ticks_mut);
@@ -294,7 +466,7 @@ impl ShowGrid {
         }
     }
 
-    pub(crate) fn _is_show_minor(&self) -> bool {
+    pub(crate) fn is_show_minor(&self) -> bool {
         match self {
             ShowGrid::None => false,
             ShowGrid::Major => false,
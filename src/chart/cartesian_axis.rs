@@ -9,28 +9,87 @@ use essay_graphics::api::{
 };
 
 use crate::{
+    artist::{math_text, text_layout},
     config::Config, 
     transform::Transform
 };
 
 use super::{axis::Axis, data_frame::DataFrame, Data};
 
+// major tick length in pixels, until ticks are sized with renderer.to_px
+const TICK_LENGTH: f32 = 10.;
+
+/// Minor tick length in proportion to the configured major and minor sizes
+fn minor_tick_length(axis: &Axis) -> f32 {
+    let major = axis.major().get_size();
+
+    if major > 0. {
+        TICK_LENGTH * axis.minor().get_size() / major
+    } else {
+        0.5 * TICK_LENGTH
+    }
+}
+
+///
+/// Forward and inverse functions from data values to the values shown on
+/// a secondary axis, e.g. frequency to period.
+///
+pub(crate) struct SecondaryTransform {
+    forward: Box<dyn Fn(f32) -> f32 + Send>,
+    inverse: Box<dyn Fn(f32) -> f32 + Send>,
+}
+
+impl SecondaryTransform {
+    pub(crate) fn new(
+        forward: impl Fn(f32) -> f32 + Send + 'static,
+        inverse: impl Fn(f32) -> f32 + Send + 'static,
+    ) -> Self {
+        Self {
+            forward: Box::new(forward),
+            inverse: Box::new(inverse),
+        }
+    }
+
+    ///
+    /// Secondary range of a data range. The forward function may be
+    /// decreasing or infinite at an end, like 1/x at 0, so the range is
+    /// the finite extent of sampled values.
+    ///
+    fn range(&self, min: f32, max: f32) -> (f32, f32) {
+        let n = 64;
+
+        let (lo, hi) = (0..=n)
+            .map(|i| (self.forward)(min + (max - min) * i as f32 / n as f32))
+            .filter(|v| v.is_finite())
+            .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)));
+
+        if lo <= hi { (lo, hi) } else { (min, max) }
+    }
+}
+
 pub struct XAxis {
     axis: Axis,
     major_ticks: Vec<XTick>,
+    minor_ticks: Vec<XTick>,
+    offset_text: Option<String>,
 
-    is_bottom: bool,
+    secondary: Option<SecondaryTransform>,
 
+    is_bottom: bool,
 }
 
 impl XAxis {
-    pub(crate) fn new(cfg: &Config, prefix: &str) -> Self {
+    pub(crate) fn new(cfg: &Config, prefix: &str, is_bottom: bool) -> Self {
         let mut x_axis = Self {
             axis: Axis::new(cfg, prefix),
 
             major_ticks: Vec::new(),
+            minor_ticks: Vec::new(),
+            offset_text: None,
+
+            secondary: None,
 
-            is_bottom: true,
+            is_bottom,
         };
 
         if x_axis.is_bottom {
@@ -50,18 +109,34 @@ impl XAxis {
         to_canvas: &dyn Transform<Data>,
     ) {
         self.major_ticks = Vec::new();
+        self.minor_ticks = Vec::new();
 
-        let xmin = data.data_bounds().xmin();
-        let xmax = data.data_bounds().xmax();
+        // a secondary axis locates and labels ticks in its own units
+        let (xmin, xmax) = match &self.secondary {
+            Some(secondary) => secondary.range(data.data_bounds().xmin(), data.data_bounds().xmax()),
+            None => (data.data_bounds().xmin(), data.data_bounds().xmax()),
+        };
 
-        let xvalues : Vec<f32> = self
-            .x_ticks(data.pos(), data.data_bounds())
-            .iter()
-            .map(|x| x.0)
-            .collect();
+        let xvalues : Vec<f32> = match &self.secondary {
+            Some(_) => self.secondary_ticks(xmin, xmax),
+            None => self.x_ticks(data),
+        };
 
         let delta = Axis::value_delta(&xvalues);
 
+        let visible: Vec<f32> = xvalues.iter()
+            .filter(|x| xmin <= **x && **x <= xmax)
+            .map(|x| *x)
+            .collect();
+
+        self.axis.set_locs(&visible);
+
+        self.offset_text = match &self.axis.labels {
+            Some(_) => None,
+            None if ! self.axis.is_tick_label_visible() => None,
+            None => self.axis.major().offset_text(&self.axis),
+        };
+
         for (i, xv) in xvalues.iter().enumerate() {
             if xmin <= *xv && *xv <= xmax {
                 let label = if let Some(labels) = &self.axis.labels {
@@ -71,39 +146,63 @@ impl XAxis {
                 };
 
                 let is_grid = self.axis.get_show_grid().is_show_major();
+                let x = self.to_data(*xv);
 
-                self.major_ticks.push(XTick::new(*xv, data, to_canvas, Some(label), is_grid));
+                if x.is_finite() {
+                    self.major_ticks.push(XTick::new(
+                        x, data, to_canvas, Some(label), is_grid, TICK_LENGTH, self.is_bottom
+                    ));
+                }
             };
         }
+
+        let is_grid = self.axis.get_show_grid().is_show_minor();
+        let length = minor_tick_length(&self.axis);
+
+        for xv in self.axis.minor_values(&xvalues, xmin, xmax) {
+            let x = self.to_data(xv);
+
+            if x.is_finite() {
+                self.minor_ticks.push(XTick::new(
+                    x, data, to_canvas, None, is_grid, length, self.is_bottom
+                ));
+            }
+        }
     }
 
-    pub fn x_ticks(
-        &self, 
-        pos: Bounds<Canvas>,
-        bounds: Bounds<Data>,
-    ) -> Vec<(f32, f32)> {
-        let c_width = pos.width();
+    pub(crate) fn secondary(&mut self, secondary: SecondaryTransform) {
+        self.secondary = Some(secondary);
+    }
 
-        let view = bounds;
-        let v_width = view.width();
+    fn to_data(&self, value: f32) -> f32 {
+        match &self.secondary {
+            Some(secondary) => (secondary.inverse)(value),
+            None => value,
+        }
+    }
+
+    fn secondary_ticks(&self, min: f32, max: f32) -> Vec<f32> {
+        if let Some(ticks) = &self.axis.ticks {
+            ticks.clone()
+        } else {
+            let (min, max) = self.axis.locator.view_limits(min, max);
+
+            self.axis.locator.tick_values(min, max).as_slice().into()
+        }
+    }
+
+    pub fn x_ticks(&self, data: &DataFrame) -> Vec<f32> {
+        let view = data.data_bounds();
 
         if view.is_none() {
             Vec::new()
         } else if let Some(ticks) = &self.axis.ticks {
-            ticks.iter().map(|x| (*x, *x)).collect::<Vec<(f32, f32)>>()
+            ticks.clone()
         } else {
             let (vmin, vmax) = (view.xmin(), view.xmax());
             let (min, max) = self.axis.locator.view_limits(vmin, vmax);
 
-            // self.locator.tick_values(min, max)
-
-            let mut x_vec = Vec::<(f32, f32)>::new();
-
-            for x in self.axis.locator.tick_values(min, max).iter() {
-                x_vec.push((*x, ((x - vmin) * c_width / v_width).round()));
-            }
-
-            x_vec
+            self.axis.locator.tick_values(min, max).as_slice().into()
         }
     }
 
@@ -115,8 +214,12 @@ impl XAxis {
     ) -> renderer::Result<f32> {
         let pos = data.pos();
 
-        let mut y = if self.is_bottom { pos.ymin() } else { pos.ymax() };
-        let sign = if self.is_bottom { -1.0f32 } else { 1.0f32 };
+        if self.axis.get_show_grid().is_show_minor() {
+            let style = self.axis.minor().grid_style().push(style);
+            for tick in &self.minor_ticks {
+                tick.draw_grid(ui, &style)?;
+            }
+        }
 
         if self.axis.get_show_grid().is_show_major() {
             let style = self.axis.major().grid_style.push(style);
@@ -128,12 +231,86 @@ impl XAxis {
         if self.axis.is_visible() {
             self.draw_ticks(ui, style)?;
 
+            if let Some(text) = &self.offset_text {
+                let y = self.labels_edge(ui, &pos);
+
+                math_text::draw_text(ui, Point(pos.xmax(), y), text, 0., style, &self.offset_style())?;
+            }
+        }
+
+        Ok(self.outer_edge(ui, &pos))
+    }
+
+    fn offset_style(&self) -> TextStyle {
+        let mut text_style = TextStyle::new();
+        text_style.halign(HorizAlign::Right);
+        text_style.valign(if self.is_bottom { VertAlign::Top } else { VertAlign::Bottom });
+
+        text_style
+    }
+
+    ///
+    /// The y of the tick labels' far edge from the data area, or of the
+    /// ticks without labels.
+    ///
+    fn labels_edge(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> f32 {
+        let mut y = if self.is_bottom { pos.ymin() } else { pos.ymax() };
+        let sign = if self.is_bottom { -1.0f32 } else { 1.0f32 };
+
+        if self.axis.is_visible() {
             y += sign * ui.to_px(self.axis.major().get_size());
-            y += sign * ui.to_px(self.axis.major().get_pad());
-            y += sign * self.axis.major().get_label_height();
+
+            if self.axis.is_tick_label_visible() {
+                y += sign * ui.to_px(self.axis.major().get_pad());
+                y += sign * self.axis.major().get_label_height();
+            }
+        }
+
+        y
+    }
+
+    ///
+    /// The y of the axis' far edge from the data area, including the
+    /// offset text.
+    ///
+    pub(crate) fn outer_edge(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> f32 {
+        let y = self.labels_edge(ui, pos);
+        let sign = if self.is_bottom { -1.0f32 } else { 1.0f32 };
+
+        match &self.offset_text {
+            Some(_) if self.axis.is_visible() => {
+                y + sign * self.axis.major().get_label_height()
+            }
+            _ => y,
+        }
+    }
+
+    ///
+    /// Canvas bounds of the ticks and the measured tick labels, which can
+    /// extend past the ends of the data area.
+    ///
+    pub(crate) fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        let y0 = if self.is_bottom { pos.ymin() } else { pos.ymax() };
+        let y1 = self.outer_edge(ui, pos);
+
+        let mut extent = Bounds::new(
+            Point(pos.xmin(), y0.min(y1)),
+            Point(pos.xmax(), y0.max(y1)),
+        );
+
+        if self.axis.is_visible() && self.axis.is_tick_label_visible() {
+            let label_style = self.axis.major().label_style();
+
+            for tick in &self.major_ticks {
+                if let Some(label) = &tick.label {
+                    let label_extent = text_layout::text_extent(ui, tick.pos, label, label_style);
+
+                    extent = extent.union(&label_extent);
+                }
+            }
         }
 
-        Ok(y)
+        extent
     }
 
     fn draw_ticks(
@@ -146,7 +323,16 @@ impl XAxis {
 
             for tick in &self.major_ticks {
                 tick.draw_tick(ui, &tick_style)?;
-                tick.draw_text(ui, style, self.axis.major().label_style())?;
+
+                if self.axis.is_tick_label_visible() {
+                    tick.draw_text(ui, style, self.axis.major().label_style())?;
+                }
+            }
+
+            let tick_style = self.axis.minor().tick_style().push(style);
+
+            for tick in &self.minor_ticks {
+                tick.draw_tick(ui, &tick_style)?;
             }
         }
 
@@ -176,14 +362,17 @@ impl XTick {
         data: &DataFrame, 
         to_canvas: &dyn Transform<Data>,
         label: Option<String>, 
-        is_grid: bool
+        is_grid: bool,
+        tick_length: f32,
+        is_bottom: bool,
     ) -> Self {
         let (ymin, ymax) = (data.data_bounds().ymin(), data.data_bounds(). ymax());
+        let (y_axis, y_far) = if is_bottom { (ymin, ymax) } else { (ymax, ymin) };
 
-        let Point(x1, y1) = to_canvas.transform_point(Point(x, ymin));
+        let Point(x1, y1) = to_canvas.transform_point(Point(x, y_axis));
 
         let grid = if is_grid {
-            let Point(x2, y2) = to_canvas.transform_point(Point(x, ymax));
+            let Point(x2, y2) = to_canvas.transform_point(Point(x, y_far));
     
             Some(Path::move_to(x1, y1)
                 .line_to(x2, y2)
@@ -193,9 +382,7 @@ impl XTick {
             None
         };
 
-        // let tick_length = renderer.to_px(major.get_size());
-        let tick_length = 10.;
-        let sign = -1.;
+        let sign = if is_bottom { -1. } else { 1. };
 
         let tick = Path::<Canvas>::move_to(x1, y1)
             .line_to(x1, y1 + sign * tick_length).to_path();
@@ -219,7 +406,7 @@ impl XTick {
         text_style: &TextStyle,
     ) -> renderer::Result<()> {
         if let Some(label) = &self.label {
-            ui.draw_text(self.pos, label, 0., style, &text_style)?;
+            math_text::draw_text(ui, self.pos, label, 0., style, &text_style)?;
         }
 
         Ok(())
@@ -260,20 +447,26 @@ pub struct YAxis {
     major_labels: Vec<String>,
 
     major_ticks2: Vec<YTick>,
+    minor_ticks: Vec<YTick>,
+
+    offset_text: Option<(Point, String)>,
 
     is_left: bool,
 }
 
 impl YAxis {
-    pub(crate) fn new(cfg: &Config, prefix: &str) -> Self {
+    pub(crate) fn new(cfg: &Config, prefix: &str, is_left: bool) -> Self {
         let mut y_axis = Self {
             axis: Axis::new(cfg, prefix),
 
             major_ticks: Vec::new(),
             major_labels: Vec::new(),
             major_ticks2: Vec::new(),
+            minor_ticks: Vec::new(),
 
-            is_left: true,
+            offset_text: None,
+
+            is_left,
         };
 
         y_axis.axis.major_mut().label_style_mut().valign(VertAlign::Center);
@@ -297,6 +490,7 @@ impl YAxis {
         self.major_ticks = Vec::new();
         self.major_labels = Vec::new();
         self.major_ticks2 = Vec::new();
+        self.minor_ticks = Vec::new();
 
         let ymin = data.data_bounds().ymin();
         let ymax = data.data_bounds().ymax();
@@ -305,7 +499,26 @@ impl YAxis {
 
         let delta = Axis::value_delta(&yvalues);
 
-        let mut width = 0;
+        let visible: Vec<f32> = yvalues.iter()
+            .filter(|y| ymin <= **y && **y <= ymax)
+            .map(|y| *y)
+            .collect();
+
+        self.axis.set_locs(&visible);
+
+        let pos = data.pos();
+
+        self.offset_text = match &self.axis.labels {
+            Some(_) => None,
+            None if ! self.axis.is_tick_label_visible() => None,
+            None => self.axis.major().offset_text(&self.axis).map(|text| {
+                let x = if self.is_left { pos.xmin() } else { pos.xmax() };
+
+                (Point(x, pos.ymax() + ui.to_px(self.axis.major().get_pad())), text)
+            })
+        };
+
+        let mut width: f32 = 0.;
 
         for (i, yv) in yvalues.iter().enumerate() {
             if ymin <= *yv && *yv <= ymax {
@@ -318,20 +531,32 @@ impl YAxis {
                     self.axis.major().format(&self.axis, tick_v, delta)
                 };
 
-                width = width.max(label.len());
+                width = width.max(math_text::text_width(&label));
 
                 self.major_labels.push(label.clone());
-                self.major_ticks2.push(YTick::new(*yv, data, to_canvas, Some(label), true));
+                self.major_ticks2.push(YTick::new(
+                    *yv, data, to_canvas, Some(label), true, TICK_LENGTH, self.is_left
+                ));
             };
         }
 
-        let pos = data.pos();
+        let length = minor_tick_length(&self.axis);
+
+        for yv in self.axis.minor_values(&yvalues, ymin, ymax) {
+            self.minor_ticks.push(YTick::new(
+                yv, data, to_canvas, None, true, length, self.is_left
+            ));
+        }
+
         let (mut x, sign) = if self.is_left { (pos.xmin(), -1.) } else { (pos.xmax(), 1.) };
         
         if self.axis.is_visible {
             x += sign * ui.to_px(self.axis.major().get_size());
-            x += sign * ui.to_px(self.axis.major().get_pad());
-            x += sign * 0.5 * width as f32 * self.axis.major().get_label_height();
+
+            if self.axis.is_tick_label_visible() {
+                x += sign * ui.to_px(self.axis.major().get_pad());
+                x += sign * width * self.axis.major().get_label_height();
+            }
         }
 
         x
@@ -358,6 +583,14 @@ impl YAxis {
         style: &dyn PathOpt,
     ) -> renderer::Result<()> {
         // Grid
+        if self.axis.get_show_grid().is_show_minor() {
+            let style = self.axis.minor().grid_style().push(style);
+
+            for tick in &self.minor_ticks {
+                tick.draw_grid(ui, &style)?;
+            }
+        }
+
         if self.axis.get_show_grid().is_show_major() {
             let style = self.axis.major().grid_style().push(style);
 
@@ -369,13 +602,73 @@ impl YAxis {
         if self.axis.is_visible() {
             for tick in &self.major_ticks2 {
                 tick.draw_tick(ui, style)?;
-                tick.draw_text(ui, style, self.axis.major().label_style())?;
+
+                if self.axis.is_tick_label_visible() {
+                    tick.draw_text(ui, style, self.axis.major().label_style())?;
+                }
+            }
+
+            let tick_style = self.axis.minor().tick_style().push(style);
+
+            for tick in &self.minor_ticks {
+                tick.draw_tick(ui, &tick_style)?;
+            }
+
+            if let Some((pos, text)) = &self.offset_text {
+                math_text::draw_text(ui, *pos, text, 0., style, &self.offset_style())?;
             }
         }
 
         Ok(())
     }
 
+    fn offset_style(&self) -> TextStyle {
+        let mut text_style = TextStyle::new();
+        text_style.halign(if self.is_left { HorizAlign::Left } else { HorizAlign::Right });
+        text_style.valign(VertAlign::Bottom);
+
+        text_style
+    }
+
+    ///
+    /// Canvas bounds of the ticks, the measured tick labels and the offset
+    /// text above the axis.
+    ///
+    pub(crate) fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        let (x0, sign) = if self.is_left { (pos.xmin(), -1.) } else { (pos.xmax(), 1.) };
+
+        let x1 = if self.axis.is_visible() {
+            x0 + sign * ui.to_px(self.axis.major().get_size())
+        } else {
+            x0
+        };
+
+        let mut extent = Bounds::new(
+            Point(x0.min(x1), pos.ymin()),
+            Point(x0.max(x1), pos.ymax()),
+        );
+
+        if self.axis.is_visible() && self.axis.is_tick_label_visible() {
+            let label_style = self.axis.major().label_style();
+
+            for tick in &self.major_ticks2 {
+                if let Some(label) = &tick.label {
+                    let label_extent = text_layout::text_extent(ui, tick.pos, label, label_style);
+
+                    extent = extent.union(&label_extent);
+                }
+            }
+        }
+
+        if self.axis.is_visible() {
+            if let Some((pos, text)) = &self.offset_text {
+                extent = extent.union(&text_layout::text_extent(ui, *pos, text, &self.offset_style()));
+            }
+        }
+
+        extent
+    }
+
     pub(crate) fn axis_mut(&mut self) -> &mut Axis {
         &mut self.axis
     }
@@ -396,15 +689,17 @@ impl YTick {
         data: &DataFrame, 
         to_canvas: &dyn Transform<Data>,
         label: Option<String>, 
-        is_grid: bool
+        is_grid: bool,
+        tick_length: f32,
+        is_left: bool,
     ) -> Self {
         let (xmin, xmax) = (data.data_bounds().xmin(), data.data_bounds().xmax());
+        let (x_axis, x_far) = if is_left { (xmin, xmax) } else { (xmax, xmin) };
 
-        // todo: left vs right
-        let Point(x1, y1) = to_canvas.transform_point(Point(xmin, y));
+        let Point(x1, y1) = to_canvas.transform_point(Point(x_axis, y));
 
         let grid = if is_grid {
-            let Point(x2, y2) = to_canvas.transform_point(Point(xmax, y));
+            let Point(x2, y2) = to_canvas.transform_point(Point(x_far, y));
     
             Some(Path::move_to(x1, y1)
                 .line_to(x2, y2)
@@ -414,9 +709,7 @@ impl YTick {
             None
         };
 
-        // let tick_length = renderer.to_px(major.get_size());
-        let tick_length = 10.;
-        let sign = -1.;
+        let sign = if is_left { -1. } else { 1. };
 
         let tick = Path::<Canvas>::move_to(x1, y1)
             .line_to(x1 + sign * tick_length, y1).to_path();
@@ -440,7 +733,7 @@ impl YTick {
         text_style: &TextStyle,
     ) -> renderer::Result<()> {
         if let Some(label) = &self.label {
-            ui.draw_text(self.pos, label, 0., style, &text_style)?;
+            math_text::draw_text(ui, self.pos, label, 0., style, &text_style)?;
         }
 
         Ok(())
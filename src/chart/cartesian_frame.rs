@@ -1,5 +1,6 @@
 use std::f32::consts::PI;
 
+use essay_tensor::tensor::Tensor;
 use essay_graphics::{
     api::{
         renderer::{Canvas, Drawable, Renderer, Result}, 
@@ -10,15 +11,21 @@ use essay_graphics::{
 
 use crate::{
     artist::{
-        patch::CanvasPatch, paths, ArtistDraw, Colorbar, Stale, TextCanvas
+        patch::CanvasPatch, paths, ArtistDraw, Colorbar, Norms, Stale, TextCanvas
     }, 
     config::{Config, ConfigArc, PathStyle}, 
     palette::Palette, 
+    text_bbox_options,
     transform::{CartesianTransform, ToCanvas, Transform}
 };
 
 use super::{
-    axis::{Axis, AxisTicks}, cartesian_axis::{XAxis, YAxis}, data_frame::DataFrame, legend::Legend, Data
+    axis::{Axis, AxisTicks}, 
+    cartesian_axis::{SecondaryTransform, XAxis, YAxis}, 
+    category::AxisData, data_frame::DataFrame, legend::Legend,
+    tick_formatter::{Formatter, ScalarFormatter, TickFormatter},
+    tick_locator::{LogLocator, MaxNLocator, TickLocator},
+    Data, Scale
 };
 
 pub struct CartesianFrame {
@@ -29,9 +36,9 @@ pub struct CartesianFrame {
     to_canvas: CartesianTransform<Data>,
 
     margins: FrameMargins,
-
-    _is_share_x: bool,
-    _is_share_y: bool,
+    is_tight: bool,
+    // canvas bounds of the figure, when the chart shares it
+    figure_pos: Option<Bounds<Canvas>>,
 
     path_style: PathStyle,
 
@@ -47,6 +54,11 @@ pub struct CartesianFrame {
     //is_frame_visible: bool,
     legend: Legend,
 
+    // twin charts drawn over this one, sharing an axis
+    twins: Vec<View<CartesianFrame>>,
+    is_x_top: bool,
+    is_y_right: bool,
+
     //is_stale: bool, 
     //aspect_ratio: Option<f32>,
     //box_aspect_ratio: Option<f32>,
@@ -59,13 +71,11 @@ pub struct CartesianFrame {
     // transform
     // xbound (min, max)
     // xmargin
-    // xscale - linear, log, symlog, logit
     // xticks - sets ticks and labels
     // ybound
     // ylabel
     // ylim
     // ymargin
-    // yscale
     // yticks
     // zorder
 
@@ -87,11 +97,13 @@ impl CartesianFrame {
             title: TextCanvas::new(),
 
             margins: FrameMargins::new(cfg),
+            is_tight: false,
+            figure_pos: None,
 
             bottom: BottomFrame::new(cfg),
             left: LeftFrame::new(cfg),
-            top: TopFrame::new(),
-            right: RightFrame::new(),
+            top: TopFrame::new(cfg),
+            right: RightFrame::new(cfg),
 
             path_style: PathStyle::default(),
 
@@ -99,8 +111,9 @@ impl CartesianFrame {
 
             legend: Legend::new(cfg),
 
-            _is_share_x: false,
-            _is_share_y: false,
+            twins: Vec::new(),
+            is_x_top: false,
+            is_y_right: false,
 
             stale_for_update: Stale::new_for_update(),
             stale: Stale::default(),
@@ -119,36 +132,189 @@ impl CartesianFrame {
 
     pub(crate) fn get_axis_mut(&mut self, artist: FrameArtist) -> &mut Axis {
         match artist {
-            FrameArtist::X => self.bottom.axis_mut(),
-            FrameArtist::Y => self.left.axis_mut(),
+            FrameArtist::X => self.x_axis_mut(),
+            FrameArtist::Y => self.y_axis_mut(),
+            FrameArtist::XSecondary => {
+                match &mut self.top.x_axis {
+                    Some(x_axis) => x_axis.axis_mut(),
+                    None => panic!("secondary x axis is not set"),
+                }
+            }
 
             _ => panic!("Invalid axis {:?}", artist)
         }
     }
 
+    ///
+    /// Numeric values for artist data, mapping category names to the
+    /// axis' category positions.
+    ///
+    pub(crate) fn axis_values(&mut self, artist: FrameArtist, data: AxisData) -> Tensor {
+        match data {
+            AxisData::Values(values) => values,
+            AxisData::Categories(names) => {
+                let categories = match artist {
+                    FrameArtist::X => self.data.x_categories_mut(),
+                    FrameArtist::Y => self.data.y_categories_mut(),
+                    _ => panic!("Invalid category axis {:?}", artist)
+                };
+
+                let positions = categories.positions(&names);
+                let names = Vec::from(categories.names());
+
+                self.get_axis_mut(artist).categories(&names);
+
+                Tensor::from(positions)
+            }
+        }
+    }
+
+    pub(crate) fn get_legend_mut(&mut self, artist: FrameArtist) -> &mut Legend {
+        match artist {
+            FrameArtist::Legend => &mut self.legend,
+
+            _ => panic!("Invalid legend {:?}", artist)
+        }
+    }
+
     pub(crate) fn get_ticks_mut(&mut self, artist: FrameArtist) -> &mut AxisTicks {
         match artist {
-            FrameArtist::XMajor => self.bottom.axis_mut().major_mut(),
-            FrameArtist::XMinor => self.bottom.axis_mut().minor_mut(),
-            FrameArtist::YMajor => self.left.axis_mut().major_mut(),
-            FrameArtist::YMinor => self.left.axis_mut().minor_mut(),
+            FrameArtist::XMajor => self.x_axis_mut().major_mut(),
+            FrameArtist::XMinor => self.x_axis_mut().minor_mut(),
+            FrameArtist::YMajor => self.y_axis_mut().major_mut(),
+            FrameArtist::YMinor => self.y_axis_mut().minor_mut(),
 
             _ => panic!("Invalid axis-texts {:?}", artist)
         }
     }
 
+    /// The primary x axis, on top for a twin sharing the y axis
+    fn x_axis_mut(&mut self) -> &mut Axis {
+        if self.is_x_top {
+            if let Some(x_axis) = &mut self.top.x_axis {
+                return x_axis.axis_mut();
+            }
+        }
+
+        self.bottom.axis_mut()
+    }
+
+    /// The primary y axis, on the right for a twin sharing the x axis
+    fn y_axis_mut(&mut self) -> &mut Axis {
+        if self.is_y_right {
+            if let Some(y_axis) = &mut self.right.y_axis {
+                return y_axis.axis_mut();
+            }
+        }
+
+        self.left.axis_mut()
+    }
+
+    pub(crate) fn set_scale(&mut self, artist: FrameArtist, scale: Scale) {
+        match artist {
+            FrameArtist::X => {
+                self.data.x_scale(scale);
+                self.x_axis_mut().set_scale(scale);
+            }
+            FrameArtist::Y => {
+                self.data.y_scale(scale);
+                self.y_axis_mut().set_scale(scale);
+            }
+
+            _ => panic!("Invalid axis {:?}", artist)
+        }
+
+        self.stale = Stale::default();
+    }
+
     pub(crate) fn colorbar(&mut self) {
         self.right.colorbar();
     }
 
+    pub(crate) fn add_twin(&mut self, twin: View<CartesianFrame>) {
+        self.twins.push(twin);
+    }
+
+    ///
+    /// Configures a twin sharing the x axis: the x axis is drawn by the
+    /// original chart and the y axis is drawn on the right.
+    ///
+    pub(crate) fn twin_x_frame(&mut self) {
+        self.bottom.axis_mut().is_visible = false;
+        self.left.axis_mut().is_visible = false;
+
+        self.right.y_axis = Some(YAxis::new(&self.config, "y_axis", false));
+        self.is_y_right = true;
+    }
+
+    ///
+    /// Configures a twin sharing the y axis: the y axis is drawn by the
+    /// original chart and the x axis is drawn on top.
+    ///
+    pub(crate) fn twin_y_frame(&mut self) {
+        self.bottom.axis_mut().is_visible = false;
+        self.left.axis_mut().is_visible = false;
+
+        self.top.x_axis = Some(XAxis::new(&self.config, "x_axis", false));
+        self.is_x_top = true;
+    }
+
+    fn is_twin(&self) -> bool {
+        self.is_x_top || self.is_y_right
+    }
+
+    pub(crate) fn secondary_x(&mut self, secondary: SecondaryTransform) {
+        assert!(! self.is_x_top, "secondary x axis conflicts with a twin's top x axis");
+
+        let mut x_axis = XAxis::new(&self.config, "x_axis", false);
+        x_axis.secondary(secondary);
+
+        self.top.x_axis = Some(x_axis);
+    }
+
     pub(crate) fn color_cycle(&mut self, cycle: impl Into<Palette>) {
         self.data.color_cycle(cycle);
     }
 
+    pub(crate) fn clear_margins(&mut self) {
+        self.margins = FrameMargins::none();
+    }
+
+    ///
+    /// Set by the figure at layout time. A tight frame fits its measured
+    /// decorations in its area instead of using the margins.
+    ///
+    pub(crate) fn set_tight_layout(&mut self, is_tight: bool) {
+        if self.is_tight != is_tight {
+            self.is_tight = is_tight;
+            self.stale = Stale::default();
+            // forces a resize on the next draw
+            self.pos = Bounds::none();
+        }
+    }
+
+    pub(crate) fn is_tight_layout(&self) -> bool {
+        self.is_tight
+    }
+
+    ///
+    /// Set by the figure or grid with the figure's canvas bounds, which
+    /// figure-fraction text positions are relative to, or cleared when
+    /// the figure's bounds aren't known yet.
+    ///
+    pub(crate) fn set_figure_pos(&mut self, pos: Option<Bounds<Canvas>>) {
+        for twin in &mut self.twins {
+            twin.write(|frame| frame.set_figure_pos(pos));
+        }
+
+        self.figure_pos = pos;
+    }
+
     fn check_cache(&mut self, ui: &mut dyn Renderer) -> bool {
         if self.stale != self.stale_for_update
             || self.pos_cache != ui.pos()
-            || self.data_cache != self.data.data_bounds() {
+            || self.data_cache != self.data.data_bounds()
+            || self.data.is_shared_stale() {
             self.stale_for_update = self.stale_for_update.update();
             self.stale = self.stale_for_update;
             self.pos_cache = ui.pos();
@@ -165,13 +331,75 @@ impl CartesianFrame {
             return;
         }
 
+        // a twin's margins follow the chart it's drawn over
+        let margins = if self.is_tight && ! self.is_twin() {
+            self.tight_margins(ui)
+        } else {
+            self.margins.clone()
+        };
+
+        // twins are drawn over this chart, so they share its data area
+        for twin in &mut self.twins {
+            twin.write(|frame| {
+                frame.margins = margins.clone();
+                frame.stale = Stale::default();
+            });
+        }
+
+        self.layout(ui, &margins);
+    }
+
+    ///
+    /// Margins that fit the measured tick labels, axis labels, title and
+    /// colorbar of this chart and its twins inside the chart's area.
+    ///
+    fn tight_margins(&mut self, ui: &mut dyn Renderer) -> FrameMargins {
+        let margins = self.margins.clone();
+
+        self.layout(ui, &margins);
+        let mut extent = self.extent(ui);
+
+        for twin in &mut self.twins {
+            let twin_extent = twin.write(|frame| {
+                frame.layout(ui, &margins);
+                frame.extent(ui)
+            });
+
+            extent = extent.union(&twin_extent);
+        }
+
+        let pad = ui.to_px(self.config.get_as_type("figure", "tight_pad").unwrap_or(4.));
+
+        // decorations larger than the chart keep the configured margins
+        match tight_fit(&ui.pos(), &self.pos, &extent, pad) {
+            Some(tight) => tight,
+            None => margins,
+        }
+    }
+
+    ///
+    /// Canvas bounds of the frame's data area and its decorations.
+    ///
+    fn extent(&self, ui: &dyn Renderer) -> Bounds<Canvas> {
+        let pos_data = self.data.pos();
+
+        let extent = pos_data.clone()
+            .union(&self.bottom.extent(ui, &pos_data))
+            .union(&self.left.extent(ui, &pos_data))
+            .union(&self.top.extent(ui, &pos_data))
+            .union(&self.right.extent(ui, &pos_data));
+
+        text_union(extent, &self.title)
+    }
+
+    fn layout(&mut self, ui: &mut dyn Renderer, margins: &FrameMargins) {
         let pos = ui.pos();
 
         let pos = Bounds::from([
-            [pos.xmin() + pos.width() * self.margins.left,
-            pos.ymin() + pos.height() * self.margins.top],
-            [pos.xmin() + pos.width() * self.margins.right,
-            pos.ymin() + pos.height() * self.margins.bottom]
+            [pos.xmin() + pos.width() * margins.left,
+            pos.ymin() + pos.height() * margins.top],
+            [pos.xmin() + pos.width() * margins.right,
+            pos.ymin() + pos.height() * margins.bottom]
         ]);
     
         self.pos = pos.clone();
@@ -191,11 +419,22 @@ impl CartesianFrame {
             Point(pos.xmin(), pos.ymin()), 
             Point(pos.xmax(), pos.ymax()),
         );
+
+        // an outside legend takes room from the data area
+        let pos_outer = ui.pos();
+        self.legend.update_handlers(&mut self.data);
+        self.legend.resize(ui, &pos_data, &pos_outer);
+        let pos_data = self.legend.reserve(&pos_data);
     
         self.data.update_pos(ui, &pos_data);
     
         let pos_data = self.data.pos().clone();
-        self.to_canvas = CartesianTransform::bounds_to(self.data.data_bounds(), pos_data);
+        self.to_canvas = CartesianTransform::scaled_bounds_to(
+            self.data.data_bounds(), 
+            pos_data,
+            self.data.get_x_scale(),
+            self.data.get_y_scale(),
+        );
     
         let pos_top = Bounds::<Canvas>::new(
             Point(pos_data.xmin(), pos_data.ymax()),
@@ -209,25 +448,35 @@ impl CartesianFrame {
         );
         self.right.resize(ui, &pos_right);
     
-        let pos_legend = Bounds::<Canvas>::new(
-            Point(pos_data.xmin(), pos_data.ymax()),
-            Point(pos_data.xmin(), pos_data.ymax()),
-        );
-        self.legend.resize(ui, &pos_legend);
+        self.legend.resize(ui, &pos_data, &pos_outer);
     
         self.bottom.resize(ui, &self.data, &self.to_canvas);
         self.left.resize(ui, &self.data, &self.to_canvas);
+
+        // a top axis pushes the title up
+        if let Some(y) = self.top.update_axis(ui, &self.data, &self.to_canvas) {
+            let height = self.title.height();
+
+            self.title.update_pos(ui, Bounds::from([
+                [pos.xmin(), y], 
+                [pos.xmax(), y + height]
+            ]));
+        }
+
+        self.right.update_axis(ui, &self.data, &self.to_canvas);
     
         self.top.resize(ui, pos_data);
         self.right.resize(ui, &pos_data);
     
-        self.legend.update_handlers(&mut self.data);
+        self.legend.update_points(&self.to_canvas);
+        self.right.update_colorbar(&mut self.data);
     }
 }
 
 impl Drawable for CartesianFrame {
     fn draw(&mut self, ui: &mut dyn Renderer) -> Result<()> {
-        if self.pos != ui.pos() {
+        // a linked frame's new data changes the shared range
+        if self.pos != ui.pos() || self.data.is_shared_stale() {
             self.resize(ui);
         }
 
@@ -238,11 +487,19 @@ impl Drawable for CartesianFrame {
             &frame_affine,
         );
 
+        // a chart alone on the page covers the figure
+        let figure_pos = self.figure_pos.unwrap_or(ui.pos());
+
         let data_to_canvas = ToCanvas::new(
             self.stale,
             self.data.data_bounds(), 
             &self.to_canvas,
-        );
+        ).figure(figure_pos);
+
+        // the title's position above a top axis is set in layout
+        self.top.draw_axis(ui, &self.data, &frame_to_canvas, &self.path_style)?;
+
+        self.right.draw_axis(ui, &frame_to_canvas, &self.path_style)?;
 
         self.title.draw(ui, &frame_to_canvas, &self.path_style)?;
 
@@ -259,6 +516,10 @@ impl Drawable for CartesianFrame {
 
         self.legend.draw(ui, &frame_to_canvas, &self.path_style)?;
 
+        for twin in &mut self.twins {
+            twin.drawable().draw(ui)?;
+        }
+
         Ok(())
     }
 }
@@ -267,8 +528,11 @@ impl FrameWithTextArtist for CartesianFrame {
     fn get_text_mut(&mut self, artist: FrameArtist) -> &mut TextCanvas {
         match artist {
             FrameArtist::Title => &mut self.title,
+            FrameArtist::XLabel if self.is_x_top => &mut self.top.title,
             FrameArtist::XLabel => &mut self.bottom.title,
+            FrameArtist::YLabel if self.is_y_right => &mut self.right.title,
             FrameArtist::YLabel => &mut self.left.title,
+            FrameArtist::XSecondaryLabel => &mut self.top.title,
 
             _ => panic!("Invalid text {:?}", artist)
         }
@@ -286,6 +550,9 @@ pub enum FrameArtist {
     YMinor,
     XLabel,
     YLabel,
+    XSecondary,
+    XSecondaryLabel,
+    Legend,
 }
 
 pub struct FrameSizes {
@@ -311,15 +578,27 @@ pub struct TopFrame {
     bounds: Bounds<Canvas>,
     pos: Bounds<Canvas>,
     spine: Option<CanvasPatch>,
+
+    sizes: FrameSizes,
+    x_axis: Option<XAxis>,
+    title: TextCanvas,
 }
 
 impl TopFrame {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(cfg: &Config) -> Self {
+        let mut frame = Self {
             bounds: Bounds::new(Point(0., 0.), Point(0., 20.)),
             pos: Bounds::none(),
             spine: Some(CanvasPatch::new(paths::line(Point(0., 0.), Point(1., 0.)))),
-        }
+
+            sizes: FrameSizes::new(cfg),
+            x_axis: None,
+            title: TextCanvas::new(),
+        };
+
+        frame.title.text_style_mut().valign(VertAlign::Bottom);
+
+        frame
     }
 
     pub fn set_pos(&mut self, pos: Bounds<Canvas>) {
@@ -336,6 +615,61 @@ impl TopFrame {
     fn resize(&mut self, _renderer: &mut dyn Renderer, pos: Bounds<Canvas>) {
         self.set_pos(pos);
     }
+
+    ///
+    /// Updates the top x axis and places its label, returning the top of
+    /// the label, or None without a top axis.
+    ///
+    fn update_axis(
+        &mut self, 
+        ui: &mut dyn Renderer, 
+        data: &DataFrame, 
+        to_canvas: &dyn Transform<Data>
+    ) -> Option<f32> {
+        let x_axis = match &mut self.x_axis {
+            Some(x_axis) => x_axis,
+            None => return None,
+        };
+
+        x_axis.resize(ui, data.pos());
+        x_axis.update_axis(data, to_canvas);
+
+        let y = x_axis.outer_edge(ui, &data.pos()) + ui.to_px(self.sizes.label_pad);
+
+        self.title.update_pos(ui, Bounds::new(
+            Point(data.pos().xmin(), y),
+            Point(data.pos().xmax(), y),
+        ));
+
+        Some(y + self.title.height())
+    }
+
+    fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        match &self.x_axis {
+            Some(x_axis) => text_union(x_axis.extent(ui, pos), &self.title),
+            None => pos.clone(),
+        }
+    }
+
+    ///
+    /// Draws the top x axis and its label, which `update_axis` placed.
+    ///
+    fn draw_axis(
+        &mut self, 
+        ui: &mut dyn Renderer,
+        data: &DataFrame,
+        to_canvas: &ToCanvas<Canvas>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        let x_axis = match &mut self.x_axis {
+            Some(x_axis) => x_axis,
+            None => return Ok(()),
+        };
+
+        x_axis.draw(ui, data, style)?;
+
+        self.title.draw(ui, to_canvas, style)
+    }
 }
 
 impl ArtistDraw<Canvas> for TopFrame {
@@ -375,7 +709,7 @@ impl BottomFrame {
         let mut frame = Self {
             sizes: FrameSizes::new(cfg),
 
-            x_axis: XAxis::new(cfg, "x_axis"),
+            x_axis: XAxis::new(cfg, "x_axis", true),
 
             title: TextCanvas::new(),
         };
@@ -388,9 +722,19 @@ impl BottomFrame {
     fn resize(&mut self, ui: &mut dyn Renderer, data: &DataFrame, to_canvas: &dyn Transform<Data>) {
         let pos = data.pos();
         
-        self.title.update_pos(ui, pos);
         self.x_axis.resize(ui, pos);
         self.x_axis.update_axis(data, to_canvas);
+
+        let y = self.x_axis.outer_edge(ui, &pos) - ui.to_px(self.sizes.label_pad);
+
+        self.title.update_pos(ui, Bounds::new(
+            Point(pos.xmin(), y),
+            Point(pos.xmax(), y),
+        ));
+    }
+
+    fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        text_union(self.x_axis.extent(ui, pos), &self.title)
     }
 
     fn draw(
@@ -440,7 +784,7 @@ impl LeftFrame {
         let mut frame = Self {
             sizes: FrameSizes::new(cfg),
 
-            y_axis: YAxis::new(cfg, "y_axis"),
+            y_axis: YAxis::new(cfg, "y_axis", true),
 
             title: label,
         };
@@ -469,6 +813,10 @@ impl LeftFrame {
         ));
     }
 
+    fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        text_union(self.y_axis.extent(ui, pos), &self.title)
+    }
+
     fn draw(
         &mut self, 
         ui: &mut dyn Renderer,
@@ -490,16 +838,31 @@ pub struct RightFrame {
     pos: Bounds<Canvas>,
     spine: Option<CanvasPatch>,
     colorbar: Option<Colorbar>,
+
+    sizes: FrameSizes,
+    y_axis: Option<YAxis>,
+    title: TextCanvas,
 }
 
 impl RightFrame {
-    pub fn new() -> Self {
-        Self {
+    pub fn new(cfg: &Config) -> Self {
+        let mut label = TextCanvas::new();
+        label.angle(PI / 2.);
+
+        let mut frame = Self {
             bounds: Bounds::new(Point(0., 0.), Point(20., 0.)),
             pos: Bounds::none(),
             spine: Some(CanvasPatch::new(paths::line(Point(0., 0.), Point(0., 1.)))),
             colorbar: None,
-        }
+
+            sizes: FrameSizes::new(cfg),
+            y_axis: None,
+            title: label,
+        };
+
+        frame.title.text_style_mut().valign(VertAlign::Top);
+
+        frame
     }
 
     pub fn set_pos(&mut self, pos: &Bounds<Canvas>) {
@@ -511,13 +874,6 @@ impl RightFrame {
                 Point(pos.xmin() + 1., pos.ymax()),
             ))
         }
-
-        if let Some(colorbar) = &mut self.colorbar {
-            colorbar.set_pos(Bounds::new(
-                Point(pos.xmin() + 40., pos.ymin()),
-                Point(pos.xmin() + 80., pos.ymax()),
-            ))
-        }
     }
 
     pub fn colorbar(&mut self) {
@@ -531,6 +887,89 @@ impl RightFrame {
             colorbar.resize(renderer, pos);
         }
     }
+
+    ///
+    /// Updates the colorbar from the data's color-mapped artist.
+    ///
+    fn update_colorbar(&mut self, data: &mut DataFrame) {
+        if let Some(colorbar) = &mut self.colorbar {
+            if let Some(handler) = data.get_colorbar() {
+                let ticks = match handler.norm() {
+                    Norms::Linear => {
+                        let locator = MaxNLocator::new(None);
+                        let formatter = ScalarFormatter::new();
+
+                        let values: Vec<f32> = locator.tick_values(handler.min(), handler.max())
+                            .iter()
+                            .map(|v| *v)
+                            .collect();
+
+                        let delta = Axis::value_delta(&values);
+
+                        values.iter()
+                            .map(|v| (*v, formatter.format(*v, delta)))
+                            .collect()
+                    }
+                    // log norms tick the data values at their scaled position
+                    norm => {
+                        let (min, max) = (norm.inverse(handler.min()), norm.inverse(handler.max()));
+
+                        LogLocator::new().tick_values(min, max)
+                            .iter()
+                            .map(|v| (norm.forward(*v), Formatter::Log10.format(*v, 0.)))
+                            .collect()
+                    }
+                };
+
+                colorbar.set_handler(handler, ticks);
+            }
+        }
+    }
+
+    fn update_axis(
+        &mut self, 
+        ui: &mut dyn Renderer, 
+        data: &DataFrame, 
+        to_canvas: &dyn Transform<Data>
+    ) {
+        if let Some(y_axis) = &mut self.y_axis {
+            let x = y_axis.resize(ui, data, to_canvas)
+                + ui.to_px(self.sizes.label_pad);
+
+            self.title.update_pos(ui, Bounds::new(
+                Point(x, data.pos().ymid()),
+                Point(x, data.pos().ymid()),
+            ));
+        }
+    }
+
+    fn extent(&self, ui: &dyn Renderer, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        let mut extent = match &self.y_axis {
+            Some(y_axis) => text_union(y_axis.extent(ui, pos), &self.title),
+            None => pos.clone(),
+        };
+
+        if let Some(colorbar) = &self.colorbar {
+            extent = extent.union(&colorbar.extent(ui));
+        }
+
+        extent
+    }
+
+    fn draw_axis(
+        &mut self, 
+        ui: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Canvas>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        if let Some(y_axis) = &mut self.y_axis {
+            y_axis.draw(ui, style)?;
+
+            self.title.draw(ui, to_canvas, style)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl ArtistDraw<Canvas> for RightFrame {
@@ -548,9 +987,8 @@ impl ArtistDraw<Canvas> for RightFrame {
             patch.draw(renderer, to_canvas, style)?;
         }
 
-        if let Some(_colorbar) = &mut self.colorbar {
-            todo!();
-            // colorbar.draw(renderer, to_canvas, style)?;
+        if let Some(colorbar) = &mut self.colorbar {
+            colorbar.draw(renderer, to_canvas, style)?;
         }
 
         Ok(())
@@ -590,12 +1028,61 @@ impl<F: FrameWithTextArtist> FrameTextOpt<F> {
         self.write(|text| { text.text_style_mut().size(size); });
         self
     }
+
+    pub fn line_spacing(&mut self, spacing: f32) -> &mut Self {
+        self.write(|text| { text.line_spacing(spacing); });
+        self
+    }
+
+    text_bbox_options!(bbox);
+}
+
+///
+/// Extends the extent by the placed text, if any.
+///
+fn text_union(extent: Bounds<Canvas>, text: &TextCanvas) -> Bounds<Canvas> {
+    let text_extent = text.canvas_extent();
+
+    if text_extent.is_none() {
+        extent
+    } else {
+        extent.union(&text_extent)
+    }
+}
+
+///
+/// Margins placing the frame inside `outer`, inset by `pad`, so that
+/// decorations sticking out of the frame `pos` by as much as they do in
+/// `extent` still fit. None when the decorations leave no room.
+///
+fn tight_fit(
+    outer: &Bounds<Canvas>,
+    pos: &Bounds<Canvas>,
+    extent: &Bounds<Canvas>,
+    pad: f32,
+) -> Option<FrameMargins> {
+    let x0 = outer.xmin() + pad + (pos.xmin() - extent.xmin()).max(0.);
+    let x1 = outer.xmax() - pad - (extent.xmax() - pos.xmax()).max(0.);
+    let y0 = outer.ymin() + pad + (pos.ymin() - extent.ymin()).max(0.);
+    let y1 = outer.ymax() - pad - (extent.ymax() - pos.ymax()).max(0.);
+
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+
+    Some(FrameMargins {
+        left: (x0 - outer.xmin()) / outer.width(),
+        right: (x1 - outer.xmin()) / outer.width(),
+        bottom: (y0 - outer.ymin()) / outer.height(),
+        top: (y1 - outer.ymin()) / outer.height(),
+    })
 }
 
 pub trait FrameWithTextArtist: Drawable + Send + 'static {
     fn get_text_mut(&mut self, artist: FrameArtist) -> &mut TextCanvas;
 }
 
+#[derive(Clone)]
 pub(super) struct FrameMargins {
     pub top: f32,
     pub bottom: f32,
@@ -621,5 +1108,48 @@ impl FrameMargins {
             right, 
         }
     }
+
+    ///
+    /// No margins, for frames whose position is already laid out, e.g. in
+    /// a grid.
+    ///
+    pub fn none() -> Self {
+        Self {
+            bottom: 0.,
+            top: 1.,
+            left: 0.,
+            right: 1.,
+        }
+    }
 }
 
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{renderer::Canvas, Bounds, Point};
+
+    use super::tight_fit;
+
+    #[test]
+    fn tight_fit_margins() {
+        let outer = Bounds::<Canvas>::new(Point(0., 0.), Point(100., 200.));
+        let pos = Bounds::<Canvas>::new(Point(20., 20.), Point(80., 180.));
+        // labels 10 left and below, a title 20 above, nothing on the right
+        let extent = Bounds::<Canvas>::new(Point(10., 10.), Point(80., 200.));
+
+        let margins = tight_fit(&outer, &pos, &extent, 5.).unwrap();
+
+        assert_eq!(margins.left, 0.15);
+        assert_eq!(margins.right, 0.95);
+        assert_eq!(margins.bottom, 0.075);
+        assert_eq!(margins.top, 0.875);
+    }
+
+    #[test]
+    fn tight_fit_no_room() {
+        let outer = Bounds::<Canvas>::new(Point(0., 0.), Point(100., 100.));
+        let pos = Bounds::<Canvas>::new(Point(40., 40.), Point(60., 60.));
+        let extent = Bounds::<Canvas>::new(Point(-20., 40.), Point(130., 60.));
+
+        assert!(tight_fit(&outer, &pos, &extent, 5.).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+use essay_tensor::tensor::Tensor;
+
+///
+/// Axis values for an artist: numbers, or category names that the chart
+/// maps to integer positions.
+///
+pub enum AxisData {
+    Values(Tensor),
+    Categories(Vec<String>),
+}
+
+pub trait IntoAxisData {
+    fn into_axis_data(self) -> AxisData;
+}
+
+impl IntoAxisData for AxisData {
+    fn into_axis_data(self) -> AxisData {
+        self
+    }
+}
+
+impl IntoAxisData for Tensor {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Values(self)
+    }
+}
+
+impl IntoAxisData for &Tensor {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Values(self.clone())
+    }
+}
+
+impl IntoAxisData for Vec<f32> {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Values(Tensor::from(self))
+    }
+}
+
+impl IntoAxisData for &[f32] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Values(Tensor::from(Vec::from(self)))
+    }
+}
+
+impl<const N: usize> IntoAxisData for [f32; N] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Values(Tensor::from(Vec::from(self)))
+    }
+}
+
+impl IntoAxisData for &[&str] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(self.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl<const N: usize> IntoAxisData for [&str; N] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(self.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl<const N: usize> IntoAxisData for &[&str; N] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(self.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl IntoAxisData for Vec<&str> {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(self.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+impl IntoAxisData for Vec<String> {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(self)
+    }
+}
+
+impl IntoAxisData for &[String] {
+    fn into_axis_data(self) -> AxisData {
+        AxisData::Categories(Vec::from(self))
+    }
+}
+
+///
+/// Category names of an axis in first-seen order. All artists on the axis
+/// share the registry, so a name keeps its position across artists.
+///
+#[derive(Default)]
+pub(crate) struct Categories {
+    names: Vec<String>,
+}
+
+impl Categories {
+    pub(crate) fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    ///
+    /// Positions of the names, registering new names after existing ones.
+    ///
+    pub(crate) fn positions(&mut self, names: &[String]) -> Vec<f32> {
+        names.iter().map(|name| {
+            let index = match self.names.iter().position(|n| n == name) {
+                Some(index) => index,
+                None => {
+                    self.names.push(name.clone());
+                    self.names.len() - 1
+                }
+            };
+
+            index as f32
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Categories;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn categories_positions() {
+        let mut categories = Categories::default();
+
+        assert_eq!(categories.positions(&names(&["b", "a", "b"])), vec![0., 1., 0.]);
+        assert_eq!(categories.positions(&names(&["c", "a"])), vec![2., 1.]);
+        assert_eq!(categories.names(), &names(&["b", "a", "c"])[..]);
+    }
+}
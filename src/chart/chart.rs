@@ -1,16 +1,17 @@
+use essay_tensor::tensor::Tensor;
 use essay_graphics::{
-    api::renderer::{self, Drawable, Renderer},
+    api::{renderer::{self, Canvas, Drawable, Renderer}, Bounds},
     layout::View, 
 };
 
 use crate::{
     artist::{Artist, ArtistDraw}, 
-    chart::{AspectMode, AxisOpt, CartesianFrame, Data, FrameArtist, FrameTextOpt}, 
+    chart::{AspectMode, AxisOpt, CartesianFrame, Data, FrameArtist, FrameTextOpt, LegendOpt}, 
     palette::Palette, 
     config::ConfigArc
 };
 
-use super::{style::PlotOptHandle, PlotOpt, Scaling};
+use super::{cartesian_axis::SecondaryTransform, style::PlotOptHandle, IntoAxisData, PlotOpt, Scaling};
 
 #[derive(Clone)]
 pub struct Chart {
@@ -50,6 +51,13 @@ impl Chart {
         AxisOpt::new(&self.view, FrameArtist::Y)
     }
 
+    ///
+    /// Legend options. The legend shows the artists with labels.
+    ///
+    pub fn legend(&mut self) -> LegendOpt {
+        LegendOpt::new(&self.view, FrameArtist::Legend)
+    }
+
     pub fn x_label(&mut self, label: &str) -> FrameTextOpt<CartesianFrame> {
         let mut opt = self.text_opt(FrameArtist::XLabel);
         opt.label(label);
@@ -62,6 +70,116 @@ impl Chart {
         opt
     }
 
+    ///
+    /// New chart drawn over this one, sharing its x range, with its own
+    /// y axis on the right.
+    ///
+    pub fn twin_x(&mut self) -> Chart {
+        let config = self.view.write(|f| f.config().clone());
+
+        let mut twin = Chart::new(&config);
+        twin.share_x(self);
+        twin.view.write(|f| f.twin_x_frame());
+
+        self.view.write(|f| f.add_twin(twin.view.clone()));
+
+        twin
+    }
+
+    ///
+    /// New chart drawn over this one, sharing its y range, with its own
+    /// x axis on top.
+    ///
+    pub fn twin_y(&mut self) -> Chart {
+        let config = self.view.write(|f| f.config().clone());
+
+        let mut twin = Chart::new(&config);
+        twin.share_y(self);
+        twin.view.write(|f| f.twin_y_frame());
+
+        self.view.write(|f| f.add_twin(twin.view.clone()));
+
+        twin
+    }
+
+    ///
+    /// Shares the x range and scale with `other`: both charts show the
+    /// union of their data and follow limits set on either. Interactive
+    /// pan/zoom isn't shared.
+    ///
+    pub fn share_x(&mut self, other: &Chart) -> &mut Self {
+        let (shared, scale) = other.view.write(|f| {
+            (f.data_mut().share_x(), f.data_mut().get_x_scale())
+        });
+
+        self.view.write(|f| {
+            f.set_scale(FrameArtist::X, scale);
+            f.data_mut().join_x(&shared);
+        });
+
+        self
+    }
+
+    ///
+    /// Shares the y range and scale with `other`.
+    ///
+    pub fn share_y(&mut self, other: &Chart) -> &mut Self {
+        let (shared, scale) = other.view.write(|f| {
+            (f.data_mut().share_y(), f.data_mut().get_y_scale())
+        });
+
+        self.view.write(|f| {
+            f.set_scale(FrameArtist::Y, scale);
+            f.data_mut().join_y(&shared);
+        });
+
+        self
+    }
+
+    ///
+    /// Adds an x axis on top showing derived units, e.g. period for a
+    /// frequency axis with `|f| 1. / f` as both functions.
+    ///
+    pub fn secondary_x_axis(
+        &mut self, 
+        forward: impl Fn(f32) -> f32 + Send + 'static,
+        inverse: impl Fn(f32) -> f32 + Send + 'static,
+    ) -> AxisOpt {
+        self.view.write(|f| {
+            f.secondary_x(SecondaryTransform::new(forward, inverse));
+        });
+
+        AxisOpt::new(&self.view, FrameArtist::XSecondary)
+    }
+
+    pub fn secondary_x_label(&mut self, label: &str) -> FrameTextOpt<CartesianFrame> {
+        let mut opt = self.text_opt(FrameArtist::XSecondaryLabel);
+        opt.label(label);
+        opt
+    }
+
+    pub(crate) fn clear_margins(&mut self) {
+        self.view.write(|f| {
+            f.clear_margins();
+        });
+    }
+
+    pub(crate) fn set_tight_layout(&mut self, is_tight: bool) {
+        self.view.write(|f| {
+            f.set_tight_layout(is_tight);
+        });
+    }
+
+    pub(crate) fn is_tight_layout(&mut self) -> bool {
+        self.view.write(|f| f.is_tight_layout())
+    }
+
+    pub(crate) fn set_figure_pos(&mut self, pos: Option<Bounds<Canvas>>) {
+        self.view.write(|f| {
+            f.set_figure_pos(pos);
+        });
+    }
+
     pub fn scaling(&mut self, scaling: Scaling) -> &mut Self {
         self.view.write(|f| { 
             f.data_mut().scaling(scaling); 
@@ -130,6 +248,24 @@ impl Chart {
         // self.title.font().size(12.);
     }
 
+    ///
+    /// X values for an artist, registering category names on the x axis.
+    ///
+    pub(crate) fn x_values(&mut self, x: impl IntoAxisData) -> Tensor {
+        let x = x.into_axis_data();
+
+        self.view.write(|f| f.axis_values(FrameArtist::X, x))
+    }
+
+    ///
+    /// Y values for an artist, registering category names on the y axis.
+    ///
+    pub(crate) fn y_values(&mut self, y: impl IntoAxisData) -> Tensor {
+        let y = y.into_axis_data();
+
+        self.view.write(|f| f.axis_values(FrameArtist::Y, y))
+    }
+
     // TODO: should there be a plain add_artist that doesn't wrap PlotStyle?
 
     pub fn add_simple_artist<'a, A>(
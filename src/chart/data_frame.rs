@@ -1,4 +1,5 @@
 use core::fmt;
+use std::sync::{Arc, Mutex};
 
 use essay_graphics::api::{
     renderer::{Canvas, Renderer, Result}, 
@@ -6,10 +7,10 @@ use essay_graphics::api::{
 };
 
 use crate::{
-    artist::{Artist, ArtistContainer, ArtistDraw, Stale}, config::{ConfigArc, PathStyle}, palette::Palette, transform::ToCanvas
+    artist::{Artist, ArtistContainer, ArtistDraw, ColorbarHandler, Stale}, config::{ConfigArc, PathStyle}, palette::Palette, transform::ToCanvas
 };
 
-use super::LegendHandler;
+use super::{category::Categories, LegendHandler, Scale};
 
 pub(crate) struct DataFrame {
     pos_canvas: Bounds<Canvas>,
@@ -26,6 +27,15 @@ pub(crate) struct DataFrame {
     x_margin: Option<f32>,
     y_margin: Option<f32>,
 
+    x_scale: Scale,
+    y_scale: Scale,
+
+    x_shared: Option<SharedRange>,
+    y_shared: Option<SharedRange>,
+
+    x_categories: Categories,
+    y_categories: Categories,
+
     scaling: Scaling,
     aspect: Option<f32>,
     aspect_mode: AspectMode,
@@ -55,6 +65,12 @@ impl DataFrame {
             y_max: None,
             x_margin: cfg.get_as_type(prefix, "x_margin"),
             y_margin: cfg.get_as_type(prefix, "y_margin"),
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
+            x_shared: None,
+            y_shared: None,
+            x_categories: Categories::default(),
+            y_categories: Categories::default(),
             scaling: Scaling::Auto,
             aspect: None,
             aspect_mode: AspectMode::BoundingBox,
@@ -72,6 +88,14 @@ impl DataFrame {
         }
     }
 
+    pub(crate) fn x_categories_mut(&mut self) -> &mut Categories {
+        &mut self.x_categories
+    }
+
+    pub(crate) fn y_categories_mut(&mut self) -> &mut Categories {
+        &mut self.y_categories
+    }
+
     pub fn scaling(&mut self, scaling: Scaling) -> &mut Self {
         self.scaling = scaling;
 
@@ -96,6 +120,26 @@ impl DataFrame {
         self
     }
 
+    pub fn x_scale(&mut self, scale: Scale) -> &mut Self {
+        self.x_scale = scale;
+
+        self
+    }
+
+    pub fn y_scale(&mut self, scale: Scale) -> &mut Self {
+        self.y_scale = scale;
+
+        self
+    }
+
+    pub fn get_x_scale(&self) -> Scale {
+        self.x_scale
+    }
+
+    pub fn get_y_scale(&self) -> Scale {
+        self.y_scale
+    }
+
     pub fn xlim(&mut self, x_min: Option<f32>, x_max: Option<f32>) -> &mut Self {
         if let Some(x_min) = x_min {
             if let Some(x_max) = x_max {
@@ -106,6 +150,10 @@ impl DataFrame {
         self.x_min = x_min;
         self.x_max = x_max;
 
+        if let Some(shared) = &self.x_shared {
+            shared.set_lim(x_min, x_max);
+        }
+
         self
     }
 
@@ -119,8 +167,49 @@ impl DataFrame {
         self.y_min = y_min;
         self.y_max = y_max;
 
+        if let Some(shared) = &self.y_shared {
+            shared.set_lim(y_min, y_max);
+        }
+
         self
     }
+
+    ///
+    /// Shares the x range with other frames, returning the range so later
+    /// frames can join it.
+    ///
+    pub(crate) fn share_x(&mut self) -> SharedRange {
+        if self.x_shared.is_none() {
+            let shared = SharedRange::new();
+            shared.set_lim(self.x_min, self.x_max);
+            self.x_shared = Some(shared);
+            // publishes this frame's extent to frames joining later
+            self.update_data_bounds();
+        }
+
+        self.x_shared.clone().unwrap()
+    }
+
+    pub(crate) fn share_y(&mut self) -> SharedRange {
+        if self.y_shared.is_none() {
+            let shared = SharedRange::new();
+            shared.set_lim(self.y_min, self.y_max);
+            self.y_shared = Some(shared);
+            self.update_data_bounds();
+        }
+
+        self.y_shared.clone().unwrap()
+    }
+
+    pub(crate) fn join_x(&mut self, shared: &SharedRange) {
+        self.x_shared = Some(shared.join());
+        self.update_data_bounds();
+    }
+
+    pub(crate) fn join_y(&mut self, shared: &SharedRange) {
+        self.y_shared = Some(shared.join());
+        self.update_data_bounds();
+    }
     
     pub(crate) fn color_cycle(&mut self, cycle: impl Into<Palette>) {
         let cycle = cycle.into();
@@ -137,7 +226,14 @@ impl DataFrame {
 
         artist.config(config);
 
-        self.artist_items.add(artist)
+        let opt = self.artist_items.add(artist);
+
+        // linked frames see the new extent before this frame is drawn
+        if self.x_shared.is_some() || self.y_shared.is_some() {
+            self.update_data_bounds();
+        }
+
+        opt
     }
 
     pub(super) fn update_pos(&mut self, _renderer: &mut dyn Renderer, pos: &Bounds<Canvas>) {
@@ -162,10 +258,15 @@ impl DataFrame {
     fn update_view(&mut self) {
         let data = &self.data_bounds;
 
-        let (height, width) = (data.height(), data.width());
+        // margins and the single-point expansion are computed in scaled 
+        // coordinates, so a log axis gets a margin in decades
+        let (xmin, xmax) = self.x_scale.limit_range(data.xmin(), data.xmax());
+        let (ymin, ymax) = self.y_scale.limit_range(data.ymin(), data.ymax());
+
+        let (mut xmin, mut xmax) = (self.x_scale.forward(xmin), self.x_scale.forward(xmax));
+        let (mut ymin, mut ymax) = (self.y_scale.forward(ymin), self.y_scale.forward(ymax));
 
-        let (mut xmin, mut xmax) = (data.xmin(), data.xmax());
-        let (mut ymin, mut ymax) = (data.ymin(), data.ymax());
+        let (height, width) = (ymax - ymin, xmax - xmin);
 
         match self.scaling {
             Scaling::Auto => {
@@ -196,11 +297,24 @@ impl DataFrame {
             ymax = ymax + 1.;
         }
 
-        xmin = self.x_min.unwrap_or(xmin);
-        xmax = self.x_max.unwrap_or(xmax);
+        (xmin, xmax) = (self.x_scale.inverse(xmin), self.x_scale.inverse(xmax));
+        (ymin, ymax) = (self.y_scale.inverse(ymin), self.y_scale.inverse(ymax));
+
+        let (x_min, x_max) = match &self.x_shared {
+            Some(shared) => shared.lim(),
+            None => (self.x_min, self.x_max),
+        };
+
+        let (y_min, y_max) = match &self.y_shared {
+            Some(shared) => shared.lim(),
+            None => (self.y_min, self.y_max),
+        };
+
+        xmin = x_min.unwrap_or(xmin);
+        xmax = x_max.unwrap_or(xmax);
 
-        ymin = self.y_min.unwrap_or(ymin);
-        ymax = self.y_max.unwrap_or(ymax);
+        ymin = y_min.unwrap_or(ymin);
+        ymax = y_max.unwrap_or(ymax);
 
         self.view_bounds = Bounds::new(Point(xmin, ymin), Point(xmax, ymax));
         // pos.clone()
@@ -272,13 +386,43 @@ impl DataFrame {
         self.pan_zoom_bounds.unwrap_or(self.view_bounds)
     }
 
+    ///
+    /// True when a linked frame changed the shared range since this frame
+    /// last laid out.
+    ///
+    pub(crate) fn is_shared_stale(&self) -> bool {
+        self.x_shared.as_ref().map_or(false, |s| s.is_stale())
+            || self.y_shared.as_ref().map_or(false, |s| s.is_stale())
+    }
+
     pub(crate) fn get_handlers(&mut self) -> Vec<LegendHandler> {
         self.artist_items.get_handlers()
     }
+
+    pub(crate) fn get_colorbar(&mut self) -> Option<ColorbarHandler> {
+        self.artist_items.get_colorbar()
+    }
     
     fn update_data_bounds(&mut self) {
-        let bounds = self.artist_items.bounds(Bounds::none())
-            .or(Bounds::unit());
+        let own = self.artist_items.bounds(Bounds::none());
+
+        let mut bounds = own.or(Bounds::unit());
+
+        if let Some(shared) = &mut self.x_shared {
+            shared.update(if own.is_none() { None } else { Some((own.xmin(), own.xmax())) });
+
+            if let Some((xmin, xmax)) = shared.range() {
+                bounds = Bounds::new(Point(xmin, bounds.ymin()), Point(xmax, bounds.ymax()));
+            }
+        }
+
+        if let Some(shared) = &mut self.y_shared {
+            shared.update(if own.is_none() { None } else { Some((own.ymin(), own.ymax())) });
+
+            if let Some((ymin, ymax)) = shared.range() {
+                bounds = Bounds::new(Point(bounds.xmin(), ymin), Point(bounds.xmax(), ymax));
+            }
+        }
 
         if bounds != self.data_bounds {
             self.stale_data_bounds = self.stale_data_bounds.update();
@@ -327,6 +471,94 @@ impl fmt::Debug for DataFrame {
     }
 }
 
+///
+/// Data range shared by the x or y axes of linked frames. Each member
+/// publishes its own data extent and every member uses their union, along
+/// with any limits set on one of them. The version counts changes, so a
+/// member can tell when another member's change needs a new layout.
+///
+#[derive(Clone)]
+pub(crate) struct SharedRange {
+    id: usize,
+    inner: Arc<Mutex<SharedRangeInner>>,
+    // version of the range this member last used
+    version: usize,
+}
+
+struct SharedRangeInner {
+    extents: Vec<Option<(f32, f32)>>,
+    lim: (Option<f32>, Option<f32>),
+    version: usize,
+}
+
+impl SharedRange {
+    fn new() -> Self {
+        Self {
+            id: 0,
+            inner: Arc::new(Mutex::new(SharedRangeInner {
+                extents: vec![None],
+                lim: (None, None),
+                version: 0,
+            })),
+            version: 0,
+        }
+    }
+
+    fn join(&self) -> Self {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.extents.push(None);
+
+        Self {
+            id: inner.extents.len() - 1,
+            inner: self.inner.clone(),
+            version: inner.version,
+        }
+    }
+
+    ///
+    /// Publishes this member's extent and marks the range as seen.
+    ///
+    fn update(&mut self, extent: Option<(f32, f32)>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.extents[self.id] != extent {
+            inner.extents[self.id] = extent;
+            inner.version += 1;
+        }
+
+        self.version = inner.version;
+    }
+
+    fn is_stale(&self) -> bool {
+        self.inner.lock().unwrap().version != self.version
+    }
+
+    fn range(&self) -> Option<(f32, f32)> {
+        let inner = self.inner.lock().unwrap();
+
+        inner.extents.iter().flatten().fold(None, |range, (min, max)| {
+            match range {
+                Some((a, b)) => Some((min.min(a), max.max(b))),
+                None => Some((*min, *max)),
+            }
+        })
+    }
+
+    fn set_lim(&self, min: Option<f32>, max: Option<f32>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.lim != (min, max) {
+            inner.lim = (min, max);
+            inner.version += 1;
+        }
+    }
+
+    fn lim(&self) -> (Option<f32>, Option<f32>) {
+        self.inner.lock().unwrap().lim
+    }
+}
+
 pub enum AspectMode {
     BoundingBox,
     View
@@ -345,3 +577,28 @@ pub struct Data;
 
 impl Coord for Data {
 }
+
+#[cfg(test)]
+mod test {
+    use super::SharedRange;
+
+    #[test]
+    fn shared_range_stale() {
+        let mut a = SharedRange::new();
+        let mut b = a.join();
+
+        a.update(Some((0., 1.)));
+        assert!(! a.is_stale());
+        assert!(b.is_stale());
+
+        b.update(Some((-1., 0.5)));
+        assert_eq!(b.range(), Some((-1., 1.)));
+        assert!(a.is_stale());
+
+        a.update(Some((0., 1.)));
+        assert!(! a.is_stale() && ! b.is_stale());
+
+        a.set_lim(Some(0.), None);
+        assert!(b.is_stale());
+    }
+}
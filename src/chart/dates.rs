@@ -0,0 +1,212 @@
+///
+/// UTC calendar date and time, converted from epoch seconds.
+///
+/// Axis values are `f32`, which resolves present-day epoch seconds only
+/// to about two minutes. For finer data, store seconds relative to a
+/// nearby epoch and give that epoch to the date locator and formatter.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateTime {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: f64,
+}
+
+pub const SECONDS_PER_MINUTE: f64 = 60.;
+pub const SECONDS_PER_HOUR: f64 = 3600.;
+pub const SECONDS_PER_DAY: f64 = 86400.;
+
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+];
+
+impl DateTime {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        assert!(1 <= month && month <= 12, "month must be in 1..=12 {}", month);
+        assert!(1 <= day && day <= 31, "day must be in 1..=31 {}", day);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0.,
+        }
+    }
+
+    pub fn hms(mut self, hour: u32, minute: u32, second: f64) -> Self {
+        self.hour = hour;
+        self.minute = minute;
+        self.second = second;
+
+        self
+    }
+
+    pub fn from_epoch(secs: f64) -> Self {
+        let days = (secs / SECONDS_PER_DAY).floor();
+        let rem = secs - days * SECONDS_PER_DAY;
+
+        let (year, month, day) = civil_from_days(days as i64);
+
+        let hour = (rem / SECONDS_PER_HOUR).floor();
+        let rem = rem - hour * SECONDS_PER_HOUR;
+        let minute = (rem / SECONDS_PER_MINUTE).floor();
+        let second = rem - minute * SECONDS_PER_MINUTE;
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (hour as u32).min(23),
+            minute: (minute as u32).min(59),
+            second,
+        }
+    }
+
+    pub fn to_epoch(&self) -> f64 {
+        let days = days_from_civil(self.year, self.month, self.day) as f64;
+
+        days * SECONDS_PER_DAY
+            + self.hour as f64 * SECONDS_PER_HOUR
+            + self.minute as f64 * SECONDS_PER_MINUTE
+            + self.second
+    }
+
+    ///
+    /// Day of the week, with Monday as 0.
+    ///
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday
+        (days_from_civil(self.year, self.month, self.day) + 3).rem_euclid(7) as u32
+    }
+
+    pub fn day_of_year(&self) -> u32 {
+        (days_from_civil(self.year, self.month, self.day)
+            - days_from_civil(self.year, 1, 1) + 1) as u32
+    }
+
+    ///
+    /// Formats the date with strftime-style directives: `%Y %y %m %d %e
+    /// %H %I %M %S %p %b %B %a %A %j %f %%`.
+    ///
+    pub fn strftime(&self, pattern: &str) -> String {
+        let mut out = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                out.push(ch);
+                continue;
+            }
+
+            let sec = self.second.floor() as u32;
+
+            match chars.next() {
+                Some('Y') => out.push_str(&self.year.to_string()),
+                Some('y') => out.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('e') => out.push_str(&format!("{:2}", self.day)),
+                Some('H') => out.push_str(&format!("{:02}", self.hour)),
+                Some('I') => out.push_str(&format!("{:02}", (self.hour + 11) % 12 + 1)),
+                Some('M') => out.push_str(&format!("{:02}", self.minute)),
+                Some('S') => out.push_str(&format!("{:02}", sec)),
+                Some('f') => {
+                    let micros = ((self.second - sec as f64) * 1e6).round() as u32;
+                    out.push_str(&format!("{:06}", micros.min(999_999)));
+                }
+                Some('p') => out.push_str(if self.hour < 12 { "AM" } else { "PM" }),
+                Some('b') => out.push_str(&MONTHS[self.month as usize - 1][..3]),
+                Some('B') => out.push_str(MONTHS[self.month as usize - 1]),
+                Some('a') => out.push_str(&WEEKDAYS[self.weekday() as usize][..3]),
+                Some('A') => out.push_str(WEEKDAYS[self.weekday() as usize]),
+                Some('j') => out.push_str(&format!("{:03}", self.day_of_year())),
+                Some('%') => out.push('%'),
+                Some(other) => { out.push('%'); out.push(other); }
+                None => out.push('%'),
+            }
+        }
+
+        out
+    }
+}
+
+///
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+///
+pub(crate) fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+///
+/// Proleptic Gregorian (year, month, day) for days since 1970-01-01.
+///
+pub(crate) fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    ((if m <= 2 { y + 1 } else { y }) as i32, m as u32, d as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{civil_from_days, days_from_civil, DateTime};
+
+    #[test]
+    fn dates_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+
+        for days in [-800_000, -1, 0, 59, 60, 11016, 11017, 20_000, 800_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn dates_epoch() {
+        let date = DateTime::from_epoch(1_772_368_496.);
+
+        assert_eq!((date.year, date.month, date.day), (2026, 3, 1));
+        assert_eq!((date.hour, date.minute, date.second), (12, 34, 56.));
+        assert_eq!(date.to_epoch(), 1_772_368_496.);
+
+        assert_eq!(DateTime::new(2026, 3, 1).weekday(), 6);
+        assert_eq!(DateTime::new(2024, 12, 31).day_of_year(), 366);
+    }
+
+    #[test]
+    fn dates_strftime() {
+        let date = DateTime::new(2026, 3, 1).hms(15, 4, 5.25);
+
+        assert_eq!(date.strftime("%Y-%m-%d %H:%M:%S"), "2026-03-01 15:04:05");
+        assert_eq!(date.strftime("%b %e, %y"), "Mar  1, 26");
+        assert_eq!(date.strftime("%A %B %I%p %j"), "Sunday March 03PM 060");
+        assert_eq!(date.strftime("%f 100%%"), "250000 100%");
+    }
+}
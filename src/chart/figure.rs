@@ -1,9 +1,14 @@
+use std::{io, panic};
+
 use essay_graphics::layout::{BuildTabs, Page, Page2, PageBuilder, PageBuilder2};
 use essay_graphics::wgpu::{WgpuBackend, WgpuHardcopy};
 
-use essay_graphics::api::renderer::Backend;
+use essay_graphics::api::{
+    renderer::{self, Backend, Canvas, Drawable, Renderer}, Bounds, Point
+};
 
-use crate::chart::Chart;
+use crate::backend::{PageFormat, PdfRenderer, RasterRenderer, SvgRenderer};
+use crate::chart::{Chart, GridSpec};
 use crate::config::ConfigArc;
 
 use super::polar_chart::PolarChart; // , frame::{Layout, LayoutArc}};
@@ -16,15 +21,25 @@ pub struct Figure {
 
     config: ConfigArc,
     page: Option<Page2>,
+
+    // cartesian charts, which take the tight layout flag at layout time
+    charts: Vec<Chart>,
+    is_tight: bool,
 }
 
 impl Figure {
     pub fn new() -> Self {
+        let config = ConfigArc::default();
+        let is_tight = config.get_as_type("figure", "autolayout").unwrap_or(false);
+
         Self {
             backend: Box::new(WgpuBackend::new()),
-            config: ConfigArc::default(),
+            config,
             page: None,
 
+            charts: Vec::new(),
+            is_tight,
+
             size: (6.4, 4.8),
             dpi: 200.,
         }
@@ -34,6 +49,7 @@ impl Figure {
         let chart = Chart::new(&self.config);
 
         self.page = Some(Page2::new(chart.clone()));
+        self.charts = vec![chart.clone()];
 
         chart
     }
@@ -41,6 +57,7 @@ impl Figure {
     pub fn polar(&mut self) -> PolarChart {
         let chart = PolarChart::new(&self.config);
 
+        self.charts.clear();
         self.page = Some(Page2::new(chart.clone()));
 
         chart
@@ -48,10 +65,13 @@ impl Figure {
 
     pub fn multichart<R>(&mut self, f: impl FnOnce(&mut SubFigure) -> R) -> R {
         let mut result = None;
+
+        self.charts.clear();
         
         self.page = Some(Page2::build(|ui| {
             let mut builder = SubFigure {
                 config: &self.config,
+                charts: &mut self.charts,
                 sub_page: ui,
             };
 
@@ -61,9 +81,62 @@ impl Figure {
         result.unwrap()
     }
 
+    ///
+    /// Lays out charts on an `n_rows` by `n_cols` grid, where each chart
+    /// can span several rows and columns.
+    ///
+    pub fn grid_spec<R>(
+        &mut self, 
+        n_rows: usize, 
+        n_cols: usize, 
+        f: impl FnOnce(&mut GridSpec) -> R
+    ) -> R {
+        let mut grid = GridSpec::new(&self.config, n_rows, n_cols);
+
+        let result = (f)(&mut grid);
+
+        self.charts = Vec::from(grid.charts());
+        self.page = Some(Page2::new(grid));
+
+        result
+    }
+
+    ///
+    /// Sizes each chart to fit its measured tick labels, axis labels, title
+    /// and colorbar in its area, instead of the `figure.subplot` margins.
+    /// Applies to the figure's cartesian charts when the figure is shown or
+    /// saved. Defaults to the `figure.autolayout` config.
+    ///
+    pub fn tight_layout(&mut self, is_tight: bool) -> &mut Self {
+        self.is_tight = is_tight;
+
+        self
+    }
+
+    ///
+    /// Passes the figure's layout flags and its canvas bounds to its charts
+    /// before drawing, clearing the bounds of an earlier layout when they
+    /// aren't known yet.
+    ///
+    fn layout(&mut self, figure_pos: Option<Bounds<Canvas>>) {
+        for chart in &mut self.charts {
+            chart.set_tight_layout(self.is_tight);
+            chart.set_figure_pos(figure_pos.clone());
+        }
+    }
+
     pub fn show(self) {
         let mut own = self;
+        // the window size is only known to the backend, so FigurePage
+        // passes it to the charts when drawing
+        own.layout(None);
+
         if let Some(page) = own.page.take() {
+            let page = FigurePage {
+                page,
+                charts: own.charts.clone(),
+            };
+
             own.backend.main_loop(Box::new(page)).unwrap();
         }
     }
@@ -83,10 +156,84 @@ impl Figure {
         self.dpi
     }
 
-    pub fn save(&mut self, path: impl AsRef<std::path::Path>, dpi: f32) {
+    ///
+    /// Saves the figure, choosing the output format from the path's
+    /// extension: `.svg`, `.pdf` and `.eps` are written by the headless
+    /// vector renderers, anything else is rendered to PNG with wgpu, or
+    /// with the CPU rasterizer when no wgpu adapter is available.
+    ///
+    /// Fails when the file can't be written or when the CPU rasterizer
+    /// finds no font for the figure's text.
+    ///
+    pub fn save(&mut self, path: impl AsRef<std::path::Path>, dpi: f32) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let (width, height) = (self.get_width() * dpi, self.get_height() * dpi);
+        self.layout(Some(Bounds::new(Point(0., 0.), Point(width, height))));
+
+        let ext = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        match ext.as_deref() {
+            Some("svg") => self.save_svg(path, dpi),
+            Some("pdf") => self.save_pdf(path, dpi, PageFormat::Pdf),
+            Some("eps") => self.save_pdf(path, dpi, PageFormat::Eps),
+            _ => self.save_wgpu(path, dpi),
+        }
+    }
+
+    fn save_svg(&mut self, path: &std::path::Path, dpi: f32) -> io::Result<()> {
+        let width = self.get_width() * dpi;
+        let height = self.get_height() * dpi;
+        let mut svg = SvgRenderer::new(width, height, dpi / 100.);
+
+        if let Some(page) = &mut self.page {
+            svg.draw(page).map_err(render_err)?;
+        }
+        svg.save(path)
+    }
+
+    fn save_pdf(&mut self, path: &std::path::Path, dpi: f32, format: PageFormat) -> io::Result<()> {
+        let width = self.get_width() * dpi;
+        let height = self.get_height() * dpi;
+        let mut pdf = PdfRenderer::new(format, width, height, dpi);
+
+        if let Some(page) = &mut self.page {
+            pdf.draw(page).map_err(render_err)?;
+        }
+        pdf.save(path)
+    }
+
+    fn save_raster(&mut self, path: &std::path::Path, dpi: f32) -> io::Result<()> {
+        let width = self.get_width() * dpi;
+        let height = self.get_height() * dpi;
+        let mut raster = RasterRenderer::new(width as usize, height as usize, dpi / 100.)?;
+
+        if let Some(page) = &mut self.page {
+            raster.draw(page).map_err(render_err)?;
+        }
+        raster.save(path)
+    }
+
+    fn save_wgpu(&mut self, path: &std::path::Path, dpi: f32) -> io::Result<()> {
         let width = self.get_width() * dpi;
         let height = self.get_height() * dpi;
-        let mut hardcopy = WgpuHardcopy::new(width as u32, height as u32);
+
+        // the wgpu backend panics when it can't find an adapter, e.g. on
+        // GPU-less servers
+        let hardcopy = panic::catch_unwind(|| {
+            WgpuHardcopy::new(width as u32, height as u32)
+        });
+
+        let mut hardcopy = match hardcopy {
+            Ok(hardcopy) => hardcopy,
+            Err(_) => {
+                log::warn!("no wgpu adapter available, saving with the CPU rasterizer");
+
+                return self.save_raster(path, dpi);
+            }
+        };
         hardcopy.scale_factor(dpi / 100.);
 
         let surface = hardcopy.add_surface();
@@ -94,11 +241,39 @@ impl Figure {
             hardcopy.draw(page);
         }
         hardcopy.save(surface, path, dpi as usize);
+
+        Ok(())
+    }
+}
+
+fn render_err(err: impl std::fmt::Debug) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{:?}", err))
+}
+
+///
+/// The figure's page in a window, which passes the window's canvas bounds
+/// to the charts before each draw, e.g. after a resize.
+///
+struct FigurePage {
+    page: Page2,
+    charts: Vec<Chart>,
+}
+
+impl Drawable for FigurePage {
+    fn draw(&mut self, ui: &mut dyn Renderer) -> renderer::Result<()> {
+        let pos = ui.pos();
+
+        for chart in &mut self.charts {
+            chart.set_figure_pos(Some(pos.clone()));
+        }
+
+        self.page.draw(ui)
     }
 }
 
 pub struct SubFigure<'a> {
     config: &'a ConfigArc,
+    charts: &'a mut Vec<Chart>,
     sub_page: &'a mut PageBuilder2,
 }
 
@@ -107,6 +282,33 @@ impl SubFigure<'_> {
         let chart = Chart::new(&self.config);
 
         self.sub_page.view(chart.clone());
+        self.charts.push(chart.clone());
+
+        chart
+    }
+
+    ///
+    /// New chart sharing the x range of `other`. The new chart is expected
+    /// below `other`, so `other`'s x tick labels are hidden.
+    ///
+    pub fn chart_sharing_x(&mut self, other: &Chart) -> Chart {
+        let mut chart = self.chart();
+        chart.share_x(other);
+
+        other.clone().x().tick_labels_visible(false);
+
+        chart
+    }
+
+    ///
+    /// New chart sharing the y range of `other`. The new chart is expected
+    /// to the right of `other`, so its own y tick labels are hidden.
+    ///
+    pub fn chart_sharing_y(&mut self, other: &Chart) -> Chart {
+        let mut chart = self.chart();
+        chart.share_y(other);
+
+        chart.y().tick_labels_visible(false);
 
         chart
     }
@@ -123,6 +325,7 @@ impl SubFigure<'_> {
         self.sub_page.horizontal(|page_builder| {
             let mut sub = SubFigure {
                 config: &self.config,
+                charts: &mut *self.charts,
                 sub_page: page_builder,
             };
 
@@ -134,6 +337,7 @@ impl SubFigure<'_> {
         self.sub_page.vertical(|page_builder| {
             let mut sub = SubFigure {
                 config: &self.config,
+                charts: &mut *self.charts,
                 sub_page: page_builder,
             };
 
@@ -147,6 +351,7 @@ impl SubFigure<'_> {
         self.sub_page.tabs(|ui| {
             let mut tabs = Tabs {
                 config: &self.config,
+                charts: &mut *self.charts,
                 tabs: ui,
             };
 
@@ -159,6 +364,7 @@ impl SubFigure<'_> {
 
 pub struct Tabs<'a> {
     config: &'a ConfigArc,
+    charts: &'a mut Vec<Chart>,
     tabs: &'a mut BuildTabs,
 }
 
@@ -167,6 +373,7 @@ impl<'a> Tabs<'a> {
         self.tabs.tab(String::from(label), |ui| {
             let mut sub = SubFigure {
                 config: &self.config,
+                charts: &mut *self.charts,
                 sub_page: ui,
             };
 
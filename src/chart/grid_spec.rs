@@ -0,0 +1,383 @@
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+
+use essay_graphics::api::{
+    renderer::{Canvas, Drawable, FontId, Renderer, Result},
+    Bounds, FontStyle, Mesh2dColor, Path, PathOpt, Point, TextStyle,
+    path_style::MeshStyle,
+};
+
+use crate::config::ConfigArc;
+
+use super::{Chart, PolarChart};
+
+///
+/// Grid of charts where each chart covers a span of rows and columns,
+/// e.g. a top chart across all columns above a row of smaller charts.
+///
+/// Column widths and row heights follow their ratios. The outer margins
+/// and the `wspace`/`hspace` gaps, as fractions of the average chart
+/// width and height, default to the `figure.subplot.*` config. When the
+/// figure's tight layout is set on its charts, the grid ignores the
+/// margins and gaps, and each chart fits its labels in its cell.
+///
+pub struct GridSpec {
+    config: ConfigArc,
+
+    n_rows: usize,
+    n_cols: usize,
+
+    width_ratios: Option<Vec<f32>>,
+    height_ratios: Option<Vec<f32>>,
+
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    wspace: f32,
+    hspace: f32,
+
+    cells: Vec<GridCell>,
+    charts: Vec<Chart>,
+}
+
+struct GridCell {
+    rows: (usize, usize),
+    cols: (usize, usize),
+    view: Box<dyn Drawable + Send>,
+}
+
+impl GridSpec {
+    pub(crate) fn new(config: &ConfigArc, n_rows: usize, n_cols: usize) -> Self {
+        assert!(n_rows > 0 && n_cols > 0, "grid must have rows and columns ({}, {})", n_rows, n_cols);
+
+        let get = |name: &str, default: f32| {
+            config.get_as_type("figure.subplot", name).unwrap_or(default)
+        };
+
+        Self {
+            config: config.clone(),
+
+            n_rows,
+            n_cols,
+
+            width_ratios: None,
+            height_ratios: None,
+
+            left: get("left", 0.125),
+            right: get("right", 0.9),
+            bottom: get("bottom", 0.11),
+            top: get("top", 0.88),
+            wspace: get("wspace", 0.2),
+            hspace: get("hspace", 0.2),
+
+            cells: Vec::new(),
+            charts: Vec::new(),
+        }
+    }
+
+    pub fn chart(&mut self, rows: impl GridSpan, cols: impl GridSpan) -> Chart {
+        let mut chart = Chart::new(&self.config);
+        chart.clear_margins();
+
+        self.add(rows, cols, Box::new(chart.clone()));
+        self.charts.push(chart.clone());
+
+        chart
+    }
+
+    pub fn polar(&mut self, rows: impl GridSpan, cols: impl GridSpan) -> PolarChart {
+        let mut chart = PolarChart::new(&self.config);
+        chart.clear_margins();
+
+        self.add(rows, cols, Box::new(chart.clone()));
+
+        chart
+    }
+
+    pub fn width_ratios(&mut self, ratios: &[f32]) -> &mut Self {
+        assert_eq!(ratios.len(), self.n_cols, "width_ratios needs one ratio per column");
+
+        self.width_ratios = Some(Vec::from(ratios));
+
+        self
+    }
+
+    pub fn height_ratios(&mut self, ratios: &[f32]) -> &mut Self {
+        assert_eq!(ratios.len(), self.n_rows, "height_ratios needs one ratio per row");
+
+        self.height_ratios = Some(Vec::from(ratios));
+
+        self
+    }
+
+    ///
+    /// Gap between columns as a fraction of the average column width.
+    ///
+    pub fn wspace(&mut self, wspace: f32) -> &mut Self {
+        self.wspace = wspace;
+
+        self
+    }
+
+    ///
+    /// Gap between rows as a fraction of the average row height.
+    ///
+    pub fn hspace(&mut self, hspace: f32) -> &mut Self {
+        self.hspace = hspace;
+
+        self
+    }
+
+    ///
+    /// Outer margins as fractions of the figure, measured from its
+    /// bottom-left corner.
+    ///
+    pub fn margins(&mut self, left: f32, right: f32, bottom: f32, top: f32) -> &mut Self {
+        assert!(left < right && bottom < top, "margins must be increasing");
+
+        self.left = left;
+        self.right = right;
+        self.bottom = bottom;
+        self.top = top;
+
+        self
+    }
+
+    pub(crate) fn charts(&self) -> &[Chart] {
+        &self.charts
+    }
+
+    fn add(&mut self, rows: impl GridSpan, cols: impl GridSpan, view: Box<dyn Drawable + Send>) {
+        let rows = rows.span(self.n_rows);
+        let cols = cols.span(self.n_cols);
+
+        assert!(rows.0 < rows.1 && rows.1 <= self.n_rows, "invalid row span {:?}", rows);
+        assert!(cols.0 < cols.1 && cols.1 <= self.n_cols, "invalid column span {:?}", cols);
+
+        self.cells.push(GridCell {
+            rows,
+            cols,
+            view,
+        });
+    }
+}
+
+impl Drawable for GridSpec {
+    fn draw(&mut self, ui: &mut dyn Renderer) -> Result<()> {
+        let pos = ui.pos();
+
+        // a tight layout fits each chart's labels inside its own cell
+        let is_tight = self.charts.iter_mut().any(|chart| chart.is_tight_layout());
+
+        // the grid covers the figure
+        for chart in &mut self.charts {
+            chart.set_figure_pos(Some(pos.clone()));
+        }
+
+        let (cols, rows) = if is_tight {
+            (
+                tracks(self.n_cols, &self.width_ratios, 0., 0., 1.),
+                tracks(self.n_rows, &self.height_ratios, 0., 0., 1.),
+            )
+        } else {
+            (
+                tracks(self.n_cols, &self.width_ratios, self.wspace, self.left, self.right),
+                // rows are numbered from the top
+                tracks(self.n_rows, &self.height_ratios, self.hspace, 1. - self.top, 1. - self.bottom),
+            )
+        };
+
+        for cell in &mut self.cells {
+            let (x0, x1) = (cols[cell.cols.0].0, cols[cell.cols.1 - 1].1);
+            let (y0, y1) = (rows[cell.rows.0].0, rows[cell.rows.1 - 1].1);
+
+            let cell_pos = Bounds::new(
+                Point(pos.xmin() + x0 * pos.width(), pos.ymax() - y1 * pos.height()),
+                Point(pos.xmin() + x1 * pos.width(), pos.ymax() - y0 * pos.height()),
+            );
+
+            cell.view.draw(&mut CellRenderer { ui, pos: cell_pos })?;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Start and end of each track as fractions between `lo` and `hi`, with
+/// gaps of `space` times the average track size.
+///
+fn tracks(n: usize, ratios: &Option<Vec<f32>>, space: f32, lo: f32, hi: f32) -> Vec<(f32, f32)> {
+    let cell = (hi - lo) / (n as f32 + space * (n as f32 - 1.));
+    let gap = space * cell;
+
+    let ratios = match ratios {
+        Some(ratios) => ratios.clone(),
+        None => vec![1.; n],
+    };
+
+    let sum: f32 = ratios.iter().sum();
+
+    let mut x = lo;
+
+    ratios.iter().map(|ratio| {
+        let size = cell * n as f32 * ratio / sum;
+        let track = (x, x + size);
+
+        x += size + gap;
+
+        track
+    }).collect()
+}
+
+///
+/// Rows or columns covered by a chart: an index, or a range such as
+/// `1..3`, `1..=2`, `1..` or `..`.
+///
+pub trait GridSpan {
+    /// Half-open range of indices in a grid of `n` tracks
+    fn span(&self, n: usize) -> (usize, usize);
+}
+
+impl GridSpan for usize {
+    fn span(&self, _n: usize) -> (usize, usize) {
+        (*self, *self + 1)
+    }
+}
+
+impl GridSpan for Range<usize> {
+    fn span(&self, _n: usize) -> (usize, usize) {
+        (self.start, self.end)
+    }
+}
+
+impl GridSpan for RangeInclusive<usize> {
+    fn span(&self, _n: usize) -> (usize, usize) {
+        (*self.start(), *self.end() + 1)
+    }
+}
+
+impl GridSpan for RangeFrom<usize> {
+    fn span(&self, n: usize) -> (usize, usize) {
+        (self.start, n)
+    }
+}
+
+impl GridSpan for RangeTo<usize> {
+    fn span(&self, _n: usize) -> (usize, usize) {
+        (0, self.end)
+    }
+}
+
+impl GridSpan for RangeFull {
+    fn span(&self, n: usize) -> (usize, usize) {
+        (0, n)
+    }
+}
+
+///
+/// Renderer for a grid cell, reporting the cell as its position.
+///
+struct CellRenderer<'a> {
+    ui: &'a mut dyn Renderer,
+    pos: Bounds<Canvas>,
+}
+
+impl Renderer for CellRenderer<'_> {
+    fn pos(&self) -> Bounds<Canvas> {
+        self.pos.clone()
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.ui.scale_factor()
+    }
+
+    fn to_px(&self, size: f32) -> f32 {
+        self.ui.to_px(size)
+    }
+
+    fn flush(&mut self) {
+        self.ui.flush()
+    }
+
+    fn font(&mut self, style: &FontStyle) -> Result<FontId> {
+        self.ui.font(style)
+    }
+
+    fn draw_path(&mut self, path: &Path<Canvas>, style: &dyn PathOpt) -> Result<()> {
+        self.ui.draw_path(path, style)
+    }
+
+    fn draw_markers(
+        &mut self,
+        path: &Path<Canvas>,
+        style: &dyn PathOpt,
+        markers: &[MeshStyle],
+    ) -> Result<()> {
+        self.ui.draw_markers(path, style, markers)
+    }
+
+    fn draw_text(
+        &mut self,
+        xy: Point,
+        text: &str,
+        angle: f32,
+        style: &dyn PathOpt,
+        text_style: &TextStyle,
+    ) -> Result<()> {
+        self.ui.draw_text(xy, text, angle, style, text_style)
+    }
+
+    fn draw_mesh2d_color(&mut self, mesh: &Mesh2dColor) -> Result<()> {
+        self.ui.draw_mesh2d_color(mesh)
+    }
+
+    fn draw_with_clip(
+        &mut self,
+        pos: Bounds<Canvas>,
+        f: Box<dyn FnOnce(&mut dyn Renderer) -> Result<()> + '_>,
+    ) -> Result<()> {
+        let cell_pos = self.pos.clone();
+
+        self.ui.draw_with_clip(pos, Box::new(move |ui| {
+            (f)(&mut CellRenderer { ui, pos: cell_pos })
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{tracks, GridSpan};
+
+    fn assert_tracks(tracks: Vec<(f32, f32)>, expect: &[(f32, f32)]) {
+        assert_eq!(tracks.len(), expect.len());
+
+        for (a, b) in tracks.iter().zip(expect) {
+            assert!((a.0 - b.0).abs() < 1e-5 && (a.1 - b.1).abs() < 1e-5, "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn grid_spec_tracks() {
+        assert_tracks(tracks(1, &None, 0.2, 0., 1.), &[(0., 1.)]);
+        assert_tracks(tracks(2, &None, 0., 0., 1.), &[(0., 0.5), (0.5, 1.)]);
+
+        // gap is 0.25 of the average track
+        assert_tracks(tracks(2, &None, 0.25, 0., 0.9), &[(0., 0.4), (0.5, 0.9)]);
+
+        assert_tracks(
+            tracks(2, &Some(vec![1., 3.]), 0., 0.1, 0.9),
+            &[(0.1, 0.3), (0.3, 0.9)]
+        );
+    }
+
+    #[test]
+    fn grid_spec_span() {
+        assert_eq!(2.span(3), (2, 3));
+        assert_eq!((1..3).span(3), (1, 3));
+        assert_eq!((0..=1).span(3), (0, 2));
+        assert_eq!((1..).span(3), (1, 3));
+        assert_eq!((..2).span(3), (0, 2));
+        assert_eq!((..).span(3), (0, 3));
+    }
+}
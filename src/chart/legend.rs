@@ -1,25 +1,67 @@
 use essay_graphics::api::{
-    renderer::{Canvas, Renderer, Result}, 
-    Bounds, HorizAlign, Path, PathCode, PathOpt, Point, TextStyle, VertAlign
+    affine2d, path_style::MeshStyle,
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Color, HorizAlign, Path, PathCode, PathOpt, Point, TextStyle, VertAlign
 };
+use essay_tensor::tensor::Tensor;
 
 use crate::{
-    artist::ArtistDraw, 
-    config::{Config, PathStyle, StyleCycle}, transform::ToCanvas,
+    artist::{math_text, ArtistDraw},
+    config::{Config, PathStyle, StyleCycle},
+    frame_option_struct,
+    transform::{ToCanvas, Transform},
 };
 
-use super::data_frame::DataFrame;
+use super::{data_frame::DataFrame, CartesianFrame, Data};
+
+///
+/// Legend position, either inside the data area or outside it on one side.
+/// `Best` picks the inside position covering the fewest data points.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LegendLoc {
+    Best,
+    UpperRight,
+    UpperLeft,
+    LowerLeft,
+    LowerRight,
+    CenterRight,
+    CenterLeft,
+    LowerCenter,
+    UpperCenter,
+    Center,
+    Outside(Side),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
 
 pub struct Legend {
     pos: Bounds<Canvas>,
+    outer: Bounds<Canvas>,
     extent: Bounds<Canvas>,
 
     handlers: Vec<LegendHandler>,
+    points: Vec<Point>,
 
     path_style: PathStyle,
     text_style: TextStyle,
+    title_style: TextStyle,
     style_cycle: StyleCycle,
 
+    loc: LegendLoc,
+    n_cols: usize,
+    title: Option<String>,
+    entries: Option<Vec<String>>,
+    is_frame: bool,
+    is_visible: bool,
+    marker_scale: f32,
+
     glyph_size: f32,
 }
 
@@ -27,21 +69,34 @@ impl Legend {
     pub fn new(cfg: &Config) -> Self {
         let mut legend = Self {
             pos: Bounds::zero(),
+            outer: Bounds::zero(),
             extent: Bounds::zero(),
 
-            path_style: PathStyle::new(),
+            path_style: PathStyle::from_config(cfg, "legend"),
             text_style: TextStyle::new(),
+            title_style: TextStyle::new(),
             style_cycle: StyleCycle::from_config(cfg, "frame.cycle"),
 
             handlers: Vec::new(),
+            points: Vec::new(),
+
+            loc: LegendLoc::UpperLeft,
+            n_cols: 1,
+            title: None,
+            entries: None,
+            is_frame: true,
+            is_visible: true,
+            marker_scale: 1.,
 
             glyph_size: 0.,
         };
 
-        legend.path_style.face_color("white").edge_color("#b0b0b0").line_width(1.);
         legend.text_style.valign(VertAlign::Top);
         legend.text_style.halign(HorizAlign::Left);
 
+        legend.title_style.valign(VertAlign::Top);
+        legend.title_style.halign(HorizAlign::Center);
+
         legend
     }
 
@@ -54,7 +109,27 @@ impl Legend {
         self.handlers = handlers;
     }
 
-    pub(super) fn resize(&mut self, renderer: &mut dyn Renderer, pos: &Bounds<Canvas>) {
+    ///
+    /// Canvas positions of the legend artists' data, avoided by `Best`.
+    ///
+    pub(crate) fn update_points(&mut self, to_canvas: &dyn Transform<Data>) {
+        self.points = self.handlers.iter()
+            .filter_map(|h| h.get_points())
+            .flat_map(|xy| xy.iter_row().map(|p| Point(p[0], p[1])).collect::<Vec<Point>>())
+            .map(|p| to_canvas.transform_point(p))
+            .collect();
+    }
+
+    ///
+    /// Sets the data area `pos` and the chart area `outer`, which holds
+    /// outside legends.
+    ///
+    pub(super) fn resize(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        pos: &Bounds<Canvas>,
+        outer: &Bounds<Canvas>,
+    ) {
         let font_size = match self.text_style.get_size() {
             Some(size) => *size,
             None => 10.,
@@ -62,6 +137,233 @@ impl Legend {
 
         self.glyph_size = renderer.to_px(font_size);
         self.pos = pos.clone();
+        self.outer = outer.clone();
+    }
+
+    ///
+    /// Data area left after reserving room for an outside legend.
+    ///
+    pub(super) fn reserve(&self, pos: &Bounds<Canvas>) -> Bounds<Canvas> {
+        let side = match self.loc {
+            LegendLoc::Outside(side) if self.is_drawn() => side,
+            _ => return pos.clone(),
+        };
+
+        let (w, h) = self.size();
+        let pad = self.border_axes_pad();
+
+        let (mut x0, mut y0, mut x1, mut y1) = (pos.xmin(), pos.ymin(), pos.xmax(), pos.ymax());
+
+        match side {
+            Side::Left => x0 += w + pad,
+            Side::Right => x1 -= w + pad,
+            Side::Bottom => y0 += h + pad,
+            Side::Top => y1 -= h + pad,
+        }
+
+        Bounds::new(Point(x0, y0), Point(x1.max(x0), y1.max(y0)))
+    }
+
+    fn is_drawn(&self) -> bool {
+        self.is_visible && self.order().len() > 0
+    }
+
+    ///
+    /// Indices of the displayed handlers, in display order.
+    ///
+    fn order(&self) -> Vec<usize> {
+        match &self.entries {
+            Some(entries) => entries.iter().filter_map(|label| {
+                self.handlers.iter().position(|h| h.get_label() == label)
+            }).collect(),
+            None => (0..self.handlers.len()).collect(),
+        }
+    }
+
+    #[inline]
+    fn border_pad(&self) -> f32 {
+        0.4 * self.glyph_size
+    }
+
+    #[inline]
+    fn border_axes_pad(&self) -> f32 {
+        0.5 * self.glyph_size
+    }
+
+    #[inline]
+    fn handle_length(&self) -> f32 {
+        2. * self.glyph_size
+    }
+
+    #[inline]
+    fn handle_pad(&self) -> f32 {
+        0.8 * self.glyph_size
+    }
+
+    #[inline]
+    fn col_spacing(&self) -> f32 {
+        2. * self.glyph_size
+    }
+
+    #[inline]
+    fn label_spacing(&self) -> f32 {
+        0.5 * self.glyph_size
+    }
+
+    fn text_width(&self, text: &str) -> f32 {
+        self.glyph_size * math_text::text_width(text)
+    }
+
+    fn n_rows(&self, n: usize) -> usize {
+        (n + self.n_cols - 1) / self.n_cols
+    }
+
+    ///
+    /// Label width of each column, with entries filling columns first.
+    ///
+    fn col_widths(&self, order: &[usize]) -> Vec<f32> {
+        let rows = self.n_rows(order.len()).max(1);
+
+        order.chunks(rows).map(|col| {
+            col.iter()
+                .map(|i| self.text_width(self.handlers[*i].get_label()))
+                .fold(0., f32::max)
+        }).collect()
+    }
+
+    fn title_height(&self) -> f32 {
+        match &self.title {
+            Some(_) => self.glyph_size + self.label_spacing(),
+            None => 0.,
+        }
+    }
+
+    ///
+    /// Width and height of the legend box.
+    ///
+    fn size(&self) -> (f32, f32) {
+        let order = self.order();
+        let rows = self.n_rows(order.len());
+        let cols = self.col_widths(&order);
+
+        let entry = self.handle_length() + self.handle_pad();
+
+        let mut w: f32 = cols.iter().map(|c| entry + c).sum::<f32>()
+            + self.col_spacing() * (cols.len().max(1) - 1) as f32;
+
+        if let Some(title) = &self.title {
+            w = w.max(self.text_width(title));
+        }
+
+        let h = rows as f32 * self.glyph_size
+            + rows.max(1).saturating_sub(1) as f32 * self.label_spacing()
+            + self.title_height();
+
+        (w + 2. * self.border_pad(), h + 2. * self.border_pad())
+    }
+
+    ///
+    /// Upper left corner of the legend box.
+    ///
+    fn origin(&self, size: (f32, f32)) -> Point {
+        let pad = self.border_axes_pad();
+
+        match self.loc {
+            LegendLoc::Best => best_origin(&self.pos, size, pad, &self.points),
+            LegendLoc::Outside(side) => outside_origin(&self.pos, &self.outer, side, size, pad),
+            loc => inside_origin(&self.pos, loc, size, pad),
+        }
+    }
+}
+
+///
+/// Upper left corner for a legend of `size` inside `pos` at `loc`, inset by
+/// `pad`.
+///
+fn inside_origin(pos: &Bounds<Canvas>, loc: LegendLoc, size: (f32, f32), pad: f32) -> Point {
+    let (w, h) = size;
+
+    let left = pos.xmin() + pad;
+    let right = pos.xmax() - pad - w;
+    let center_x = pos.xmid() - 0.5 * w;
+
+    let top = pos.ymax() - pad;
+    let bottom = pos.ymin() + pad + h;
+    let center_y = pos.ymid() + 0.5 * h;
+
+    let (x, y) = match loc {
+        LegendLoc::UpperRight => (right, top),
+        LegendLoc::UpperLeft => (left, top),
+        LegendLoc::LowerLeft => (left, bottom),
+        LegendLoc::LowerRight => (right, bottom),
+        LegendLoc::CenterRight => (right, center_y),
+        LegendLoc::CenterLeft => (left, center_y),
+        LegendLoc::LowerCenter => (center_x, bottom),
+        LegendLoc::UpperCenter => (center_x, top),
+        LegendLoc::Center => (center_x, center_y),
+        LegendLoc::Best | LegendLoc::Outside(_) => panic!("{:?} is not an inside location", loc),
+    };
+
+    Point(x, y)
+}
+
+///
+/// Inside position whose box covers the fewest points, preferring the
+/// earlier locations on ties.
+///
+fn best_origin(pos: &Bounds<Canvas>, size: (f32, f32), pad: f32, points: &[Point]) -> Point {
+    let candidates = [
+        LegendLoc::UpperRight,
+        LegendLoc::UpperLeft,
+        LegendLoc::LowerLeft,
+        LegendLoc::LowerRight,
+        LegendLoc::CenterRight,
+        LegendLoc::CenterLeft,
+        LegendLoc::LowerCenter,
+        LegendLoc::UpperCenter,
+        LegendLoc::Center,
+    ];
+
+    let (w, h) = size;
+
+    let mut best = inside_origin(pos, candidates[0], size, pad);
+    let mut best_count = usize::MAX;
+
+    for loc in candidates {
+        let origin = inside_origin(pos, loc, size, pad);
+
+        let count = points.iter().filter(|p| {
+            origin.0 <= p.0 && p.0 <= origin.0 + w
+            && origin.1 - h <= p.1 && p.1 <= origin.1
+        }).count();
+
+        if count < best_count {
+            best = origin;
+            best_count = count;
+        }
+    }
+
+    best
+}
+
+///
+/// Upper left corner for a legend against the `outer` edge on `side`,
+/// centered along the data area.
+///
+fn outside_origin(
+    pos: &Bounds<Canvas>,
+    outer: &Bounds<Canvas>,
+    side: Side,
+    size: (f32, f32),
+    pad: f32
+) -> Point {
+    let (w, h) = size;
+
+    match side {
+        Side::Left => Point(outer.xmin() + pad, pos.ymid() + 0.5 * h),
+        Side::Right => Point(outer.xmax() - pad - w, pos.ymid() + 0.5 * h),
+        Side::Top => Point(pos.xmid() - 0.5 * w, outer.ymax() - pad),
+        Side::Bottom => Point(pos.xmid() - 0.5 * w, outer.ymin() + pad + h),
     }
 }
 
@@ -71,70 +373,86 @@ impl ArtistDraw<Canvas> for Legend {
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         renderer: &mut dyn Renderer,
         _to_canvas: &ToCanvas<Canvas>,
         style: &dyn PathOpt,
     ) -> Result<()> {
-        if self.handlers.len() == 0 {
+        if ! self.is_drawn() {
             return Ok(());
         }
 
-        let n_chars = match self.handlers.iter().map(|h| h.get_label().len()).max() {
-            Some(n_chars) => n_chars,
-            None => 0,
-        };
+        let order = self.order();
+
+        let (w, h) = self.size();
+        let Point(x0, y0) = self.origin((w, h));
+
+        self.extent = Bounds::new(Point(x0, y0 - h), Point(x0 + w, y0));
+
+        if self.is_frame {
+            let path = Path::<Canvas>::new(vec![
+                PathCode::MoveTo(Point(x0, y0)),
+                PathCode::LineTo(Point(x0, y0 - h)),
+                PathCode::LineTo(Point(x0 + w, y0 - h)),
+                PathCode::ClosePoly(Point(x0 + w, y0)),
+            ]);
+
+            renderer.draw_path(&path, &self.path_style)?;
+        }
 
         let dh = self.glyph_size;
-        // TODO: functions for label width
-        let label_width = self.glyph_size * 0.5 * n_chars as f32;
+        let pad = self.border_pad();
+        let mut y_top = y0 - pad;
+
+        if let Some(title) = &self.title {
+            math_text::draw_text(
+                renderer,
+                Point(x0 + 0.5 * w, y_top),
+                title,
+                0.,
+                style,
+                &self.title_style,
+            )?;
 
-        let pos = &self.pos;
-        let rect_width = 80.;
-        //let rect_height = dh;
-        let pad_x = 15.;
-        let pad_y = 5.;
-        let margin = 20.;
+            y_top -= self.title_height();
+        }
 
-        let w = label_width + pad_x + rect_width + 2. * margin;
-        let h = dh * self.handlers.len() as f32 + 2. * margin;
+        let rows = self.n_rows(order.len());
+        let col_widths = self.col_widths(&order);
+        let n = self.handlers.len();
 
-        let x0 = pos.x0() + margin;
-        let y0 = pos.y0() - margin;
+        let mut x_col = x0 + pad;
 
-        let path = Path::<Canvas>::new(vec![
-            PathCode::MoveTo(Point(x0, y0)),
-            PathCode::LineTo(Point(x0, y0 - h)),
-            PathCode::LineTo(Point(x0 + w, y0 - h)),
-            PathCode::ClosePoly(Point(x0 + w, y0)),
-        ]);
+        for (c, col) in order.chunks(rows).enumerate() {
+            for (r, i) in col.iter().enumerate() {
+                let handler = &self.handlers[*i];
 
-        renderer.draw_path(&path, &self.path_style)?;
+                let y = y_top - r as f32 * (dh + self.label_spacing());
 
-        let x_symbol = x0 + margin;
-        let x_t = x_symbol + rect_width + pad_x;
+                let x_t = x_col + self.handle_length() + self.handle_pad();
 
-        let n = self.handlers.len();
+                math_text::draw_text(
+                    renderer,
+                    Point(x_t, y),
+                    handler.get_label(),
+                    0.,
+                    style,
+                    &self.text_style,
+                )?;
 
-        for (i, handler) in self.handlers.iter().enumerate() {
-            let y = y0 - i as f32 * (dh + pad_y) - margin;
-            renderer.draw_text(
-                Point(x_t, y),
-                handler.get_label(),
-                0.,
-                style,
-                &self.text_style,
-            )?;
+                let rect = Bounds::<Canvas>::new(
+                    Point(x_col, y - dh),
+                    Point(x_col + self.handle_length(), y)
+                );
 
-            let rect = Bounds::<Canvas>::new(
-                Point(x_symbol, y), 
-                Point(x_symbol + rect_width, y - dh)
-             );
+                // colors follow the artists' order, not the display order
+                let entry_style = self.style_cycle.push(style, *i, n);
 
-            let style = self.style_cycle.push(style, i, n);
-             //let color = self.cycle.
-             
-             handler.draw(renderer, &style, &rect)?;
+                handler.draw(renderer, &entry_style, &rect)?;
+                handler.draw_marker(renderer, &entry_style, &rect, self.marker_scale)?;
+            }
+
+            x_col += self.handle_length() + self.handle_pad() + col_widths[c] + self.col_spacing();
         }
 
         Ok(())
@@ -144,6 +462,8 @@ impl ArtistDraw<Canvas> for Legend {
 pub struct LegendHandler {
     label: String,
     draw: Box<dyn Fn(&mut dyn Renderer, &dyn PathOpt, &Bounds<Canvas>) -> Result<()> + Send>,
+    marker: Option<(Path<Canvas>, PathStyle)>,
+    points: Option<Tensor>,
 }
 
 impl LegendHandler {
@@ -154,19 +474,191 @@ impl LegendHandler {
         Self {
             label,
             draw: Box::new(draw),
+            marker: None,
+            points: None,
         }
     }
 
+    ///
+    /// Marker drawn at the center of the legend symbol, scaled by the
+    /// legend's marker scale.
+    ///
+    pub fn marker(mut self, path: Path<Canvas>, style: PathStyle) -> Self {
+        self.marker = Some((path, style));
+
+        self
+    }
+
+    ///
+    /// Data points of the artist, which a `Best` legend avoids covering.
+    ///
+    pub fn points(mut self, xy: Tensor) -> Self {
+        assert!(xy.rank() == 2 && xy.cols() == 2, "legend points must be [x, y] rows");
+
+        self.points = Some(xy);
+
+        self
+    }
+
     pub fn get_label(&self) -> &String {
         &self.label
     }
 
+    pub fn get_points(&self) -> Option<&Tensor> {
+        self.points.as_ref()
+    }
+
     pub fn draw(
-        &self, 
-        renderer: &mut dyn Renderer, 
+        &self,
+        renderer: &mut dyn Renderer,
         style: &dyn PathOpt,
         rect: &Bounds<Canvas>
     ) -> Result<()> {
         (self.draw)(renderer, style, rect)
     }
-}
\ No newline at end of file
+
+    fn draw_marker(
+        &self,
+        renderer: &mut dyn Renderer,
+        style: &dyn PathOpt,
+        rect: &Bounds<Canvas>,
+        scale: f32,
+    ) -> Result<()> {
+        if let Some((path, marker_style)) = &self.marker {
+            let path = path.scale(scale, scale);
+            let style = marker_style.push(style);
+
+            let color = match style.get_face_color() {
+                Some(color) if ! color.is_none() => color,
+                _ => style.get_edge_color().unwrap_or(Color::black()),
+            };
+
+            renderer.draw_markers(&path, &style, &[MeshStyle {
+                color,
+                affine: affine2d::translate(rect.xmid(), rect.ymid()),
+            }])?;
+        }
+
+        Ok(())
+    }
+}
+
+frame_option_struct!(LegendOpt, Legend, CartesianFrame, get_legend_mut);
+
+impl LegendOpt {
+    pub fn loc(&mut self, loc: LegendLoc) -> &mut Self {
+        self.write(|legend| { legend.loc = loc; });
+        self
+    }
+
+    ///
+    /// Number of columns, filled top to bottom.
+    ///
+    pub fn n_cols(&mut self, n_cols: usize) -> &mut Self {
+        assert!(n_cols > 0, "legend n_cols must be positive");
+
+        self.write(|legend| { legend.n_cols = n_cols; });
+        self
+    }
+
+    pub fn title(&mut self, title: &str) -> &mut Self {
+        let title = title.to_string();
+
+        self.write(|legend| { legend.title = Some(title); });
+        self
+    }
+
+    ///
+    /// Draws the legend's background and border.
+    ///
+    pub fn frame(&mut self, is_frame: bool) -> &mut Self {
+        self.write(|legend| { legend.is_frame = is_frame; });
+        self
+    }
+
+    pub fn visible(&mut self, is_visible: bool) -> &mut Self {
+        self.write(|legend| { legend.is_visible = is_visible; });
+        self
+    }
+
+    pub fn font_size(&mut self, size: f32) -> &mut Self {
+        self.write(|legend| {
+            legend.text_style.size(size);
+            legend.title_style.size(size);
+        });
+        self
+    }
+
+    ///
+    /// Marker size in the legend relative to the plotted markers.
+    ///
+    pub fn marker_scale(&mut self, scale: f32) -> &mut Self {
+        assert!(scale > 0., "legend marker_scale must be positive");
+
+        self.write(|legend| { legend.marker_scale = scale; });
+        self
+    }
+
+    ///
+    /// Labels of the artists to show, in display order. Unknown labels are
+    /// skipped.
+    ///
+    pub fn entries(&mut self, labels: &[&str]) -> &mut Self {
+        let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+
+        self.write(|legend| { legend.entries = Some(labels); });
+        self
+    }
+
+    pub fn face_color(&mut self, color: impl Into<Color>) -> &mut Self {
+        let color = color.into();
+
+        self.write(|legend| { legend.path_style.face_color(color); });
+        self
+    }
+
+    pub fn edge_color(&mut self, color: impl Into<Color>) -> &mut Self {
+        let color = color.into();
+
+        self.write(|legend| { legend.path_style.edge_color(color); });
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{renderer::Canvas, Bounds, Point};
+
+    use super::{best_origin, inside_origin, outside_origin, LegendLoc, Side};
+
+    #[test]
+    fn legend_inside_origin() {
+        let pos = Bounds::<Canvas>::new(Point(0., 0.), Point(100., 50.));
+
+        assert_eq!(inside_origin(&pos, LegendLoc::UpperLeft, (20., 10.), 2.), Point(2., 48.));
+        assert_eq!(inside_origin(&pos, LegendLoc::UpperRight, (20., 10.), 2.), Point(78., 48.));
+        assert_eq!(inside_origin(&pos, LegendLoc::LowerLeft, (20., 10.), 2.), Point(2., 12.));
+        assert_eq!(inside_origin(&pos, LegendLoc::Center, (20., 10.), 2.), Point(40., 30.));
+    }
+
+    #[test]
+    fn legend_best_origin() {
+        let pos = Bounds::<Canvas>::new(Point(0., 0.), Point(100., 50.));
+
+        // no points prefers upper right
+        assert_eq!(best_origin(&pos, (20., 10.), 2., &[]), Point(78., 48.));
+
+        // points in the upper right move it to the upper left
+        let points = [Point(90., 45.), Point(85., 40.)];
+        assert_eq!(best_origin(&pos, (20., 10.), 2., &points), Point(2., 48.));
+    }
+
+    #[test]
+    fn legend_outside_origin() {
+        let pos = Bounds::<Canvas>::new(Point(10., 10.), Point(90., 50.));
+        let outer = Bounds::<Canvas>::new(Point(0., 0.), Point(120., 60.));
+
+        assert_eq!(outside_origin(&pos, &outer, Side::Right, (20., 10.), 2.), Point(98., 35.));
+        assert_eq!(outside_origin(&pos, &outer, Side::Top, (20., 10.), 2.), Point(40., 58.));
+    }
+}
@@ -1,26 +1,45 @@
 mod cartesian_axis;
+mod category;
 mod polar_chart;
 mod polar_frame;
 mod axis;
 mod polar_axis;
 mod data_frame;
 mod figure;
+mod grid_spec;
 mod cartesian_frame;
 mod chart;
+mod dates;
 mod legend;
 mod tick_formatter;
 mod tick_locator;
+mod scale;
 mod style;
 
 pub use axis::{AxisOpt, ShowGrid};
 
+pub use category::{AxisData, IntoAxisData};
+
 pub use chart::{Chart, IntoArtist};
 
 pub use figure::{Figure, SubFigure};
 
+pub use grid_spec::{GridSpan, GridSpec};
+
 pub use style::PlotOpt;
 
-pub use tick_locator::IndexLocator;
+pub use tick_locator::{
+    AutoDateLocator, AutoMinorLocator, DateUnit, IndexLocator, LogLocator, LogitLocator,
+    MaxNLocator, MultipleLocator, SymLogLocator, TickLocator,
+};
+
+pub use tick_formatter::{
+    DateFormatter, FixedFormatter, Formatter, FuncFormatter, ScalarFormatter, TickFormatter,
+};
+
+pub use dates::DateTime;
+
+pub use scale::Scale;
 
 pub use polar_axis::PolarAxisOpt;
 
@@ -36,5 +55,5 @@ pub use cartesian_frame::{
 pub use cartesian_frame::CartesianFrame;
 
 pub use legend::{
-    Legend, LegendHandler,
+    Legend, LegendHandler, LegendLoc, LegendOpt, Side,
 };
@@ -6,7 +6,7 @@ use essay_graphics::api::{
 };
 
 use crate::{
-    artist::paths,
+    artist::{math_text, paths},
     config::Config, 
     frame_option_struct, path_style_options, transform::AngleCoord,
 };
@@ -149,7 +149,7 @@ impl XTick {
             text_style.halign(self.halign);
             text_style.valign(self.valign);
 
-            ui.draw_text(self.pos, label, 0., style, &text_style)?;
+            math_text::draw_text(ui, self.pos, label, 0., style, &text_style)?;
         }
 
         Ok(())
@@ -311,7 +311,7 @@ impl YTick {
             text_style.halign(self.halign);
             text_style.valign(self.valign);
 
-            ui.draw_text(self.pos, label, 0., style, &text_style)?;
+            math_text::draw_text(ui, self.pos, label, 0., style, &text_style)?;
         }
 
         Ok(())
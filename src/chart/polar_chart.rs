@@ -62,6 +62,12 @@ impl PolarChart {
         opt
     }
 
+    pub(crate) fn clear_margins(&mut self) {
+        self.view.write(|f| {
+            f.clear_margins();
+        });
+    }
+
     pub fn scaling(&mut self, scaling: Scaling) -> &mut Self {
         self.view.write(|f| { 
             f.data_mut().scaling(scaling); 
@@ -131,6 +131,10 @@ impl PolarFrame {
         self.data.color_cycle(cycle);
     }
 
+    pub(crate) fn clear_margins(&mut self) {
+        self.margins = FrameMargins::none();
+    }
+
     fn resize(&mut self, renderer: &mut dyn Renderer) {
         let pos = renderer.pos();
 
@@ -166,11 +170,8 @@ impl PolarFrame {
     
         let pos_data = self.data.pos();
     
-        let pos_legend = Bounds::<Canvas>::new(
-            Point(pos_data.xmin(), pos_data.ymax()),
-            Point(pos_data.xmin(), pos_data.ymax()),
-        );
-        self.legend.resize(renderer, &pos_legend);
+        let pos_outer = renderer.pos();
+        self.legend.resize(renderer, &pos_data, &pos_outer);
     
         // TODO:
         self.x_axis.resize(&self.data, self.angle_coord);
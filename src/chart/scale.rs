@@ -0,0 +1,155 @@
+///
+/// Data-to-axis scale, applied before the linear mapping to canvas.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scale {
+    Linear,
+    Log10,
+    SymLog { linthresh: f32 },
+    Logit,
+}
+
+impl Scale {
+    const LOGIT_EPSILON : f32 = 1e-7;
+
+    #[inline]
+    pub fn forward(&self, value: f32) -> f32 {
+        match self {
+            Scale::Linear => value,
+            Scale::Log10 => value.max(f32::MIN_POSITIVE).log10(),
+            Scale::SymLog { linthresh } => {
+                let abs = value.abs();
+
+                if abs <= *linthresh {
+                    value / linthresh
+                } else {
+                    (1. + (abs / linthresh).log10()).copysign(value)
+                }
+            }
+            Scale::Logit => {
+                let p = value.clamp(Self::LOGIT_EPSILON, 1. - Self::LOGIT_EPSILON);
+
+                (p / (1. - p)).log10()
+            }
+        }
+    }
+
+    #[inline]
+    pub fn inverse(&self, value: f32) -> f32 {
+        match self {
+            Scale::Linear => value,
+            Scale::Log10 => 10.0f32.powf(value),
+            Scale::SymLog { linthresh } => {
+                let abs = value.abs();
+
+                if abs <= 1. {
+                    value * linthresh
+                } else {
+                    (linthresh * 10.0f32.powf(abs - 1.)).copysign(value)
+                }
+            }
+            Scale::Logit => {
+                let r = 10.0f32.powf(value);
+
+                r / (1. + r)
+            }
+        }
+    }
+
+    ///
+    /// Clamps a data range to the scale's valid domain, e.g. positive values
+    /// for log scales.
+    ///
+    pub fn limit_range(&self, min: f32, max: f32) -> (f32, f32) {
+        match self {
+            Scale::Linear => (min, max),
+            Scale::SymLog { .. } => (min, max),
+            Scale::Log10 => {
+                if max <= 0. {
+                    (1., 10.)
+                } else if min <= 0. {
+                    (max * 1e-3, max)
+                } else {
+                    (min, max)
+                }
+            }
+            Scale::Logit => {
+                let min = if 0. < min && min < 1. { min } else { 1e-3 };
+                let max = if 0. < max && max < 1. { max } else { 1. - 1e-3 };
+
+                if min < max {
+                    (min, max)
+                } else {
+                    (1e-3, 1. - 1e-3)
+                }
+            }
+        }
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Scale::Linear
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Scale;
+
+    fn assert_round_trip(scale: Scale, values: &[f32]) {
+        for value in values {
+            let v = scale.inverse(scale.forward(*value));
+
+            assert!((v - value).abs() <= 1e-4 * value.abs().max(1.),
+                "{:?} round trip {} -> {}", scale, value, v);
+        }
+    }
+
+    #[test]
+    fn scale_log10() {
+        let scale = Scale::Log10;
+
+        assert_eq!(scale.forward(1.), 0.);
+        assert_eq!(scale.forward(100.), 2.);
+        assert!((scale.forward(0.01) + 2.).abs() < 1e-6);
+        assert!(scale.forward(0.).is_finite());
+
+        assert_round_trip(scale, &[1e-3, 0.5, 1., 7., 1e4]);
+    }
+
+    #[test]
+    fn scale_symlog() {
+        let scale = Scale::SymLog { linthresh: 1. };
+
+        assert_eq!(scale.forward(0.), 0.);
+        assert_eq!(scale.forward(0.5), 0.5);
+        assert_eq!(scale.forward(1.), 1.);
+        assert_eq!(scale.forward(10.), 2.);
+        assert_eq!(scale.forward(-100.), -3.);
+
+        assert_round_trip(scale, &[-1e3, -2., -0.5, 0., 0.25, 1., 30.]);
+    }
+
+    #[test]
+    fn scale_logit() {
+        let scale = Scale::Logit;
+
+        assert_eq!(scale.forward(0.5), 0.);
+        assert!((scale.forward(0.9) - 9.0f32.log10()).abs() < 1e-5);
+        assert!((scale.forward(0.1) + 9.0f32.log10()).abs() < 1e-5);
+        assert!(scale.forward(0.).is_finite());
+        assert!(scale.forward(1.).is_finite());
+
+        assert_round_trip(scale, &[0.001, 0.1, 0.5, 0.9, 0.999]);
+    }
+
+    #[test]
+    fn scale_limit_range() {
+        let (min, max) = Scale::Log10.limit_range(-1., 100.);
+        assert!((min - 0.1).abs() < 1e-6 && max == 100.);
+        assert_eq!(Scale::Log10.limit_range(-1., -0.5), (1., 10.));
+        assert_eq!(Scale::Logit.limit_range(0., 1.), (1e-3, 1. - 1e-3));
+        assert_eq!(Scale::Linear.limit_range(-1., 1.), (-1., 1.));
+    }
+}
@@ -1,9 +1,36 @@
+use super::{
+    dates::{DateTime, SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE},
+    tick_locator::DateUnit,
+};
+
 pub trait TickFormatter : Send {
     fn format(&self, value: f32, delta: f32) -> String;
+
+    ///
+    /// Called with the visible tick values before they're formatted, so
+    /// formatters can choose a shared offset or exponent.
+    ///
+    fn set_locs(&mut self, _locs: &[f32]) {
+    }
+
+    ///
+    /// Text drawn once at the end of the axis, e.g. "×10⁶" or "+1000".
+    ///
+    fn offset_text(&self) -> Option<String> {
+        None
+    }
 }
 
 pub enum Formatter {
     Plain,
+    Log10,
+    Logit,
+    /// Percent of `xmax`, e.g. "50%" for 0.5 with `xmax` 1
+    Percent { xmax: f32 },
+    /// SI prefix and unit, e.g. "1.5 kHz" or "20 µs"
+    Engineering { unit: String },
+    /// Python-style format string, e.g. "{x:.2f} m", "{x:.0%}" or "{x:g}"
+    StrMethod(String),
 }
 
 impl TickFormatter for Formatter {
@@ -12,10 +39,506 @@ impl TickFormatter for Formatter {
             Formatter::Plain => {
                 format_tick(value, delta)
             }
+            Formatter::Log10 => {
+                format_log10(value)
+            }
+            Formatter::Logit => {
+                format_logit(value)
+            }
+            Formatter::Percent { xmax } => {
+                let scale = 100. / xmax;
+
+                format!("{}%", format_tick(value * scale, (delta * scale).abs()))
+            }
+            Formatter::Engineering { unit } => {
+                format_engineering(value, delta, unit)
+            }
+            Formatter::StrMethod(pattern) => {
+                format_str_method(pattern, value)
+            }
+        }
+    }
+}
+
+///
+/// Default linear formatter. When the ticks are large relative to their
+/// spacing, labels are shown relative to an offset, and when they're very
+/// large or small, scaled by a power of ten. Both are reported in the
+/// offset text at the end of the axis.
+///
+pub struct ScalarFormatter {
+    is_offset: bool,
+    sci_limits: (i32, i32),
+
+    offset: f32,
+    order: i32,
+}
+
+impl ScalarFormatter {
+    pub fn new() -> Self {
+        Self {
+            is_offset: true,
+            sci_limits: (-5, 6),
+            offset: 0.,
+            order: 0,
+        }
+    }
+
+    pub fn use_offset(mut self, is_offset: bool) -> Self {
+        self.is_offset = is_offset;
+
+        self
+    }
+
+    ///
+    /// Range of decade exponents shown without scientific notation.
+    ///
+    pub fn sci_limits(mut self, min: i32, max: i32) -> Self {
+        assert!(min < max, "sci_limits min must be less than max ({}, {})", min, max);
+        self.sci_limits = (min, max);
+
+        self
+    }
+
+    fn compute_offset(&self, min: f32, max: f32) -> f32 {
+        let span = max - min;
+
+        // only offset when the ticks have at least four shared digits
+        if ! self.is_offset || span <= 0. || min < 0. && max > 0. {
+            return 0.;
+        }
+
+        let abs_max = min.abs().max(max.abs());
+
+        if abs_max < 1e4 * span {
+            return 0.;
+        }
+
+        let decade = 10f32.powi(span.log10().ceil() as i32);
+
+        if max <= 0. {
+            - (- max / decade).floor() * decade
+        } else {
+            (min / decade).floor() * decade
         }
     }
 }
 
+impl TickFormatter for ScalarFormatter {
+    fn format(&self, value: f32, delta: f32) -> String {
+        let scale = 10f32.powi(self.order);
+
+        format_tick((value - self.offset) / scale, delta / scale)
+    }
+
+    fn set_locs(&mut self, locs: &[f32]) {
+        self.offset = 0.;
+        self.order = 0;
+
+        if locs.is_empty() {
+            return;
+        }
+
+        let min = locs.iter().fold(f32::MAX, |a, b| a.min(*b));
+        let max = locs.iter().fold(f32::MIN, |a, b| a.max(*b));
+
+        self.offset = self.compute_offset(min, max);
+
+        let abs_max = (min - self.offset).abs().max((max - self.offset).abs());
+
+        if abs_max > 0. {
+            let oom = abs_max.log10().floor() as i32;
+
+            if oom <= self.sci_limits.0 || self.sci_limits.1 <= oom {
+                self.order = oom;
+            }
+        }
+    }
+
+    fn offset_text(&self) -> Option<String> {
+        let sci = if self.order != 0 {
+            format!("×10{}", superscript(self.order))
+        } else {
+            String::new()
+        };
+
+        let offset = if self.offset != 0. {
+            let sign = if self.offset < 0. { "-" } else { "+" };
+
+            format!("{}{}", sign, format_sci(self.offset.abs(), self.sci_limits))
+        } else {
+            String::new()
+        };
+
+        if sci.is_empty() && offset.is_empty() {
+            None
+        } else {
+            Some(format!("{}{}", sci, offset))
+        }
+    }
+}
+
+///
+/// Labels from a closure of the tick value and the tick spacing.
+///
+pub struct FuncFormatter {
+    func: Box<dyn Fn(f32, f32) -> String + Send>,
+}
+
+impl FuncFormatter {
+    pub fn new(func: impl Fn(f32, f32) -> String + Send + 'static) -> Self {
+        Self {
+            func: Box::new(func),
+        }
+    }
+}
+
+impl TickFormatter for FuncFormatter {
+    fn format(&self, value: f32, delta: f32) -> String {
+        (self.func)(value, delta)
+    }
+}
+
+///
+/// Fixed labels for categorical axes: the tick at `i` is labeled with
+/// `labels[i]`. Ticks between categories or past the end are unlabeled.
+///
+pub struct FixedFormatter {
+    labels: Vec<String>,
+}
+
+impl FixedFormatter {
+    pub fn new(labels: &[&str]) -> Self {
+        Self {
+            labels: labels.iter().map(|l| String::from(*l)).collect(),
+        }
+    }
+}
+
+impl TickFormatter for FixedFormatter {
+    fn format(&self, value: f32, _delta: f32) -> String {
+        let index = value.round();
+
+        if (value - index).abs() > 1e-3 || index < 0. {
+            return String::new();
+        }
+
+        match self.labels.get(index as usize) {
+            Some(label) => label.clone(),
+            None => String::new(),
+        }
+    }
+}
+
+///
+/// Labels for axes in epoch seconds, either with a strftime-style pattern
+/// or with concise labels that only show the changing calendar field and
+/// switch to the next larger field at its boundary, e.g. "Mar" after
+/// "2026".
+///
+pub struct DateFormatter {
+    pattern: Option<String>,
+    epoch: f64,
+}
+
+impl DateFormatter {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: Some(String::from(pattern)),
+            epoch: 0.,
+        }
+    }
+
+    pub fn concise() -> Self {
+        Self {
+            pattern: None,
+            epoch: 0.,
+        }
+    }
+
+    ///
+    /// Epoch of the axis values in seconds since 1970-01-01 UTC.
+    ///
+    pub fn epoch(mut self, epoch: f64) -> Self {
+        self.epoch = epoch;
+
+        self
+    }
+
+    fn concise_pattern(date: &DateTime, unit: DateUnit) -> &'static str {
+        let is_midnight = date.hour == 0 && date.minute == 0;
+
+        let year_or_month = if date.month == 1 { "%Y" } else { "%b" };
+
+        match unit {
+            DateUnit::Year => "%Y",
+            DateUnit::Month => year_or_month,
+            DateUnit::Day => {
+                if date.day == 1 { year_or_month } else { "%d" }
+            }
+            DateUnit::Hour | DateUnit::Minute => {
+                if ! is_midnight {
+                    "%H:%M"
+                } else if date.day == 1 {
+                    year_or_month
+                } else {
+                    "%b %d"
+                }
+            }
+            DateUnit::Second => {
+                if date.second < 0.5 { "%H:%M" } else { "%S" }
+            }
+        }
+    }
+}
+
+impl TickFormatter for DateFormatter {
+    fn format(&self, value: f32, delta: f32) -> String {
+        let delta = delta as f64;
+
+        // level of the tick spacing, with slack for month and year lengths
+        let unit = if delta >= 360. * SECONDS_PER_DAY {
+            DateUnit::Year
+        } else if delta >= 28. * SECONDS_PER_DAY {
+            DateUnit::Month
+        } else if delta >= 0.99 * SECONDS_PER_DAY {
+            DateUnit::Day
+        } else if delta >= 0.99 * SECONDS_PER_HOUR {
+            DateUnit::Hour
+        } else if delta >= 0.99 * SECONDS_PER_MINUTE {
+            DateUnit::Minute
+        } else {
+            DateUnit::Second
+        };
+
+        // f32 epoch seconds are coarse, so snap to the tick resolution
+        let resolution = match unit {
+            DateUnit::Year | DateUnit::Month | DateUnit::Day => SECONDS_PER_HOUR,
+            DateUnit::Hour => SECONDS_PER_HOUR,
+            DateUnit::Minute => SECONDS_PER_MINUTE,
+            DateUnit::Second => 1.,
+        };
+
+        let t = value as f64 + self.epoch;
+        let date = DateTime::from_epoch((t / resolution).round() * resolution);
+
+        match &self.pattern {
+            Some(pattern) => date.strftime(pattern),
+            None => date.strftime(Self::concise_pattern(&date, unit)),
+        }
+    }
+}
+
+/// Decades as math text `10^{n}`, other values as plain numbers
+fn format_log10(value: f32) -> String {
+    if value == 0. {
+        return String::from("0");
+    }
+
+    let exp = value.abs().log10();
+    let exp_round = exp.round();
+
+    if (exp - exp_round).abs() < 1e-4 {
+        let sign = if value < 0. { "-" } else { "" };
+
+        format!("${}10^{{{}}}$", sign, exp_round as i32)
+    } else {
+        format_tick(value, value.abs())
+    }
+}
+
+/// Probabilities with enough precision to distinguish 0.999 from 1
+fn format_logit(value: f32) -> String {
+    let tail = value.min(1. - value);
+
+    if tail <= 0. {
+        return format!("{}", value);
+    }
+
+    let precision = (- tail.log10() - 1e-3).ceil().max(1.) as usize;
+
+    format!("{:.*}", precision, value)
+}
+
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Mantissa with an SI prefix on a multiple-of-three exponent
+fn format_engineering(value: f32, delta: f32, unit: &str) -> String {
+    let exp = if value == 0. {
+        0
+    } else {
+        ((value.abs().log10() + 1e-5).floor() as i32).div_euclid(3) * 3
+    };
+
+    let exp = exp.clamp(-24, 24);
+    let prefix = SI_PREFIXES[(exp / 3 + 8) as usize];
+    let scale = 10f32.powi(exp);
+
+    let num = format_tick(value / scale, (delta / scale).abs());
+    let suffix = format!("{}{}", prefix, unit);
+
+    if suffix.is_empty() {
+        num
+    } else {
+        format!("{} {}", num, suffix)
+    }
+}
+
+/// Offset values such as "1000" or "1×10⁶"
+fn format_sci(value: f32, sci_limits: (i32, i32)) -> String {
+    let exp = (value.log10() + 1e-5).floor() as i32;
+
+    if sci_limits.0 < exp && exp < sci_limits.1 {
+        return format_tick(value, 10f32.powi(exp));
+    }
+
+    let mantissa = value / 10f32.powi(exp);
+    let mantissa = format!("{:.3}", mantissa);
+    let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+
+    format!("{}×10{}", mantissa, superscript(exp))
+}
+
+///
+/// Replaces `{x}` placeholders with the value, using a subset of Python's
+/// format spec: an optional `+`, a `.N` precision and an `f`, `e`, `%`
+/// or `d` type, e.g. `{x:+.2f}`. `{{` and `}}` are literal braces.
+///
+fn format_str_method(pattern: &str, value: f32) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut field = String::new();
+
+                while let Some(ch) = chars.next() {
+                    if ch == '}' {
+                        break;
+                    }
+                    field.push(ch);
+                }
+
+                let spec = match field.split_once(':') {
+                    Some((_, spec)) => spec,
+                    None => "",
+                };
+
+                out.push_str(&format_spec(spec, value));
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+fn format_spec(spec: &str, value: f32) -> String {
+    let (is_sign, spec) = match spec.strip_prefix('+') {
+        Some(spec) => (true, spec),
+        None => (false, spec),
+    };
+
+    let (precision, kind) = match spec.strip_prefix('.') {
+        Some(spec) => {
+            let digits = spec.chars().take_while(|c| c.is_ascii_digit()).count();
+
+            (spec[..digits].parse::<usize>().ok(), &spec[digits..])
+        }
+        None => (None, spec),
+    };
+
+    let text = match kind {
+        "f" => format!("{:.*}", precision.unwrap_or(6), value),
+        "%" => format!("{:.*}%", precision.unwrap_or(6), value * 100.),
+        "d" => format!("{}", value.round() as i64),
+        "g" => format_general(value, precision.unwrap_or(6).max(1)),
+        "e" => {
+            let text = format!("{:.*e}", precision.unwrap_or(6), value);
+
+            match text.split_once('e') {
+                Some((mantissa, exp)) => {
+                    let exp: i32 = exp.parse().unwrap_or(0);
+
+                    format!("{}e{}{:02}", mantissa, if exp < 0 { '-' } else { '+' }, exp.abs())
+                }
+                None => text,
+            }
+        }
+        _ => match precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => format!("{}", value),
+        }
+    };
+
+    if is_sign && value >= 0. {
+        format!("+{}", text)
+    } else {
+        text
+    }
+}
+
+///
+/// `%g` style: `precision` significant digits without trailing zeros, in
+/// scientific notation for very small or large values.
+///
+fn format_general(value: f32, precision: usize) -> String {
+    if value == 0. || ! value.is_finite() {
+        return format!("{}", value);
+    }
+
+    let sci = format!("{:.*e}", precision - 1, value);
+
+    let (mantissa, exp) = match sci.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse::<i32>().unwrap_or(0)),
+        None => return sci,
+    };
+
+    let trim = |text: &str| -> String {
+        if text.contains('.') {
+            text.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            text.to_string()
+        }
+    };
+
+    if exp < -4 || exp >= precision as i32 {
+        format!("{}e{}{:02}", trim(mantissa), if exp < 0 { '-' } else { '+' }, exp.abs())
+    } else {
+        trim(&format!("{:.*}", (precision as i32 - 1 - exp) as usize, value))
+    }
+}
+
+fn superscript(exp: i32) -> String {
+    exp.to_string().chars().map(|ch| {
+        match ch {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            _ => ch,
+        }
+    }).collect()
+}
+
 fn format_tick(value: f32, delta: f32) -> String {
     // handle delta = 0.19999 vs delta = 0.2004
     let delta = delta + delta * 1e-2;
@@ -32,3 +555,151 @@ fn format_tick(value: f32, delta: f32) -> String {
 
     format!("{:-#.*}", precision, value)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::chart::dates::DateTime;
+
+    use super::{
+        format_log10, DateFormatter, FixedFormatter, Formatter, FuncFormatter, 
+        ScalarFormatter, TickFormatter
+    };
+
+    #[test]
+    fn log10_format() {
+        assert_eq!(format_log10(1000.), "$10^{3}$");
+        assert_eq!(format_log10(0.01), "$10^{-2}$");
+        assert_eq!(format_log10(-10.), "$-10^{1}$");
+        assert_eq!(format_log10(1.), "$10^{0}$");
+        assert_eq!(format_log10(0.), "0");
+    }
+
+    #[test]
+    fn scalar_formatter_plain() {
+        let mut formatter = ScalarFormatter::new();
+        formatter.set_locs(&[0., 0.5, 1.0]);
+
+        assert_eq!(formatter.format(0.5, 0.5), "0.5");
+        assert_eq!(formatter.offset_text(), None);
+    }
+
+    #[test]
+    fn scalar_formatter_offset() {
+        let mut formatter = ScalarFormatter::new();
+        formatter.set_locs(&[100000.2, 100000.4, 100000.6]);
+
+        assert_eq!(formatter.offset_text(), Some(String::from("+100000")));
+        assert_eq!(formatter.format(100000.4, 0.2), "0.4");
+
+        formatter.set_locs(&[-2e7 - 4., -2e7 - 2., -2e7]);
+        assert_eq!(formatter.offset_text(), Some(String::from("-2×10⁷")));
+        assert_eq!(formatter.format(-2e7 - 2., 1.), "-2");
+
+        let mut formatter = ScalarFormatter::new().use_offset(false);
+        formatter.set_locs(&[100000.2, 100000.4, 100000.6]);
+
+        assert_eq!(formatter.offset_text(), None);
+    }
+
+    #[test]
+    fn scalar_formatter_sci() {
+        let mut formatter = ScalarFormatter::new();
+        formatter.set_locs(&[0., 1e7, 2e7, 3e7]);
+
+        assert_eq!(formatter.offset_text(), Some(String::from("×10⁷")));
+        assert_eq!(formatter.format(2e7, 1e7), "2");
+
+        formatter.set_locs(&[0., 1e-6, 2e-6, 3e-6]);
+        assert_eq!(formatter.offset_text(), Some(String::from("×10⁻⁶")));
+        assert_eq!(formatter.format(2e-6, 1e-6), "2");
+    }
+
+    #[test]
+    fn formatter_percent() {
+        let formatter = Formatter::Percent { xmax: 1. };
+
+        assert_eq!(formatter.format(0.5, 0.2), "50%");
+        assert_eq!(formatter.format(0.025, 0.005), "2.5%");
+    }
+
+    #[test]
+    fn formatter_engineering() {
+        let formatter = Formatter::Engineering { unit: String::from("Hz") };
+
+        assert_eq!(formatter.format(1500., 500.), "1.5 kHz");
+        assert_eq!(formatter.format(500., 500.), "500 Hz");
+        assert_eq!(formatter.format(2e6, 1e6), "2 MHz");
+        assert_eq!(formatter.format(20e-6, 10e-6), "20 µHz");
+        assert_eq!(formatter.format(0., 10.), "0 Hz");
+
+        let formatter = Formatter::Engineering { unit: String::new() };
+        assert_eq!(formatter.format(3000., 1000.), "3 k");
+        assert_eq!(formatter.format(3., 1.), "3");
+    }
+
+    #[test]
+    fn formatter_str_method() {
+        let fmt = |pattern: &str, value: f32| {
+            Formatter::StrMethod(String::from(pattern)).format(value, 1.)
+        };
+
+        assert_eq!(fmt("{x:.2f} m", 1.5), "1.50 m");
+        assert_eq!(fmt("{x:.0%}", 0.25), "25%");
+        assert_eq!(fmt("{x:+.1f}", 2.), "+2.0");
+        assert_eq!(fmt("{x:d}", 2.6), "3");
+        assert_eq!(fmt("{x:.2e}", 1500.), "1.50e+03");
+        assert_eq!(fmt("{{{x}}}", 2.5), "{2.5}");
+        assert_eq!(fmt("{x:g}", 0.1 + 0.2), "0.3");
+        assert_eq!(fmt("{x:g}", 15.), "15");
+        assert_eq!(fmt("{x:g}", 1234567.), "1.23457e+06");
+        assert_eq!(fmt("{x:.2g}", 0.000012), "1.2e-05");
+    }
+
+    #[test]
+    fn formatter_func_and_fixed() {
+        let formatter = FuncFormatter::new(|v, _| format!("${}", v));
+        assert_eq!(formatter.format(3., 1.), "$3");
+
+        let formatter = FixedFormatter::new(&["a", "b", "c"]);
+        assert_eq!(formatter.format(1., 1.), "b");
+        assert_eq!(formatter.format(1.5, 0.5), "");
+        assert_eq!(formatter.format(3., 1.), "");
+    }
+
+    #[test]
+    fn date_formatter_pattern() {
+        let formatter = DateFormatter::new("%Y-%m-%d");
+        let t = DateTime::new(2026, 3, 1).to_epoch() as f32;
+
+        assert_eq!(formatter.format(t, 86400.), "2026-03-01");
+    }
+
+    #[test]
+    fn date_formatter_concise() {
+        let formatter = DateFormatter::concise();
+        let month = 30. * 86400.;
+        let day = 86400.;
+
+        let jan = DateTime::new(2026, 1, 1).to_epoch() as f32;
+        let mar = DateTime::new(2026, 3, 1).to_epoch() as f32;
+        let mar_5 = DateTime::new(2026, 3, 5).to_epoch() as f32;
+
+        assert_eq!(formatter.format(jan, month), "2026");
+        assert_eq!(formatter.format(mar, month), "Mar");
+        assert_eq!(formatter.format(mar, day), "Mar");
+        assert_eq!(formatter.format(mar_5, day), "05");
+
+        let noon = DateTime::new(2026, 3, 5).hms(12, 0, 0.).to_epoch() as f32;
+        assert_eq!(formatter.format(noon, 3600.), "12:00");
+        assert_eq!(formatter.format(mar_5, 3600.), "Mar 05");
+    }
+
+    #[test]
+    fn date_formatter_epoch() {
+        let epoch = DateTime::new(2026, 3, 5).to_epoch();
+        let formatter = DateFormatter::concise().epoch(epoch);
+
+        assert_eq!(formatter.format(90., 15.), "30");
+        assert_eq!(formatter.format(120., 15.), "00:02");
+    }
+}
@@ -2,12 +2,23 @@
 
 use essay_tensor::{init::linspace, ten, tensor::Tensor};
 
+use super::dates::{
+    civil_from_days, DateTime, SECONDS_PER_DAY, SECONDS_PER_HOUR, SECONDS_PER_MINUTE
+};
+
 pub trait TickLocator : Send {
     fn tick_values(&self, min: f32, max: f32) -> Tensor<f32>;
 
     fn view_limits(&self, min: f32, max: f32) -> (f32, f32) {
         (min, max)
     }
+
+    ///
+    /// Called with the major tick values before a minor locator's
+    /// `tick_values`, for locators that subdivide the major ticks.
+    ///
+    fn set_major(&mut self, _major: &[f32]) {
+    }
 }
 
 pub struct IndexLocator {
@@ -27,8 +38,15 @@ impl IndexLocator {
 }
 
 impl TickLocator for IndexLocator {
+    ///
+    /// Ticks at `offset + k * base` within the view, so index ticks stay
+    /// on the indices when the view has margins.
+    ///
     fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
-        let range = Tensor::arange(min + self.offset, max + 1., self.base);
+        let start = ((min - self.offset) / self.base).ceil() * self.base + self.offset;
+        let end = ((max - self.offset) / self.base).floor() * self.base + self.offset;
+
+        let range = Tensor::arange(start, end + 0.5 * self.base, self.base);
 
         assert!(range.len() < Self::MAXTICKS);
 
@@ -36,6 +54,121 @@ impl TickLocator for IndexLocator {
     }
 }
 
+///
+/// Ticks at every multiple of `base`.
+///
+pub struct MultipleLocator {
+    base: f32,
+}
+
+impl MultipleLocator {
+    const MAXTICKS : usize = 1000;
+
+    pub fn new(base: f32) -> Self {
+        assert!(base > 0., "MultipleLocator base must be positive {}", base);
+
+        Self {
+            base,
+        }
+    }
+}
+
+impl TickLocator for MultipleLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+        if ! min.is_finite() || ! max.is_finite() {
+            return Tensor::from(Vec::<f32>::new());
+        }
+
+        // a wide view coarsens the step to a multiple of base
+        let count = (max - min) / self.base;
+        let scale = (count / Self::MAXTICKS as f32).floor() + 1.;
+        let base = self.base * scale;
+
+        let lo = (min / base - 1e-4).ceil() as i64;
+        let hi = (max / base + 1e-4).floor() as i64;
+
+        let ticks: Vec<f32> = (lo..=hi).map(|i| i as f32 * base).collect();
+
+        Tensor::from(ticks)
+    }
+}
+
+///
+/// Minor ticks dividing each major interval into `n` parts. The default
+/// is 5 for major steps of 1, 2.5 or 5 times a power of ten, otherwise 4.
+///
+pub struct AutoMinorLocator {
+    n: Option<usize>,
+    major: Vec<f32>,
+}
+
+impl AutoMinorLocator {
+    const MAXTICKS : usize = 1000;
+
+    pub fn new(n: Option<usize>) -> Self {
+        Self {
+            n,
+            major: Vec::new(),
+        }
+    }
+
+    fn n_divisions(&self, step: f32) -> usize {
+        match self.n {
+            Some(n) => n,
+            None => {
+                let mantissa = step / 10f32.powf(step.log10().floor());
+
+                if [1., 2.5, 5., 10.].iter().any(|m| (mantissa - m).abs() < 1e-3) {
+                    5
+                } else {
+                    4
+                }
+            }
+        }
+    }
+}
+
+impl TickLocator for AutoMinorLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+        if self.major.len() < 2 {
+            return Tensor::from(Vec::<f32>::new());
+        }
+
+        let t0 = self.major[0];
+        let step = (self.major[1] - t0).abs();
+        let n = self.n_divisions(step) as i64;
+
+        if step <= 0. || n < 2 {
+            return Tensor::from(Vec::<f32>::new());
+        }
+
+        let minor_step = step / n as f32;
+
+        // too many minor ticks to be useful, e.g. a zoomed out view
+        if ! ((max - min) / minor_step < Self::MAXTICKS as f32) {
+            return Tensor::from(Vec::<f32>::new());
+        }
+
+        let lo = ((min - t0) / minor_step - 1e-4).ceil() as i64;
+        let hi = ((max - t0) / minor_step + 1e-4).floor() as i64;
+
+        let ticks: Vec<f32> = (lo..=hi)
+            .filter(|i| i.rem_euclid(n) != 0)
+            .map(|i| t0 + i as f32 * minor_step)
+            .collect();
+
+        Tensor::from(ticks)
+    }
+
+    fn set_major(&mut self, major: &[f32]) {
+        self.major = Vec::from(major);
+    }
+}
+
 pub struct LinearLocator {
     n_ticks: usize,
 }
@@ -225,6 +358,373 @@ impl TickLocator for MaxNLocator {
     }
 }
 
+///
+/// Ticks at integer powers of ten, and optionally at multiples of each
+/// decade, e.g. 2..9 for minor ticks.
+///
+pub struct LogLocator {
+    subs: Vec<f32>,
+    n_decades: usize,
+}
+
+impl LogLocator {
+    const MAXTICKS : usize = 1000;
+
+    pub fn new() -> Self {
+        Self {
+            subs: vec![1.],
+            n_decades: 9,
+        }
+    }
+
+    pub fn minor() -> Self {
+        Self {
+            subs: vec![2., 3., 4., 5., 6., 7., 8., 9.],
+            n_decades: usize::MAX,
+        }
+    }
+
+    pub fn subs(&mut self, subs: &[f32]) -> &mut Self {
+        assert!(subs.len() > 0);
+
+        self.subs = Vec::from(subs);
+
+        self
+    }
+
+    fn decades(min: f32, max: f32) -> (f32, f32) {
+        let (min, max) = if min <= 0. || max <= 0. {
+            (1., 10.)
+        } else if min < max {
+            (min, max)
+        } else {
+            (max, min)
+        };
+
+        (min.log10().floor(), max.log10().ceil())
+    }
+}
+
+impl TickLocator for LogLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let (lo, hi) = Self::decades(min, max);
+
+        let n = (hi - lo) as usize + 1;
+        let stride = if n > self.n_decades {
+            (n + self.n_decades - 1) / self.n_decades
+        } else {
+            1
+        };
+
+        let mut ticks = Vec::<f32>::new();
+
+        let mut decade = lo as i32;
+        while decade <= hi as i32 {
+            let base = 10.0f32.powi(decade);
+
+            for sub in &self.subs {
+                ticks.push(base * sub);
+            }
+
+            decade += stride as i32;
+        }
+
+        assert!(ticks.len() < Self::MAXTICKS);
+
+        Tensor::from(ticks)
+    }
+
+    fn view_limits(&self, min: f32, max: f32) -> (f32, f32) {
+        let (lo, hi) = Self::decades(min, max);
+
+        let hi = if lo == hi { hi + 1. } else { hi };
+
+        (10.0f32.powi(lo as i32), 10.0f32.powi(hi as i32))
+    }
+}
+
+///
+/// Ticks for a symmetric log scale: zero, the linear threshold and decades
+/// beyond the threshold on both sides.
+///
+pub struct SymLogLocator {
+    linthresh: f32,
+}
+
+impl SymLogLocator {
+    pub fn new(linthresh: f32) -> Self {
+        assert!(linthresh > 0., "symlog linthresh must be positive {}", linthresh);
+
+        Self {
+            linthresh,
+        }
+    }
+
+    fn side_ticks(&self, max: f32, sign: f32, ticks: &mut Vec<f32>) {
+        if max < self.linthresh {
+            return;
+        }
+
+        let n = (max / self.linthresh).log10().ceil().max(0.) as i32;
+
+        for k in 0..=n {
+            ticks.push(sign * self.linthresh * 10.0f32.powi(k));
+        }
+    }
+}
+
+impl TickLocator for SymLogLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+        let mut neg = Vec::<f32>::new();
+        let mut pos = Vec::<f32>::new();
+
+        if min < 0. {
+            self.side_ticks(- min, -1., &mut neg);
+        }
+
+        if max > 0. {
+            self.side_ticks(max, 1., &mut pos);
+        }
+
+        neg.reverse();
+
+        let mut ticks = neg;
+
+        if min <= 0. && 0. <= max {
+            ticks.push(0.);
+        }
+
+        ticks.append(&mut pos);
+
+        Tensor::from(ticks)
+    }
+}
+
+///
+/// Ticks for a logit scale: 0.5 and decades approaching 0 and 1, e.g.
+/// 0.01, 0.1, 0.5, 0.9, 0.99.
+///
+pub struct LogitLocator {
+}
+
+impl LogitLocator {
+    pub fn new() -> Self {
+        Self {
+        }
+    }
+}
+
+impl TickLocator for LogitLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let min = min.clamp(1e-7, 0.5);
+        let max = max.clamp(0.5, 1. - 1e-7);
+
+        // tolerance so 0.99 is treated as two decades, not three
+        let n_lo = (- min.log10() - 1e-3).ceil() as i32;
+        let n_hi = (- (1. - max).log10() - 1e-3).ceil() as i32;
+
+        let mut ticks = Vec::<f32>::new();
+
+        for k in (1..=n_lo).rev() {
+            ticks.push(10.0f32.powi(- k));
+        }
+
+        ticks.push(0.5);
+
+        for k in 1..=n_hi {
+            ticks.push(1. - 10.0f32.powi(- k));
+        }
+
+        let n_visible = ticks.iter()
+            .filter(|t| min <= **t && **t <= max)
+            .count();
+
+        if n_visible < 3 {
+            // narrow range, so decade ticks are too sparse
+            ticks = (1..10).map(|i| i as f32 * 0.1).collect();
+        }
+
+        Tensor::from(ticks)
+    }
+}
+
+///
+/// Calendar unit of a date tick interval.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateUnit {
+    ///
+    /// Approximate length in seconds, used to choose an interval.
+    ///
+    pub fn seconds(&self) -> f64 {
+        match self {
+            DateUnit::Year => 365.25 * SECONDS_PER_DAY,
+            DateUnit::Month => 30.44 * SECONDS_PER_DAY,
+            DateUnit::Day => SECONDS_PER_DAY,
+            DateUnit::Hour => SECONDS_PER_HOUR,
+            DateUnit::Minute => SECONDS_PER_MINUTE,
+            DateUnit::Second => 1.,
+        }
+    }
+}
+
+///
+/// Ticks on calendar boundaries for axes in epoch seconds, choosing a
+/// year, month, day, hour, minute or second step for the visible range.
+///
+pub struct AutoDateLocator {
+    epoch: f64,
+    max_ticks: usize,
+}
+
+impl AutoDateLocator {
+    const MAXTICKS : usize = 1000;
+
+    const INTERVALS : [(DateUnit, u32); 38] = [
+        (DateUnit::Second, 1), (DateUnit::Second, 2), (DateUnit::Second, 5),
+        (DateUnit::Second, 10), (DateUnit::Second, 15), (DateUnit::Second, 30),
+        (DateUnit::Minute, 1), (DateUnit::Minute, 2), (DateUnit::Minute, 5),
+        (DateUnit::Minute, 10), (DateUnit::Minute, 15), (DateUnit::Minute, 30),
+        (DateUnit::Hour, 1), (DateUnit::Hour, 2), (DateUnit::Hour, 3),
+        (DateUnit::Hour, 4), (DateUnit::Hour, 6), (DateUnit::Hour, 12),
+        (DateUnit::Day, 1), (DateUnit::Day, 2), (DateUnit::Day, 3),
+        (DateUnit::Day, 7), (DateUnit::Day, 14),
+        (DateUnit::Month, 1), (DateUnit::Month, 2), (DateUnit::Month, 3),
+        (DateUnit::Month, 4), (DateUnit::Month, 6),
+        (DateUnit::Year, 1), (DateUnit::Year, 2), (DateUnit::Year, 5),
+        (DateUnit::Year, 10), (DateUnit::Year, 20), (DateUnit::Year, 50),
+        (DateUnit::Year, 100), (DateUnit::Year, 200), (DateUnit::Year, 500),
+        (DateUnit::Year, 1000),
+    ];
+
+    pub fn new() -> Self {
+        Self {
+            epoch: 0.,
+            max_ticks: 8,
+        }
+    }
+
+    ///
+    /// Epoch of the axis values in seconds since 1970-01-01 UTC.
+    ///
+    pub fn epoch(mut self, epoch: f64) -> Self {
+        self.epoch = epoch;
+
+        self
+    }
+
+    pub fn max_ticks(mut self, max_ticks: usize) -> Self {
+        assert!(max_ticks > 0);
+
+        self.max_ticks = max_ticks;
+
+        self
+    }
+
+    pub fn interval(&self, min: f32, max: f32) -> (DateUnit, u32) {
+        let span = (max as f64 - min as f64).abs();
+
+        for (unit, step) in Self::INTERVALS {
+            if span / (unit.seconds() * step as f64) <= self.max_ticks as f64 {
+                return (unit, step);
+            }
+        }
+
+        Self::INTERVALS[Self::INTERVALS.len() - 1]
+    }
+
+    fn date_ticks(unit: DateUnit, step: u32, min: f64, max: f64) -> Vec<f64> {
+        let mut ticks = Vec::<f64>::new();
+
+        match unit {
+            DateUnit::Second | DateUnit::Minute | DateUnit::Hour => {
+                let delta = unit.seconds() * step as f64;
+                let mut t = (min / delta).floor() * delta;
+
+                while t <= max + delta && ticks.len() < Self::MAXTICKS {
+                    ticks.push(t);
+                    t += delta;
+                }
+            }
+            DateUnit::Day => {
+                let d_min = (min / SECONDS_PER_DAY).floor() as i64 - 1;
+                let d_max = (max / SECONDS_PER_DAY).ceil() as i64 + 1;
+
+                for days in d_min..=d_max {
+                    let (_, _, day) = civil_from_days(days);
+
+                    if (day - 1) % step == 0 {
+                        ticks.push(days as f64 * SECONDS_PER_DAY);
+                    }
+
+                    if ticks.len() >= Self::MAXTICKS {
+                        break;
+                    }
+                }
+            }
+            DateUnit::Month => {
+                let lo = DateTime::from_epoch(min);
+                let hi = DateTime::from_epoch(max);
+
+                let m_min = lo.year as i64 * 12 + lo.month as i64 - 1 - step as i64;
+                let m_max = hi.year as i64 * 12 + hi.month as i64 - 1 + step as i64;
+
+                for m in m_min..=m_max {
+                    let (year, month) = (m.div_euclid(12) as i32, (m.rem_euclid(12) + 1) as u32);
+
+                    if (month - 1) % step == 0 {
+                        ticks.push(DateTime::new(year, month, 1).to_epoch());
+                    }
+
+                    if ticks.len() >= Self::MAXTICKS {
+                        break;
+                    }
+                }
+            }
+            DateUnit::Year => {
+                let step = step as i32;
+                let lo = DateTime::from_epoch(min).year.div_euclid(step) * step;
+                let hi = DateTime::from_epoch(max).year + step;
+
+                let mut year = lo;
+                while year <= hi && ticks.len() < Self::MAXTICKS {
+                    ticks.push(DateTime::new(year, 1, 1).to_epoch());
+                    year += step;
+                }
+            }
+        }
+
+        ticks
+    }
+}
+
+impl TickLocator for AutoDateLocator {
+    fn tick_values(&self, min: f32, max: f32) -> Tensor<f32> {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        let (unit, step) = self.interval(min, max);
+
+        let ticks: Vec<f32> = Self::date_ticks(
+            unit, 
+            step, 
+            min as f64 + self.epoch, 
+            max as f64 + self.epoch
+        ).iter().map(|t| (t - self.epoch) as f32).collect();
+
+        Tensor::from(ticks)
+    }
+}
+
 fn nonsingular(min: f32, max: f32, expander: f32, tiny: f32) -> (f32, f32) {
     if ! min.is_finite() || ! max.is_finite() {
         return (-expander, expander);
@@ -263,11 +763,64 @@ fn scale_range(vmin: f32, vmax: f32, n_bins: usize) -> (f32, f32) {
 
 #[cfg(test)]
 mod test {
-    use essay_tensor::ten;
+    use essay_tensor::{ten, tensor::Tensor};
 
     use crate::chart::tick_locator::TickLocator;
 
-    use super::MaxNLocator;
+    use crate::chart::dates::DateTime;
+
+    use super::{
+        AutoDateLocator, AutoMinorLocator, DateUnit, IndexLocator, LogLocator, LogitLocator, 
+        MaxNLocator, MultipleLocator, SymLogLocator
+    };
+
+    #[test]
+    fn index_locator() {
+        let locator = IndexLocator::new(1., 0.);
+
+        assert_eq!(locator.tick_values(-0.4, 2.6), ten![0., 1., 2.]);
+
+        let locator = IndexLocator::new(2., 1.);
+
+        assert_eq!(locator.tick_values(0., 6.), ten![1., 3., 5.]);
+    }
+
+    #[test]
+    fn multiple_locator() {
+        let locator = MultipleLocator::new(0.25);
+
+        assert_eq!(locator.tick_values(0.1, 1.), ten![0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(locator.tick_values(-0.5, 0.), ten![-0.5, -0.25, 0.]);
+
+        // a wide view coarsens the step instead of failing
+        let locator = MultipleLocator::new(1.);
+        let ticks = locator.tick_values(0., 1.0e6);
+        assert!(ticks.len() > 0 && ticks.len() <= 1001);
+        assert_eq!(ticks[0], 0.);
+    }
+
+    #[test]
+    fn auto_minor_locator() {
+        let mut locator = AutoMinorLocator::new(None);
+        assert_eq!(locator.tick_values(0., 1.), Tensor::from(Vec::<f32>::new()));
+
+        locator.set_major(&[0., 1., 2.]);
+        assert_eq!(locator.tick_values(0., 1.), ten![0.2, 0.4, 0.6, 0.8]);
+
+        // minor ticks extend past the major ticks to the view limits
+        assert_eq!(locator.tick_values(-0.5, 0.), ten![-0.4, -0.2]);
+
+        locator.set_major(&[0., 2., 4.]);
+        assert_eq!(locator.tick_values(0., 2.), ten![0.5, 1.0, 1.5]);
+
+        let mut locator = AutoMinorLocator::new(Some(2));
+        locator.set_major(&[10., 20.]);
+        assert_eq!(locator.tick_values(10., 20.), ten![15.]);
+
+        // a zoomed out view drops the minor ticks instead of failing
+        locator.set_major(&[0., 0.001]);
+        assert_eq!(locator.tick_values(0., 1.0e6), Tensor::from(Vec::<f32>::new()));
+    }
 
     #[test]
     fn max_n_locator_view_limits() {
@@ -400,4 +953,114 @@ mod test {
             ten![-2., -1., 0., 1., 2., 3., 4., 5., 6.]
         );
     }
-}
\ No newline at end of file
+
+    fn assert_approx(ticks: Tensor, expect: &[f32]) {
+        assert_eq!(ticks.len(), expect.len(), "{:?} {:?}", ticks, expect);
+
+        for (tick, value) in ticks.iter().zip(expect) {
+            assert!((tick - value).abs() <= 1e-5 * value.abs(), "{:?} {:?}", ticks, expect);
+        }
+    }
+
+    #[test]
+    fn log_locator_tick_values() {
+        let locator = LogLocator::new();
+
+        assert_approx(locator.tick_values(1., 1000.), &[1., 10., 100., 1000.]);
+        assert_approx(locator.tick_values(0.02, 3.), &[0.01, 0.1, 1., 10.]);
+
+        assert_eq!(locator.view_limits(2., 300.), (1., 1000.));
+        assert_eq!(locator.view_limits(10., 10.), (10., 100.));
+    }
+
+    #[test]
+    fn log_locator_minor() {
+        let locator = LogLocator::minor();
+
+        let ticks = locator.tick_values(1., 10.);
+
+        assert_eq!(ticks.len(), 16);
+        assert_eq!(ticks[0], 2.);
+        assert_eq!(ticks[7], 9.);
+        assert_eq!(ticks[8], 20.);
+    }
+
+    #[test]
+    fn log_locator_many_decades() {
+        let locator = LogLocator::new();
+
+        let ticks = locator.tick_values(1e-20, 1e20);
+
+        assert!(ticks.len() <= 9);
+        assert!((ticks[0] - 1e-20).abs() < 1e-25);
+    }
+
+    #[test]
+    fn symlog_locator_tick_values() {
+        let locator = SymLogLocator::new(1.);
+
+        assert_eq!(
+            locator.tick_values(-100., 100.), 
+            ten![-100., -10., -1., 0., 1., 10., 100.]
+        );
+
+        assert_eq!(
+            locator.tick_values(0., 50.), 
+            ten![0., 1., 10., 100.]
+        );
+    }
+
+    #[test]
+    fn logit_locator_tick_values() {
+        let locator = LogitLocator::new();
+
+        assert_approx(locator.tick_values(0.01, 0.99), &[0.01, 0.1, 0.5, 0.9, 0.99]);
+
+        assert_eq!(locator.tick_values(0.3, 0.7).len(), 9);
+    }
+
+    #[test]
+    fn auto_date_locator_interval() {
+        let locator = AutoDateLocator::new();
+        let day = 86400.;
+
+        assert_eq!(locator.interval(0., 30.), (DateUnit::Second, 5));
+        assert_eq!(locator.interval(0., 6. * 3600.), (DateUnit::Hour, 1));
+        assert_eq!(locator.interval(0., 5. * day), (DateUnit::Day, 1));
+        assert_eq!(locator.interval(0., 90. * day), (DateUnit::Day, 14));
+        assert_eq!(locator.interval(0., 200. * day), (DateUnit::Month, 1));
+        assert_eq!(locator.interval(0., 10. * 365. * day), (DateUnit::Year, 2));
+    }
+
+    #[test]
+    fn auto_date_locator_months() {
+        let locator = AutoDateLocator::new();
+
+        let min = DateTime::new(2026, 1, 15).to_epoch() as f32;
+        let max = DateTime::new(2026, 5, 10).to_epoch() as f32;
+
+        let ticks = locator.tick_values(min, max);
+        let months: Vec<(i32, u32, u32)> = ticks.iter().map(|t| {
+            let date = DateTime::from_epoch(*t as f64);
+            (date.year, date.month, date.day)
+        }).collect();
+
+        assert_eq!(months, vec![
+            (2025, 12, 1), (2026, 1, 1), (2026, 2, 1), (2026, 3, 1),
+            (2026, 4, 1), (2026, 5, 1), (2026, 6, 1),
+        ]);
+    }
+
+    #[test]
+    fn auto_date_locator_epoch() {
+        let epoch = DateTime::new(2026, 3, 1).to_epoch();
+        let locator = AutoDateLocator::new().epoch(epoch);
+
+        // seconds after the epoch keep full f32 precision
+        let ticks = locator.tick_values(10., 100.);
+
+        assert_eq!(ticks[0], 0.);
+        assert_eq!(ticks[1], 15.);
+        assert_eq!(ticks[ticks.len() - 1], 105.);
+    }
+}
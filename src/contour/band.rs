@@ -0,0 +1,78 @@
+///
+/// Part of a convex polygon with linearly varying z where
+/// `lower <= z <= upper`, as a convex polygon. Infinite bounds leave that
+/// side open, and NaN vertices are outside every band.
+///
+pub(crate) fn clip_band(poly: &[[f32; 3]], lower: f32, upper: f32) -> Vec<[f32; 2]> {
+    let poly = clip_half(poly, |z| z >= lower, lower);
+    let poly = clip_half(&poly, |z| z <= upper, upper);
+
+    if poly.len() < 3 {
+        Vec::new()
+    } else {
+        poly.iter().map(|p| [p[0], p[1]]).collect()
+    }
+}
+
+///
+/// Sutherland-Hodgman clip against a z threshold.
+///
+fn clip_half(
+    poly: &[[f32; 3]],
+    is_inside: impl Fn(f32) -> bool,
+    threshold: f32
+) -> Vec<[f32; 3]> {
+    let n = poly.len();
+    let mut vec = Vec::<[f32; 3]>::new();
+
+    for i in 0..n {
+        let a = poly[i];
+        let b = poly[(i + 1) % n];
+
+        let (in_a, in_b) = (is_inside(a[2]), is_inside(b[2]));
+
+        if in_a {
+            vec.push(a);
+        }
+
+        if in_a != in_b && a[2].is_finite() && b[2].is_finite() {
+            let t = (threshold - a[2]) / (b[2] - a[2]);
+
+            vec.push([
+                a[0] + t * (b[0] - a[0]),
+                a[1] + t * (b[1] - a[1]),
+                threshold,
+            ]);
+        }
+    }
+
+    vec
+}
+
+#[cfg(test)]
+mod test {
+    use super::clip_band;
+
+    fn area(poly: &[[f32; 2]]) -> f32 {
+        let n = poly.len();
+
+        (0..n).map(|i| {
+            let (a, b) = (poly[i], poly[(i + 1) % n]);
+
+            a[0] * b[1] - b[0] * a[1]
+        }).sum::<f32>() * 0.5
+    }
+
+    #[test]
+    fn band_clip_triangle() {
+        let tri = [[0., 0., 0.], [2., 0., 2.], [0., 2., 2.]];
+
+        assert_eq!(area(&clip_band(&tri, f32::NEG_INFINITY, f32::INFINITY)), 2.);
+        assert_eq!(area(&clip_band(&tri, 1., f32::INFINITY)), 1.5);
+        assert_eq!(area(&clip_band(&tri, f32::NEG_INFINITY, 1.)), 0.5);
+        assert_eq!(area(&clip_band(&tri, 0.5, 1.5)), 1.);
+
+        assert!(clip_band(&tri, 3., 4.).is_empty());
+        assert!(clip_band(&[[0., 0., f32::NAN], [1., 0., 1.], [0., 1., 1.]], 0., 2.).is_empty());
+    }
+}
@@ -2,7 +2,7 @@ use essay_tensor::tensor::Tensor;
 
 use crate::contour::tile::TileGrid;
 
-use super::tile::{CrossEdge, Tile};
+use super::{band::clip_band, tile::{CrossEdge, Tile}};
 
 pub struct ContourGenerator {
     z: Tensor,
@@ -37,6 +37,55 @@ impl ContourGenerator {
         self.find_contours()
     }
 
+    ///
+    /// Filled polygons where `lower <= z <= upper`. Each grid cell is split
+    /// into four triangles around its center, so each polygon is convex and
+    /// holes in the band are left unfilled.
+    ///
+    pub fn contour_bands(&self, lower: f32, upper: f32) -> Vec<Vec<[f32; 2]>> {
+        let z = &self.z;
+        let mut polygons = Vec::<Vec<[f32; 2]>>::new();
+
+        for j in 0..self.tile_rows {
+            for i in 0..self.tile_cols {
+                let (x0, y0) = (i as f32, j as f32);
+                let (x1, y1) = (x0 + 1., y0 + 1.);
+
+                let quad = [
+                    [x0, y0, z[(j, i)]],
+                    [x1, y0, z[(j, i + 1)]],
+                    [x1, y1, z[(j + 1, i + 1)]],
+                    [x0, y1, z[(j + 1, i)]],
+                ];
+
+                let (mut min, mut max) = (f32::MAX, f32::MIN);
+                for [_, _, z] in &quad {
+                    (min, max) = (min.min(*z), max.max(*z));
+                }
+
+                if quad.iter().any(|p| p[2].is_nan()) || max < lower || upper < min {
+                    continue;
+                } else if lower <= min && max <= upper {
+                    polygons.push(quad.iter().map(|p| [p[0], p[1]]).collect());
+                    continue;
+                }
+
+                let z_mid = 0.25 * quad.iter().map(|p| p[2]).sum::<f32>();
+                let center = [x0 + 0.5, y0 + 0.5, z_mid];
+
+                for k in 0..4 {
+                    let poly = clip_band(&[quad[k], quad[(k + 1) % 4], center], lower, upper);
+
+                    if poly.len() > 0 {
+                        polygons.push(poly);
+                    }
+                }
+            }
+        }
+
+        polygons
+    }
+
     pub(crate) fn get_tile(&mut self, x: usize, y: usize) -> &Tile {
         &self.tiles[(x, y)]
     }
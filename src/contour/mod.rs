@@ -1,3 +1,4 @@
+mod band;
 mod tile;
 mod contour;
 mod tritile;
@@ -4,7 +4,7 @@ use essay_tensor::tensor::Tensor;
 
 use crate::tri::Triangulation;
 
-use super::tritile::{TileId, Tile, CrossEdge};
+use super::{band::clip_band, tritile::{TileId, Tile, CrossEdge}};
 
 pub struct TriContourGenerator {
     tiles: Tiles,
@@ -36,6 +36,31 @@ impl TriContourGenerator {
         self.tiles.find_contours()
     }
 
+    ///
+    /// Filled polygons where `lower <= z <= upper`, clipped from each
+    /// triangle, where z is linear.
+    ///
+    pub fn contour_bands(&self, lower: f32, upper: f32) -> Vec<Vec<[f32; 2]>> {
+        let tiles = &self.tiles;
+        let mut polygons = Vec::<Vec<[f32; 2]>>::new();
+
+        for tile in &tiles.tiles {
+            let poly = tile.verts.map(|v| {
+                let [x, y] = tiles.vertex(v);
+
+                [x, y, tiles.z[v.i()]]
+            });
+
+            let poly = clip_band(&poly, lower, upper);
+
+            if poly.len() > 0 {
+                polygons.push(poly);
+            }
+        }
+
+        polygons
+    }
+
     #[cfg(test)]
     fn tile(&self, index: usize) -> &Tile {
         &self.tiles.tiles[index]
@@ -54,7 +79,12 @@ impl Tiles {
 
         let mut tiles = Vec::<Tile>::new();
 
-        for (i, verts) in tri.triangles().iter_row().enumerate() {
+        let unmasked = tri.triangles().iter_row()
+            .enumerate()
+            .filter(|(i, _)| ! tri.is_masked(*i))
+            .map(|(_, verts)| verts);
+
+        for (i, verts) in unmasked.enumerate() {
             let a = VertId(verts[0]);
             let b = VertId(verts[1]);
             let c = VertId(verts[2]);
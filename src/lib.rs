@@ -7,6 +7,7 @@ pub mod macros;
 pub mod chart;
 pub mod artist;
 pub mod plot;
+pub mod backend;
 
 pub mod api {
     pub use essay_graphics::api::*;
@@ -109,6 +109,35 @@ macro_rules! path_style_options {
     }
 }
 
+#[macro_export]
+macro_rules! text_bbox_options {
+    ($field: ident) => {
+
+        pub fn bbox(&mut self, style: $crate::artist::BoxStyle) -> &mut Self {
+            self.write(|artist| { artist.$field.box_style(style); });
+            self
+        }
+
+        ///
+        /// Padding in points between the text and its box.
+        ///
+        pub fn bbox_pad(&mut self, pad: f32) -> &mut Self {
+            self.write(|artist| { artist.$field.pad(pad); });
+            self
+        }
+
+        pub fn bbox_face_color(&mut self, color: impl Into<essay_graphics::api::Color>) -> &mut Self {
+            self.write(|artist| { artist.$field.path_style_mut().face_color(color); });
+            self
+        }
+
+        pub fn bbox_edge_color(&mut self, color: impl Into<essay_graphics::api::Color>) -> &mut Self {
+            self.write(|artist| { artist.$field.path_style_mut().edge_color(color); });
+            self
+        }
+    }
+}
+
 
 #[macro_export]
 macro_rules! transform_options {
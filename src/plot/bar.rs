@@ -1,18 +1,247 @@
 use essay_tensor::tensor::Tensor;
 
-use crate::{chart::Chart, artist::{Bar, BarOpt}};
+use crate::{chart::{AxisOpt, Chart, IntoAxisData}, artist::{Bar, BarOpt, Orientation}};
 
 pub fn bar(
     graph: &mut Chart,
     data: impl Into<Tensor>,
 ) -> BarOpt {
     let bar = Bar::new(data);
-    
+
     graph.artist(bar)
 }
 
+///
+/// Bars of `height` at x positions or categories.
+///
+pub fn bar_xy(
+    graph: &mut Chart,
+    x: impl IntoAxisData,
+    height: impl Into<Tensor>,
+) -> BarOpt {
+    let x = graph.x_values(x);
+
+    let mut bar = graph.artist(Bar::new(height));
+    bar.x(x);
+
+    bar
+}
+
+pub fn barh(
+    graph: &mut Chart,
+    data: impl Into<Tensor>,
+) -> BarOpt {
+    let mut bar = graph.artist(Bar::new(data));
+
+    bar.orientation(Orientation::Horizontal);
+
+    bar
+}
+
+///
+/// Horizontal bars of `width` at y positions or categories.
+///
+pub fn barh_xy(
+    graph: &mut Chart,
+    y: impl IntoAxisData,
+    width: impl Into<Tensor>,
+) -> BarOpt {
+    let y = graph.y_values(y);
+
+    let mut bar = graph.artist(Bar::new(width));
+    bar.x(y).orientation(Orientation::Horizontal);
+
+    bar
+}
+
+///
+/// Side-by-side bars for each category of a `[n_series, n_cat]` tensor.
+///
+pub fn bar_grouped(
+    graph: &mut Chart,
+    data: impl Into<Tensor>,
+) -> BarGroupOpt {
+    let data = data.into();
+
+    assert!(data.rank() == 2, "bar_grouped requires [n_series, n_cat] data {:?}", data.shape());
+
+    let (n_series, n_cat) = (data.rows(), data.cols());
+
+    let width = 0.8 / n_series as f32;
+
+    let mut bars = Vec::new();
+
+    for (i, row) in data.iter_row().enumerate() {
+        let offset = (i as f32 + 0.5) * width - 0.4;
+        let x: Vec<f32> = (0..n_cat).map(|j| j as f32 + offset).collect();
+
+        let mut bar = graph.artist(Bar::new(Tensor::from(Vec::from(row))));
+        bar.x(Tensor::from(x)).width(width);
+
+        bars.push(bar);
+    }
+
+    BarGroupOpt::new(graph.clone(), bars, n_cat)
+}
+
+///
+/// Bars for each category of a `[n_series, n_cat]` tensor, with each
+/// series stacked on the previous ones.
+///
+pub fn bar_stacked(
+    graph: &mut Chart,
+    data: impl Into<Tensor>,
+) -> BarGroupOpt {
+    let data = data.into();
+
+    assert!(data.rank() == 2, "bar_stacked requires [n_series, n_cat] data {:?}", data.shape());
+
+    let n_cat = data.cols();
+
+    let mut bottom = vec![0.; n_cat];
+    let mut bars = Vec::new();
+
+    for row in data.iter_row() {
+        let mut bar = graph.artist(Bar::new(Tensor::from(Vec::from(row))));
+        bar.bottom(Tensor::from(bottom.clone()));
+
+        for (bottom, value) in bottom.iter_mut().zip(row) {
+            *bottom += value;
+        }
+
+        bars.push(bar);
+    }
+
+    BarGroupOpt::new(graph.clone(), bars, n_cat)
+}
+
+///
+/// Options for a grouped or stacked bar chart, one `BarOpt` per series.
+///
+pub struct BarGroupOpt {
+    chart: Chart,
+    bars: Vec<BarOpt>,
+
+    n_cat: usize,
+    categories: Option<Vec<String>>,
+    orientation: Orientation,
+}
+
+impl BarGroupOpt {
+    fn new(chart: Chart, bars: Vec<BarOpt>, n_cat: usize) -> Self {
+        let mut opt = Self {
+            chart,
+            bars,
+
+            n_cat,
+            categories: None,
+            orientation: Orientation::Vertical,
+        };
+
+        opt.update_ticks();
+
+        opt
+    }
+
+    pub fn series(&mut self, index: usize) -> &mut BarOpt {
+        &mut self.bars[index]
+    }
+
+    ///
+    /// Legend label for each series.
+    ///
+    pub fn labels(&mut self, labels: &[&str]) -> &mut Self {
+        assert_eq!(labels.len(), self.bars.len(), "bar labels must match the number of series");
+
+        for (bar, label) in self.bars.iter_mut().zip(labels) {
+            bar.label(label);
+        }
+
+        self
+    }
+
+    ///
+    /// Tick label for each category.
+    ///
+    pub fn categories(&mut self, categories: &[&str]) -> &mut Self {
+        assert_eq!(categories.len(), self.n_cat, "bar categories must match the data columns");
+
+        self.categories = Some(categories.iter().map(|c| c.to_string()).collect());
+        self.update_ticks();
+
+        self
+    }
+
+    pub fn orientation(&mut self, orientation: Orientation) -> &mut Self {
+        if orientation != self.orientation {
+            self.category_axis().reset_ticks();
+
+            self.orientation = orientation;
+
+            for bar in &mut self.bars {
+                bar.orientation(orientation);
+            }
+
+            self.update_ticks();
+        }
+
+        self
+    }
+
+    ///
+    /// Shows each bar's value at its end.
+    ///
+    pub fn bar_label(&mut self, is_bar_label: bool) -> &mut Self {
+        for bar in &mut self.bars {
+            bar.bar_label(is_bar_label);
+        }
+
+        self
+    }
+
+    fn category_axis(&mut self) -> AxisOpt {
+        match self.orientation {
+            Orientation::Vertical => self.chart.x(),
+            Orientation::Horizontal => self.chart.y(),
+        }
+    }
+
+    fn update_ticks(&mut self) {
+        let labels: Vec<String> = match &self.categories {
+            Some(categories) => categories.clone(),
+            None => (0..self.n_cat).map(|i| i.to_string()).collect(),
+        };
+
+        let ticks: Vec<(f32, &str)> = labels.iter().enumerate()
+            .map(|(i, label)| (i as f32, label.as_str()))
+            .collect();
+
+        self.category_axis().tick_labels(&ticks);
+    }
+}
+
 impl Chart {
     pub fn bar(&mut self, data: impl Into<Tensor>) -> BarOpt {
         bar(self, data)
     }
-}
\ No newline at end of file
+
+    pub fn bar_xy(&mut self, x: impl IntoAxisData, height: impl Into<Tensor>) -> BarOpt {
+        bar_xy(self, x, height)
+    }
+
+    pub fn barh(&mut self, data: impl Into<Tensor>) -> BarOpt {
+        barh(self, data)
+    }
+
+    pub fn barh_xy(&mut self, y: impl IntoAxisData, width: impl Into<Tensor>) -> BarOpt {
+        barh_xy(self, y, width)
+    }
+
+    pub fn bar_grouped(&mut self, data: impl Into<Tensor>) -> BarGroupOpt {
+        bar_grouped(self, data)
+    }
+
+    pub fn bar_stacked(&mut self, data: impl Into<Tensor>) -> BarGroupOpt {
+        bar_stacked(self, data)
+    }
+}
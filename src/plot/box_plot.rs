@@ -1,36 +1,117 @@
 use std::cmp::Ordering;
 
 use essay_graphics::api::{
-    renderer::{Canvas, Renderer, Result}, Bounds, Color, Path, PathOpt, Point
+    renderer::{Canvas, Renderer, Result}, Bounds, Color, Path, PathCode, PathOpt, Point
 };
 use essay_tensor::tensor::{IntoTensorList, Tensor};
 
 use crate::{
     artist::{
         paths::{self}, Artist, ArtistDraw, ArtistView, Markers, PathCollection, Stale,
-    }, 
-    chart::{Chart, Data, LegendHandler}, 
+    },
+    chart::{Chart, Data, IntoAxisData, LegendHandler},
     config::{ConfigArc, PathStyle},
-    data_artist_option_struct, path_style_options, transform::ToCanvas 
+    data_artist_option_struct, palette::Palette, path_style_options, transform::ToCanvas
 };
 
+use super::stats::percentile;
+
 pub fn box_plot(
-    chart: &mut Chart, 
-    data: impl IntoTensorList<f32>, 
+    chart: &mut Chart,
+    data: impl IntoTensorList<f32>,
 ) -> BoxPlotOpt {
     let mut vec = Vec::new();
     data.into_list(&mut vec);
 
-    let plot = BoxPlot::new(vec);
+    let plot = BoxPlot::new(vec, None);
 
     chart.artist(plot)
 }
 
+///
+/// Box plots of each tensor at the given x positions or categories.
+///
+pub fn box_plot_xy(
+    chart: &mut Chart,
+    x: impl IntoAxisData,
+    data: impl IntoTensorList<f32>,
+) -> BoxPlotOpt {
+    let x = chart.x_values(x);
+
+    box_plot_at(chart, x, data)
+}
+
+///
+/// Horizontal box plots of each tensor at the given y positions or
+/// categories.
+///
+pub fn box_ploth_xy(
+    chart: &mut Chart,
+    y: impl IntoAxisData,
+    data: impl IntoTensorList<f32>,
+) -> BoxPlotOpt {
+    let y = chart.y_values(y);
+
+    let mut plot = box_plot_at(chart, y, data);
+    plot.vert(false);
+
+    plot
+}
+
+fn box_plot_at(
+    chart: &mut Chart,
+    positions: Tensor,
+    data: impl IntoTensorList<f32>,
+) -> BoxPlotOpt {
+    let mut vec = Vec::new();
+    data.into_list(&mut vec);
+
+    // extra positions or data without a partner are dropped
+    let len = positions.len().min(vec.len());
+    vec.truncate(len);
+
+    let plot = BoxPlot::new(vec, Some(Vec::from(&positions.as_slice()[..len])));
+
+    chart.artist(plot)
+}
+
+///
+/// Whisker extent: data within a multiple of the interquartile range past
+/// the box, or fixed percentiles.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Whisker {
+    Iqr(f32),
+    Percentile(f32, f32),
+}
+
+impl From<f32> for Whisker {
+    fn from(value: f32) -> Self {
+        Whisker::Iqr(value)
+    }
+}
+
+impl From<(f32, f32)> for Whisker {
+    fn from(value: (f32, f32)) -> Self {
+        Whisker::Percentile(value.0, value.1)
+    }
+}
+
 pub struct BoxPlot {
-    whisker: f32,
-    is_draw_outliers: bool,
+    values: Vec<Tensor>,
+
+    whisker: Whisker,
+    is_show_fliers: bool,
+    is_show_means: bool,
+    is_notch: bool,
+    n_bootstrap: usize,
+    is_vertical: bool,
 
-    data: Vec<BoxPlotData>,
+    // none for an item without finite data, which has no box
+    data: Vec<Option<BoxPlotData>>,
+    positions: Option<Vec<f32>>,
+    widths: Option<Vec<f32>>,
+    colors: Option<Palette>,
 
     style: PathStyle,
 
@@ -39,16 +120,27 @@ pub struct BoxPlot {
 }
 
 impl BoxPlot {
-    fn new(data: Vec<Tensor>) -> Self {
+    const WIDTH : f32 = 0.5;
+
+    fn new(data: Vec<Tensor>, positions: Option<Vec<f32>>) -> Self {
         let style = PathStyle::new();
 
         let mut box_plot = Self {
-            whisker: 1.5,
-            is_draw_outliers: true,
+            values: Vec::new(),
+
+            whisker: Whisker::Iqr(1.5),
+            is_show_fliers: true,
+            is_show_means: false,
+            is_notch: false,
+            n_bootstrap: 1000,
+            is_vertical: true,
 
             style,
 
             data: Default::default(),
+            positions,
+            widths: None,
+            colors: None,
 
             stale: Stale::stale(),
             draw: None,
@@ -60,15 +152,79 @@ impl BoxPlot {
     }
 
     fn set_data(&mut self, data: Vec<Tensor>) {
-        self.data = data.iter().enumerate()
-            .map(|(i, item)| {
-                BoxPlotData::new(&self, (i + 1) as f32, item)
-            }).collect();
-        self.stale = Stale::stale();
+        self.values = data;
+        self.update_data();
+    }
+
+    fn update_data(&mut self) {
+        self.data = self.values.iter()
+            .map(|item| BoxPlotData::new(&self, item))
+            .collect();
+
+        self.draw = None;
+    }
+
+    fn position(&self, i: usize) -> f32 {
+        match &self.positions {
+            Some(positions) if i < positions.len() => positions[i],
+            _ => (i + 1) as f32,
+        }
+    }
+
+    fn width(&self, i: usize) -> f32 {
+        match &self.widths {
+            Some(widths) if widths.len() == 1 => widths[0],
+            Some(widths) if i < widths.len() => widths[i],
+            _ => Self::WIDTH,
+        }
+    }
+
+    fn orient(&self, x: f32, y: f32) -> Point {
+        if self.is_vertical {
+            Point(x, y)
+        } else {
+            Point(y, x)
+        }
+    }
+
+    fn line(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> Path<Data> {
+        Path::new(vec![
+            PathCode::MoveTo(self.orient(x0, y0)),
+            PathCode::LineTo(self.orient(x1, y1)),
+        ])
+    }
+
+    fn box_path(&self, x: f32, w: f32, data: &BoxPlotData) -> Path<Data> {
+        let [_, q1, median, q3, _] = data.quartiles;
+
+        let points = match data.notch {
+            Some((lo, hi)) if self.is_notch => vec![
+                (x - w, q1), (x + w, q1), (x + w, lo), (x + 0.5 * w, median),
+                (x + w, hi), (x + w, q3), (x - w, q3), (x - w, hi),
+                (x - 0.5 * w, median), (x - w, lo),
+            ],
+            _ => {
+                return paths::rect::<Data>(self.orient(x - w, q1), self.orient(x + w, q3));
+            }
+        };
+
+        let n = points.len();
+
+        Path::new(points.iter().enumerate().map(|(i, (x, y))| {
+            let point = self.orient(*x, *y);
+
+            if i == 0 {
+                PathCode::MoveTo(point)
+            } else if i == n - 1 {
+                PathCode::ClosePoly(point)
+            } else {
+                PathCode::LineTo(point)
+            }
+        }).collect())
     }
 
     fn resize(
-        &mut self, 
+        &mut self,
         to_canvas: &ToCanvas<Data>,
     ) {
         if self.stale != to_canvas.stale() || self.draw.is_none() {
@@ -76,36 +232,37 @@ impl BoxPlot {
 
             let draw_vec = self.data.iter()
                 .enumerate()
+                .filter_map(|(i, data)| data.as_ref().map(|data| (i, data)))
                 .map(|(i, data)| {
-                    let x = (i + 1) as f32;
-                    let box_w = 0.25;
-                    let path_box = paths::rect::<Data>(
-                        Point(x - box_w, data.quartiles[1]),
-                        Point(x + box_w, data.quartiles[3])
-                    );
-                    
-                    let path_median = Path::<Data>::move_to(x - box_w, data.quartiles[2])
-                        .line_to(x + box_w, data.quartiles[2])
-                        .to_path();
-
-                    let w = 0.1;
-                    let path_bottom = Path::<Data>::move_to(x - w, data.quartiles[0])
-                        .line_to(x + w, data.quartiles[0])
-                        .to_path();
-                    let path_bottom2 = Path::<Data>::move_to(x, data.quartiles[0])
-                        .line_to(x, data.quartiles[1])
-                        .to_path();
-                    let path_top = Path::<Data>::move_to(x - w, data.quartiles[4])
-                        .line_to(x + w, data.quartiles[4])
-                        .to_path();
-                    let path_top2 = Path::<Data>::move_to(x, data.quartiles[4])
-                        .line_to(x, data.quartiles[3])
-                        .to_path();
-
-                    let outliers = if data.outliers.len() > 0 {
+                    let x = self.position(i);
+                    let box_w = 0.5 * self.width(i);
+                    let [bottom, q1, median, q3, top] = data.quartiles;
+
+                    let path_box = self.box_path(x, box_w, data);
+
+                    let median_w = if self.is_notch && data.notch.is_some() {
+                        0.5 * box_w
+                    } else {
+                        box_w
+                    };
+
+                    let path_median = self.line(x - median_w, median, x + median_w, median);
+
+                    let w = 0.4 * box_w;
+                    let path_bottom = self.line(x - w, bottom, x + w, bottom);
+                    let path_bottom2 = self.line(x, bottom, x, q1);
+                    let path_top = self.line(x - w, top, x + w, top);
+                    let path_top2 = self.line(x, top, x, q3);
+
+                    let outliers = if self.is_show_fliers && data.outliers.len() > 0 {
                         let marker = Markers::Circle.get_scaled_path(8.);
 
-                        let xy = to_canvas.transform_tensor(&data.outliers);
+                        let xy: Vec<[f32; 2]> = data.outliers.iter().map(|y| {
+                            let Point(x, y) = self.orient(x, *y);
+                            [x, y]
+                        }).collect();
+
+                        let xy = to_canvas.transform_tensor(&Tensor::from(xy));
 
                         let mut scatter = PathCollection::new(marker, xy);
                         scatter.style_mut().face_color(Color::none());
@@ -117,7 +274,24 @@ impl BoxPlot {
                         None
                     };
 
+                    let mean = if self.is_show_means {
+                        let marker = Markers::TriangleUp.get_scaled_path(6.);
+
+                        let Point(x, y) = self.orient(x, data.mean);
+                        let xy = to_canvas.transform_tensor(&Tensor::from(vec![[x, y]]));
+
+                        let mut scatter = PathCollection::new(marker, xy);
+                        scatter.style_mut().face_color("green");
+                        scatter.style_mut().edge_color("green");
+
+                        Some(scatter)
+                    } else {
+                        None
+                    };
+
                     BoxDraw {
+                        index: i,
+
                         path_box: to_canvas.transform_path(&path_box),
 
                         path_median: to_canvas.transform_path(&path_median),
@@ -128,6 +302,7 @@ impl BoxPlot {
                         path_bottom2: to_canvas.transform_path(&path_bottom2),
 
                         outliers,
+                        mean,
                     }
                 }
             ).collect::<Vec<BoxDraw>>();
@@ -141,21 +316,36 @@ impl ArtistDraw<Data> for BoxPlot {
     fn bounds(&mut self) -> Bounds<Data> {
         let (mut min, mut max) = (f32::MAX, f32::MIN);
 
-        for item in &self.data {
+        for item in self.data.iter().flatten() {
             min = min.min(item.quartiles[0]);
             max = max.max(item.quartiles[4]);
 
-            if self.is_draw_outliers {
+            if self.is_show_fliers {
                 min = min.min(item.min);
                 max = max.max(item.max);
             }
         }
 
-        Bounds::new(Point(0.5, min), Point(self.data.len() as f32 + 0.5, max))
+        if min > max {
+            return Bounds::none();
+        }
+
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+
+        for i in 0..self.data.len() {
+            x_min = x_min.min(self.position(i) - 0.5);
+            x_max = x_max.max(self.position(i) + 0.5);
+        }
+
+        if self.is_vertical {
+            Bounds::new(Point(x_min, min), Point(x_max, max))
+        } else {
+            Bounds::new(Point(min, x_min), Point(max, x_max))
+        }
     }
 
     fn draw(
-        &mut self, 
+        &mut self,
         ui: &mut dyn Renderer,
         to_canvas: &ToCanvas<Data>,
         base_style: &dyn PathOpt,
@@ -170,8 +360,16 @@ impl ArtistDraw<Data> for BoxPlot {
                 median_style.line_width(line_width);
             }
 
-            for box_draw in box_draw_list {
-                ui.draw_path(&box_draw.path_box, &style)?;
+            let n = self.data.len();
+
+            for box_draw in box_draw_list.iter_mut() {
+                let mut box_style = PathStyle::new();
+
+                if let Some(colors) = &self.colors {
+                    box_style.face_color(colors.color(box_draw.index, n));
+                }
+
+                ui.draw_path(&box_draw.path_box, &box_style.push(&style))?;
 
                 let median_style = median_style.push(base_style);
                 ui.draw_path(&box_draw.path_median, &median_style)?;
@@ -184,6 +382,10 @@ impl ArtistDraw<Data> for BoxPlot {
                 if let Some(outliers) = &mut box_draw.outliers {
                     outliers.draw(ui, to_canvas, base_style)?;
                 }
+
+                if let Some(mean) = &mut box_draw.mean {
+                    mean.draw(ui, to_canvas, base_style)?;
+                }
             }
         }
 
@@ -195,71 +397,134 @@ impl ArtistDraw<Data> for BoxPlot {
 
 struct BoxPlotData {
     quartiles: [f32; 5],
+    mean: f32,
+    notch: Option<(f32, f32)>,
 
     min: f32,
     max: f32,
-    outliers: Tensor,
+    outliers: Vec<f32>,
 }
 
 impl BoxPlotData {
-    fn new(box_plot: &BoxPlot, x: f32, data: &Tensor) -> Self {
-        let mut data = Vec::from(data.as_slice());
-        data.sort_by(|a, b| {
-            if a == b {
-                Ordering::Equal
-            } else if a < b {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
-        });
+    ///
+    /// Statistics of the finite values of `data`, or none when there are
+    /// none.
+    ///
+    fn new(box_plot: &BoxPlot, data: &Tensor) -> Option<Self> {
+        let mut data: Vec<f32> = data.as_slice().iter()
+            .map(|value| *value)
+            .filter(|value| value.is_finite())
+            .collect();
+
+        if data.len() == 0 {
+            return None;
+        }
+
+        data.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
 
         let len = data.len();
-        let mean = data[len / 2];
-        let first = data[len / 4];
-        let third = data[3 * len / 4];
+        let median = percentile(&data, 50.);
+        let first = percentile(&data, 25.);
+        let third = percentile(&data, 75.);
+
+        let mean = data.iter().sum::<f32>() / len as f32;
 
-        let bot_whisker = first - box_plot.whisker * (third - first);
-        let top_whisker = third + box_plot.whisker * (third - first);
+        let (bot_whisker, top_whisker) = match box_plot.whisker {
+            Whisker::Iqr(k) => (first - k * (third - first), third + k * (third - first)),
+            Whisker::Percentile(lo, hi) => (percentile(&data, lo), percentile(&data, hi)),
+        };
 
         let (mut bot, mut top) = (f32::MAX, f32::MIN);
         let (mut min, mut max) = (f32::MAX, f32::MIN);
 
-        let mut outliers = Vec::<[f32; 2]>::new();
+        let mut outliers = Vec::<f32>::new();
+
+        for value in &data {
+            let value = *value;
 
-        for value in data {
             if value <= top_whisker {
                 top = top.max(value);
             } else {
-                outliers.push([x, value]);
+                outliers.push(value);
             }
 
             if bot_whisker <= value {
                 bot = bot.min(value);
             } else {
-                outliers.push([x, value]);
+                outliers.push(value);
             }
 
             min = min.min(value);
             max = max.max(value);
         }
-        
-        Self {
+
+        let notch = if box_plot.is_notch {
+            Some(median_ci(&data, box_plot.n_bootstrap))
+        } else {
+            None
+        };
+
+        Some(Self {
             quartiles: [
                 bot,
                 first,
-                mean,
+                median,
                 third,
                 top,
             ],
+            mean,
+            notch,
             min,
             max,
-            outliers: outliers.into(),
-        }
+            outliers,
+        })
     }
 }
 
+///
+/// 95% confidence interval of the median of sorted data, from bootstrap
+/// resampling, or from the Gaussian approximation when `n_bootstrap` is 0.
+///
+fn median_ci(sorted: &[f32], n_bootstrap: usize) -> (f32, f32) {
+    let median = percentile(sorted, 50.);
+
+    if n_bootstrap == 0 || sorted.len() < 2 {
+        let iqr = percentile(sorted, 75.) - percentile(sorted, 25.);
+        let delta = 1.57 * iqr / (sorted.len() as f32).sqrt();
+
+        return (median - delta, median + delta);
+    }
+
+    // deterministic generator so redraws don't move the notches
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+
+    let n = sorted.len();
+    let mut sample = vec![0.; n];
+
+    let mut medians: Vec<f32> = (0..n_bootstrap).map(|_| {
+        for value in sample.iter_mut() {
+            *value = sorted[(next() % n as u64) as usize];
+        }
+
+        sample.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        percentile(&sample, 50.)
+    }).collect();
+
+    medians.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    (percentile(&medians, 2.5), percentile(&medians, 97.5))
+}
+
 struct BoxDraw {
+    index: usize,
+
     path_box: Path<Canvas>,
 
     path_median: Path<Canvas>,
@@ -270,6 +535,7 @@ struct BoxDraw {
     path_bottom2: Path<Canvas>,
 
     outliers: Option<PathCollection>,
+    mean: Option<PathCollection>,
 }
 
 impl Artist<Data> for BoxPlot {
@@ -302,4 +568,157 @@ impl BoxPlotOpt {
 
         self
     }
+
+    pub fn positions(&mut self, positions: impl Into<Tensor>) -> &mut Self {
+        let positions: Tensor = positions.into();
+
+        self.write(|plot| {
+            plot.positions = Some(Vec::from(positions.as_slice()));
+            plot.draw = None;
+        });
+
+        self
+    }
+
+    ///
+    /// Box widths in data units, either one for all boxes or one per box.
+    ///
+    pub fn widths(&mut self, widths: impl Into<Tensor>) -> &mut Self {
+        let widths: Tensor = widths.into();
+
+        self.write(|plot| {
+            plot.widths = Some(Vec::from(widths.as_slice()));
+            plot.draw = None;
+        });
+
+        self
+    }
+
+    ///
+    /// Whisker extent, e.g. `1.5` times the interquartile range or
+    /// `(5., 95.)` percentiles.
+    ///
+    pub fn whisker(&mut self, whisker: impl Into<Whisker>) -> &mut Self {
+        self.write(|plot| {
+            plot.whisker = whisker.into();
+            plot.update_data();
+        });
+
+        self
+    }
+
+    ///
+    /// Notches the boxes at the 95% confidence interval of the median.
+    ///
+    pub fn notch(&mut self, is_notch: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_notch = is_notch;
+            plot.update_data();
+        });
+
+        self
+    }
+
+    ///
+    /// Bootstrap resamples for the notch interval. Zero uses the Gaussian
+    /// approximation instead.
+    ///
+    pub fn bootstrap(&mut self, n_bootstrap: usize) -> &mut Self {
+        self.write(|plot| {
+            plot.n_bootstrap = n_bootstrap;
+            plot.update_data();
+        });
+
+        self
+    }
+
+    pub fn show_means(&mut self, is_show: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_show_means = is_show;
+            plot.draw = None;
+        });
+
+        self
+    }
+
+    pub fn show_fliers(&mut self, is_show: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_show_fliers = is_show;
+            plot.draw = None;
+        });
+
+        self
+    }
+
+    pub fn vert(&mut self, is_vertical: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_vertical = is_vertical;
+            plot.draw = None;
+        });
+
+        self
+    }
+
+    ///
+    /// Box face colors, one palette color per box.
+    ///
+    pub fn colors(&mut self, colors: impl Into<Palette>) -> &mut Self {
+        self.write(|plot| {
+            plot.colors = Some(colors.into());
+        });
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_graphics::api::{Bounds, Point};
+    use essay_tensor::{ten, tensor::Tensor};
+
+    use crate::artist::ArtistDraw;
+
+    use super::{median_ci, BoxPlot, BoxPlotData};
+
+    #[test]
+    fn box_plot_quartiles() {
+        let data = ten![4., 1., 3., 2.];
+        let box_plot = BoxPlot::new(vec![data.clone()], None);
+
+        let stats = BoxPlotData::new(&box_plot, &data).unwrap();
+
+        // interpolated like the notch's median
+        assert_eq!(stats.quartiles, [1., 1.75, 2.5, 3.25, 4.]);
+    }
+
+    #[test]
+    fn box_plot_non_finite() {
+        let data = ten![4., f32::NAN, 1., 3., f32::INFINITY, 2.];
+        let box_plot = BoxPlot::new(vec![data.clone()], None);
+
+        let stats = BoxPlotData::new(&box_plot, &data).unwrap();
+        assert_eq!(stats.quartiles, [1., 1.75, 2.5, 3.25, 4.]);
+
+        // an item without data has no box
+        let empty = Tensor::from(Vec::<f32>::new());
+        let mut box_plot = BoxPlot::new(vec![empty.clone(), data.clone()], None);
+
+        assert!(BoxPlotData::new(&box_plot, &empty).is_none());
+        assert!(BoxPlotData::new(&box_plot, &ten![f32::NAN]).is_none());
+        assert_eq!(box_plot.bounds(), Bounds::new(Point(0.5, 1.), Point(2.5, 4.)));
+    }
+
+    #[test]
+    fn box_plot_median_ci() {
+        let data: Vec<f32> = (0..100).map(|i| i as f32).collect();
+
+        // gaussian approximation: 1.57 * iqr / sqrt(n)
+        let (lo, hi) = median_ci(&data, 0);
+        assert!((lo - (49.5 - 1.57 * 49.5 / 10.)).abs() < 1e-3);
+        assert!((hi - (49.5 + 1.57 * 49.5 / 10.)).abs() < 1e-3);
+
+        let (lo, hi) = median_ci(&data, 1000);
+        assert!(lo < 49.5 && 49.5 < hi, "{} {}", lo, hi);
+        assert!(35. < lo && hi < 65., "{} {}", lo, hi);
+    }
 }
@@ -1,12 +1,82 @@
 use essay_tensor::tensor::Tensor;
 
-use crate::{chart::Chart, artist::Contour};
+use crate::{chart::Chart, artist::{Contour, ContourOpt}};
 
 pub fn contour(
     graph: &mut Chart, 
     data: impl Into<Tensor>,
-) {
+) -> ContourOpt {
     let contour = Contour::new(data);
     
-    graph.add_simple_artist(contour);
+    graph.artist(contour)
+}
+
+///
+/// Contour lines on explicit grid coordinates, as 1D `x` and `y` vectors
+/// or 2D grids matching the data's shape.
+///
+pub fn contour_xy(
+    graph: &mut Chart, 
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+    data: impl Into<Tensor>,
+) -> ContourOpt {
+    let mut contour = Contour::new(data);
+    contour.set_xy(&x.into(), &y.into());
+    
+    graph.artist(contour)
+}
+
+///
+/// Filled regions between consecutive contour levels.
+///
+pub fn contourf(
+    graph: &mut Chart, 
+    data: impl Into<Tensor>,
+) -> ContourOpt {
+    let mut contour = Contour::new(data);
+    contour.filled(true);
+    
+    graph.artist(contour)
+}
+
+pub fn contourf_xy(
+    graph: &mut Chart, 
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+    data: impl Into<Tensor>,
+) -> ContourOpt {
+    let mut contour = Contour::new(data);
+    contour.set_xy(&x.into(), &y.into());
+    contour.filled(true);
+    
+    graph.artist(contour)
+}
+
+impl Chart {
+    pub fn contour(&mut self, data: impl Into<Tensor>) -> ContourOpt {
+        contour(self, data)
+    }
+
+    pub fn contour_xy(
+        &mut self,
+        x: impl Into<Tensor>,
+        y: impl Into<Tensor>,
+        data: impl Into<Tensor>
+    ) -> ContourOpt {
+        contour_xy(self, x, y, data)
+    }
+
+    pub fn contourf(&mut self, data: impl Into<Tensor>) -> ContourOpt {
+        contourf(self, data)
+    }
+
+    pub fn contourf_xy(
+        &mut self,
+        x: impl Into<Tensor>,
+        y: impl Into<Tensor>,
+        data: impl Into<Tensor>
+    ) -> ContourOpt {
+        contourf_xy(self, x, y, data)
+    }
 }
@@ -0,0 +1,19 @@
+use essay_tensor::tensor::Tensor;
+
+use crate::{artist::{ErrorBar, ErrorBarOpt}, chart::Chart};
+
+pub fn errorbar(
+    chart: &mut Chart,
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+) -> ErrorBarOpt {
+    let errorbar = ErrorBar::from_xy(x, y);
+
+    chart.artist(errorbar)
+}
+
+impl Chart {
+    pub fn errorbar(&mut self, x: impl Into<Tensor>, y: impl Into<Tensor>) -> ErrorBarOpt {
+        errorbar(self, x, y)
+    }
+}
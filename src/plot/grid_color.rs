@@ -11,8 +11,33 @@ pub fn grid_color(
     graph.artist(colormesh)
 }
 
+///
+/// Color grid on explicit mesh coordinates, as 1D or 2D `x` and `y` with
+/// either the data's shape or one larger for cell corners.
+///
+pub fn grid_color_xy(
+    graph: &mut Chart,
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+    data: impl Into<Tensor>,
+) -> GridColorOpt {
+    let mut colormesh = GridColor::new(data);
+    colormesh.set_xy(&x.into(), &y.into());
+    
+    graph.artist(colormesh)
+}
+
 impl Chart {
     pub fn grid_color(&mut self, data: impl Into<Tensor>) -> GridColorOpt {
         grid_color(self, data)
     }
-}
\ No newline at end of file
+
+    pub fn grid_color_xy(
+        &mut self,
+        x: impl Into<Tensor>,
+        y: impl Into<Tensor>,
+        data: impl Into<Tensor>
+    ) -> GridColorOpt {
+        grid_color_xy(self, x, y, data)
+    }
+}
@@ -0,0 +1,366 @@
+use essay_graphics::api::{
+    renderer::{Canvas, Renderer, Result},
+    Bounds, Color, Path, PathCode, PathOpt, Point
+};
+use essay_tensor::tensor::Tensor;
+
+use crate::{
+    artist::{Artist, ArtistDraw, ArtistView, ColorbarHandler, Norm, Norms, PathCollection},
+    chart::{Chart, Data, LegendHandler},
+    config::{ConfigArc, PathStyle},
+    data_artist_option_struct, path_style_options,
+    palette::{ColorMap, EssayColors},
+    transform::ToCanvas
+};
+
+pub fn hexbin(
+    chart: &mut Chart,
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+    grid_size: usize,
+) -> HexBinOpt {
+    let plot = HexBin::new(x, y, grid_size);
+
+    chart.artist(plot)
+}
+
+///
+/// Counts of (x, y) points in a hexagonal grid, each cell colored by its
+/// count.
+///
+pub struct HexBin {
+    x: Tensor,
+    y: Tensor,
+
+    grid_size: usize,
+    min_count: usize,
+
+    norm: Norm,
+    color_map: ColorMap,
+    style: PathStyle,
+
+    cells: Vec<HexCell>,
+    spacing: (f32, f32),
+    extent: Bounds<Data>,
+
+    is_stale: bool,
+}
+
+impl HexBin {
+    fn new(x: impl Into<Tensor>, y: impl Into<Tensor>, grid_size: usize) -> Self {
+        let x = x.into();
+        let y = y.into();
+
+        assert!(x.rank() == 1, "hexbin requires 1D x {:?}", x.shape());
+        assert_eq!(x.len(), y.len(), "hexbin x and y must have the same length");
+        assert!(grid_size > 0, "hexbin grid_size must be positive");
+
+        Self {
+            x,
+            y,
+
+            grid_size,
+            min_count: 1,
+
+            norm: Norm::from(Norms::Linear),
+            color_map: EssayColors::Default.into(),
+            style: PathStyle::new(),
+
+            cells: Vec::new(),
+            spacing: (1., 1.),
+            extent: Bounds::none(),
+
+            is_stale: true,
+        }
+    }
+
+    fn update(&mut self) {
+        if ! self.is_stale {
+            return;
+        }
+
+        self.is_stale = false;
+
+        let (x, y) = (self.x.as_slice(), self.y.as_slice());
+
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+        let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+
+        for (x, y) in x.iter().zip(y) {
+            if x.is_finite() && y.is_finite() {
+                (x_min, x_max) = (x_min.min(*x), x_max.max(*x));
+                (y_min, y_max) = (y_min.min(*y), y_max.max(*y));
+            }
+        }
+
+        if x_min > x_max {
+            self.cells = Vec::new();
+            self.extent = Bounds::none();
+            return;
+        }
+
+        let n_x = self.grid_size;
+        let n_y = ((n_x as f32 / 3f32.sqrt()) as usize).max(1);
+
+        let (cells, spacing) = hex_cells(x, y, n_x, n_y, [x_min, x_max, y_min, y_max]);
+
+        let min_count = self.min_count as f32;
+        self.cells = cells.into_iter().filter(|c| c.count >= min_count).collect();
+        self.spacing = spacing;
+        self.extent = Bounds::new(Point(x_min, y_min), Point(x_max, y_max));
+
+        let counts: Vec<f32> = self.cells.iter().map(|c| c.count).collect();
+        self.norm.set_bounds(&Tensor::from(counts));
+    }
+
+    ///
+    /// Pointy-top hexagon for a cell, converted to canvas offsets around
+    /// the cell center.
+    ///
+    fn hex_path(&self, to_canvas: &ToCanvas<Data>, center: [f32; 2]) -> Path<Canvas> {
+        let (sx, sy) = self.spacing;
+
+        let unit = [[0.5, -0.5], [0.5, 0.5], [0., 1.], [-0.5, 0.5], [-0.5, -0.5], [0., -1.]];
+
+        let Point(cx, cy) = to_canvas.transform_point(Point(center[0], center[1]));
+
+        let mut codes = Vec::<PathCode>::new();
+
+        for (i, [dx, dy]) in unit.iter().enumerate() {
+            let Point(x, y) = to_canvas.transform_point(Point(
+                center[0] + dx * sx,
+                center[1] + dy * sy / 3.
+            ));
+
+            let point = Point(x - cx, y - cy);
+
+            codes.push(match i {
+                0 => PathCode::MoveTo(point),
+                5 => PathCode::ClosePoly(point),
+                _ => PathCode::LineTo(point),
+            });
+        }
+
+        Path::new(codes)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct HexCell {
+    center: [f32; 2],
+    count: f32,
+}
+
+///
+/// Bins points into two offset rectangular lattices whose union forms a
+/// hexagonal grid, keeping the nearer center using the hexagonal metric.
+/// Returns the non-empty cells and the lattice spacing.
+///
+fn hex_cells(
+    x: &[f32],
+    y: &[f32],
+    n_x: usize,
+    n_y: usize,
+    extent: [f32; 4],
+) -> (Vec<HexCell>, (f32, f32)) {
+    let [x_min, x_max, y_min, y_max] = extent;
+
+    let sx = if x_max > x_min { (x_max - x_min) / n_x as f32 } else { 1. };
+    let sy = if y_max > y_min { (y_max - y_min) / n_y as f32 } else { 1. };
+
+    let stride1 = n_x + 1;
+    let mut lattice1 = vec![0f32; (n_x + 1) * (n_y + 1)];
+    let mut lattice2 = vec![0f32; n_x * n_y];
+
+    for (x, y) in x.iter().zip(y) {
+        if ! x.is_finite() || ! y.is_finite() {
+            continue;
+        }
+
+        let px = (x - x_min) / sx;
+        let py = (y - y_min) / sy;
+
+        if px < 0. || py < 0. || px > n_x as f32 || py > n_y as f32 {
+            continue;
+        }
+
+        let (ix1, iy1) = (px.round(), py.round());
+        let (ix2, iy2) = (px.floor(), py.floor());
+
+        let d1 = (px - ix1).powi(2) + 3. * (py - iy1).powi(2);
+        let d2 = (px - ix2 - 0.5).powi(2) + 3. * (py - iy2 - 0.5).powi(2);
+
+        if d1 <= d2 {
+            lattice1[iy1 as usize * stride1 + ix1 as usize] += 1.;
+        } else {
+            let ix2 = (ix2 as usize).min(n_x - 1);
+            let iy2 = (iy2 as usize).min(n_y - 1);
+
+            lattice2[iy2 * n_x + ix2] += 1.;
+        }
+    }
+
+    let mut cells = Vec::new();
+
+    for (k, count) in lattice1.iter().enumerate() {
+        if *count > 0. {
+            let (i, j) = (k % stride1, k / stride1);
+
+            cells.push(HexCell {
+                center: [x_min + i as f32 * sx, y_min + j as f32 * sy],
+                count: *count,
+            });
+        }
+    }
+
+    for (k, count) in lattice2.iter().enumerate() {
+        if *count > 0. {
+            let (i, j) = (k % n_x, k / n_x);
+
+            cells.push(HexCell {
+                center: [x_min + (i as f32 + 0.5) * sx, y_min + (j as f32 + 0.5) * sy],
+                count: *count,
+            });
+        }
+    }
+
+    (cells, (sx, sy))
+}
+
+impl ArtistDraw<Data> for HexBin {
+    fn bounds(&mut self) -> Bounds<Data> {
+        self.update();
+
+        self.extent.clone()
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        self.update();
+
+        if self.cells.len() == 0 {
+            return Ok(());
+        }
+
+        let path = self.hex_path(to_canvas, self.cells[0].center);
+
+        let xy: Vec<[f32; 2]> = self.cells.iter().map(|c| c.center).collect();
+        let xy = to_canvas.transform_tensor(&Tensor::from(xy));
+
+        let colors: Vec<Color> = self.cells.iter().map(|c| {
+            self.color_map.map(self.norm.norm(c.count).clamp(0., 1.))
+        }).collect();
+
+        let mut collection = PathCollection::new(path, xy);
+        collection.set_colors(colors);
+
+        let style = self.style.push(style);
+
+        collection.draw(renderer, to_canvas, &style)
+    }
+}
+
+impl Artist<Data> for HexBin {
+    type Opt = HexBinOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.style = PathStyle::from_config(cfg, "hexbin");
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, HexBin>) -> Self::Opt {
+        HexBinOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        Some(ColorbarHandler::from_norm(self.color_map.clone(), &self.norm))
+    }
+}
+
+data_artist_option_struct!(HexBinOpt, HexBin);
+
+impl HexBinOpt {
+    path_style_options!(style);
+
+    ///
+    /// Number of hexagons along x. The y count keeps the cells regular.
+    ///
+    pub fn grid_size(&mut self, grid_size: usize) -> &mut Self {
+        assert!(grid_size > 0, "hexbin grid_size must be positive");
+
+        self.write(|plot| {
+            plot.grid_size = grid_size;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Cells with fewer points are not drawn.
+    ///
+    pub fn min_count(&mut self, min_count: usize) -> &mut Self {
+        self.write(|plot| {
+            plot.min_count = min_count;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn norm(&mut self, norm: impl Into<Norm>) -> &mut Self {
+        self.write(|plot| {
+            plot.norm = norm.into();
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn color_map(&mut self, cmap: impl Into<ColorMap>) -> &mut Self {
+        self.write(|plot| {
+            plot.color_map = cmap.into();
+        });
+
+        self
+    }
+}
+
+impl Chart {
+    pub fn hexbin(
+        &mut self,
+        x: impl Into<Tensor>,
+        y: impl Into<Tensor>,
+        grid_size: usize
+    ) -> HexBinOpt {
+        hexbin(self, x, y, grid_size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hex_cells, HexCell};
+
+    #[test]
+    fn hexbin_cells() {
+        let x = [0., 1., 0.5, 0.25, 0.3];
+        let y = [0., 1., 0.5, 0.25, 0.2];
+
+        let (cells, spacing) = hex_cells(&x, &y, 2, 2, [0., 1., 0., 1.]);
+
+        assert_eq!(spacing, (0.5, 0.5));
+        assert_eq!(cells, vec![
+            HexCell { center: [0., 0.], count: 1. },
+            HexCell { center: [0.5, 0.5], count: 1. },
+            HexCell { center: [1., 1.], count: 1. },
+            HexCell { center: [0.25, 0.25], count: 2. },
+        ]);
+    }
+}
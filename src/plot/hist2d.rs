@@ -0,0 +1,280 @@
+use essay_graphics::api::{
+    renderer::{Renderer, Result},
+    Bounds, PathOpt
+};
+use essay_tensor::{ten, tensor::Tensor};
+
+use crate::{
+    artist::{bin_edges, bin_index, Artist, ArtistDraw, ArtistView, ColorbarHandler, GridColor, Norm},
+    chart::{Chart, Data, LegendHandler},
+    config::ConfigArc,
+    data_artist_option_struct,
+    palette::ColorMap,
+    transform::ToCanvas
+};
+
+pub fn hist2d(
+    chart: &mut Chart,
+    x: impl Into<Tensor>,
+    y: impl Into<Tensor>,
+) -> Hist2dOpt {
+    let plot = Hist2d::new(x, y);
+
+    chart.artist(plot)
+}
+
+///
+/// Counts of (x, y) points in a rectangular grid of bins, drawn as a
+/// color grid.
+///
+pub struct Hist2d {
+    x: Tensor,
+    y: Tensor,
+    weights: Option<Tensor>,
+
+    n_bins: (usize, usize),
+    range: Option<([f32; 2], [f32; 2])>,
+    is_density: bool,
+
+    grid: GridColor,
+
+    is_stale: bool,
+}
+
+impl Hist2d {
+    fn new(x: impl Into<Tensor>, y: impl Into<Tensor>) -> Self {
+        let x = x.into();
+        let y = y.into();
+
+        assert!(x.rank() == 1, "hist2d requires 1D x {:?}", x.shape());
+        assert_eq!(x.len(), y.len(), "hist2d x and y must have the same length");
+
+        Self {
+            x,
+            y,
+            weights: None,
+
+            n_bins: (10, 10),
+            range: None,
+            is_density: false,
+
+            grid: GridColor::new(ten![[0.]]),
+
+            is_stale: true,
+        }
+    }
+
+    fn update(&mut self) {
+        if ! self.is_stale {
+            return;
+        }
+
+        self.is_stale = false;
+
+        let (x_range, y_range) = match self.range {
+            Some(([x0, x1], [y0, y1])) => (Some((x0, x1)), Some((y0, y1))),
+            None => (None, None),
+        };
+
+        let x_edges = bin_edges(&[self.x.as_slice()], self.n_bins.0, x_range);
+        let y_edges = bin_edges(&[self.y.as_slice()], self.n_bins.1, y_range);
+
+        let counts = hist2d_counts(
+            self.x.as_slice(),
+            self.y.as_slice(),
+            self.weights.as_ref().map(|w| w.as_slice()),
+            &x_edges,
+            &y_edges,
+            self.is_density,
+        );
+
+        let (nx, ny) = (x_edges.len() - 1, y_edges.len() - 1);
+
+        self.grid.set_data(Tensor::from(counts).reshape([ny, nx]));
+        self.grid.set_xy(&Tensor::from(x_edges), &Tensor::from(y_edges));
+    }
+}
+
+///
+/// Row-major `[ny, nx]` counts, with row `j` covering `y_edges[j]` to
+/// `y_edges[j + 1]`. Density divides by the total and each bin's area.
+///
+fn hist2d_counts(
+    x: &[f32],
+    y: &[f32],
+    weights: Option<&[f32]>,
+    x_edges: &[f32],
+    y_edges: &[f32],
+    is_density: bool,
+) -> Vec<f32> {
+    let (nx, ny) = (x_edges.len() - 1, y_edges.len() - 1);
+
+    let mut counts = vec![0.; nx * ny];
+
+    for (k, (x, y)) in x.iter().zip(y).enumerate() {
+        if let (Some(i), Some(j)) = (bin_index(x_edges, *x), bin_index(y_edges, *y)) {
+            counts[j * nx + i] += weights.map_or(1., |w| w[k]);
+        }
+    }
+
+    if is_density {
+        let total: f32 = counts.iter().sum();
+
+        if total > 0. {
+            for j in 0..ny {
+                for i in 0..nx {
+                    let area = (x_edges[i + 1] - x_edges[i]) * (y_edges[j + 1] - y_edges[j]);
+
+                    counts[j * nx + i] /= total * area;
+                }
+            }
+        }
+    }
+
+    counts
+}
+
+impl ArtistDraw<Data> for Hist2d {
+    fn bounds(&mut self) -> Bounds<Data> {
+        self.update();
+
+        self.grid.bounds()
+    }
+
+    fn draw(
+        &mut self,
+        renderer: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        self.update();
+
+        self.grid.draw(renderer, to_canvas, style)
+    }
+}
+
+impl Artist<Data> for Hist2d {
+    type Opt = Hist2dOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.grid.config(cfg);
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, Hist2d>) -> Self::Opt {
+        Hist2dOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+
+    fn get_colorbar(&self) -> Option<ColorbarHandler> {
+        self.grid.get_colorbar()
+    }
+}
+
+data_artist_option_struct!(Hist2dOpt, Hist2d);
+
+impl Hist2dOpt {
+    ///
+    /// Number of bins along x and y.
+    ///
+    pub fn n_bins(&mut self, nx: usize, ny: usize) -> &mut Self {
+        assert!(nx > 0 && ny > 0);
+
+        self.write(|plot| {
+            plot.n_bins = (nx, ny);
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Bin range as `[x_min, x_max]` and `[y_min, y_max]`. Points outside
+    /// are ignored.
+    ///
+    pub fn range(&mut self, x: [f32; 2], y: [f32; 2]) -> &mut Self {
+        assert!(x[0] < x[1] && y[0] < y[1], "hist2d range requires min < max");
+
+        self.write(|plot| {
+            plot.range = Some((x, y));
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn weights(&mut self, weights: impl Into<Tensor>) -> &mut Self {
+        let weights = weights.into();
+
+        self.write(|plot| {
+            assert_eq!(weights.len(), plot.x.len(), "hist2d weights must match the data");
+
+            plot.weights = Some(weights);
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn density(&mut self, is_density: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_density = is_density;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn norm(&mut self, norm: impl Into<Norm>) -> &mut Self {
+        self.write(|plot| {
+            plot.grid.norm(norm);
+        });
+
+        self
+    }
+
+    pub fn color_map(&mut self, cmap: impl Into<ColorMap>) -> &mut Self {
+        self.write(|plot| {
+            plot.grid.color_map(cmap);
+        });
+
+        self
+    }
+}
+
+impl Chart {
+    pub fn hist2d(&mut self, x: impl Into<Tensor>, y: impl Into<Tensor>) -> Hist2dOpt {
+        hist2d(self, x, y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::hist2d_counts;
+
+    #[test]
+    fn hist2d_bin_counts() {
+        let x_edges = [0., 1., 2.];
+        let y_edges = [0., 1., 3.];
+
+        let x = [0.5, 1.5, 1.5, 0.5, 5.];
+        let y = [0.5, 0.5, 2., 2., 0.5];
+
+        assert_eq!(
+            hist2d_counts(&x, &y, None, &x_edges, &y_edges, false),
+            vec![1., 1., 1., 1.]
+        );
+
+        assert_eq!(
+            hist2d_counts(&x, &y, Some(&[1., 2., 3., 4., 5.]), &x_edges, &y_edges, false),
+            vec![1., 2., 4., 3.]
+        );
+
+        assert_eq!(
+            hist2d_counts(&x, &y, None, &x_edges, &y_edges, true),
+            vec![0.25, 0.25, 0.125, 0.125]
+        );
+    }
+}
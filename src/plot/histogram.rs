@@ -1,4 +1,4 @@
-use essay_tensor::tensor::Tensor;
+use essay_tensor::tensor::{IntoTensorList, Tensor};
 
 use crate::{chart::Chart, artist::{Histogram, HistogramOpt}};
 
@@ -11,8 +11,28 @@ pub fn hist(
     graph.artist(histogram)
 }
 
+///
+/// Histogram of several datasets over shared bins, drawn side by side or
+/// stacked.
+///
+pub fn hist_list(
+    graph: &mut Chart,
+    data: impl IntoTensorList<f32>,
+) -> HistogramOpt {
+    let mut vec = Vec::new();
+    data.into_list(&mut vec);
+
+    let histogram = Histogram::from_list(vec);
+    
+    graph.artist(histogram)
+}
+
 impl Chart {
     pub fn hist(&mut self, data: impl Into<Tensor>) -> HistogramOpt {
         hist(self, data)
     }
-}
\ No newline at end of file
+
+    pub fn hist_list(&mut self, data: impl IntoTensorList<f32>) -> HistogramOpt {
+        hist_list(self, data)
+    }
+}
@@ -3,14 +3,17 @@ use essay_tensor::tensor::{IntoTensorList, Tensor};
 
 use crate::{
     artist::{patch::PathPatch, ContainerOpt, HorizontalLineOpt, ImageOpt, Lines2d, LinesOpt, TextOpt}, 
-    chart::{Chart, PlotOpt, PolarChart}, plot::box_plot::BoxPlotOpt
+    chart::{Chart, IntoAxisData, PlotOpt, PolarChart}, plot::box_plot::BoxPlotOpt
 };
 
 mod bar;
 mod box_plot;
 mod contour;
+mod errorbar;
 mod fill_between;
 mod grid_color;
+mod hexbin;
+mod hist2d;
 mod histogram;
 mod image;
 mod matshow;
@@ -21,24 +24,34 @@ mod radar;
 mod scatter;
 mod span;
 mod specgram;
+mod stats;
 mod stem;
 mod text;
 mod triplot;
 mod tricontour;
+mod tripcolor;
+mod violin_plot;
 
-pub use bar::bar;
+pub use bar::{bar, bar_xy, barh, barh_xy, bar_grouped, bar_stacked, BarGroupOpt};
 
-pub use box_plot::box_plot;
+pub use box_plot::{box_plot, box_plot_xy, box_ploth_xy, Whisker};
 
-pub use grid_color::grid_color;
+pub use grid_color::{grid_color, grid_color_xy};
 
-pub use contour::contour;
+pub use contour::{contour, contour_xy, contourf, contourf_xy};
 
-pub use tricontour::tricontour;
+pub use errorbar::errorbar;
+
+pub use tricontour::{tricontour, tricontourf};
+pub use tripcolor::tripcolor;
 
 pub use fill_between::fill_between;
 
-pub use histogram::hist;
+pub use hexbin::{hexbin, HexBin, HexBinOpt};
+
+pub use hist2d::{hist2d, Hist2d, Hist2dOpt};
+
+pub use histogram::{hist, hist_list};
 
 pub use matshow::matshow;
 
@@ -52,7 +65,7 @@ pub use span::hline;
 
 pub use stem::stem;
 
-pub use text::text;
+pub use text::{annotate, text};
 
 pub use pie::pie;
 
@@ -62,6 +75,8 @@ pub use scatter::{
 
 pub use triplot::triplot;
 
+pub use violin_plot::{violin_plot, Bandwidth, ViolinInner, ViolinPlotOpt};
+
 use self::specgram::SpecGramOpt;
 
 impl Chart {
@@ -88,7 +103,7 @@ impl Chart {
 
     pub fn plot(
         &mut self, 
-        x: impl Into<Tensor>,
+        x: impl IntoAxisData,
         y: impl Into<Tensor>,
     ) -> LinesOpt {
         plot::plot(self, x, y)
@@ -110,7 +125,7 @@ impl Chart {
 
     pub fn scatter(
         &mut self, 
-        x: impl Into<Tensor>,
+        x: impl IntoAxisData,
         y: impl Into<Tensor>,
     ) -> ScatterOpt {
         scatter::scatter(self, x, y)
@@ -137,6 +152,35 @@ impl Chart {
     ) -> BoxPlotOpt {
         box_plot::box_plot(self, data)
     }
+
+    ///
+    /// Box plots at the given x positions or categories.
+    ///
+    pub fn box_plot_xy(
+        &mut self,
+        x: impl IntoAxisData,
+        data: impl IntoTensorList<f32>,
+    ) -> BoxPlotOpt {
+        box_plot::box_plot_xy(self, x, data)
+    }
+
+    ///
+    /// Horizontal box plots at the given y positions or categories.
+    ///
+    pub fn box_ploth_xy(
+        &mut self,
+        y: impl IntoAxisData,
+        data: impl IntoTensorList<f32>,
+    ) -> BoxPlotOpt {
+        box_plot::box_ploth_xy(self, y, data)
+    }
+
+    pub fn violin_plot(
+        &mut self,
+        data: impl IntoTensorList<f32>,
+    ) -> ViolinPlotOpt {
+        violin_plot::violin_plot(self, data)
+    }
 }
 
 
@@ -1,12 +1,14 @@
 use essay_tensor::{init::linspace, tensor::Tensor};
 
-use crate::{artist::{Lines2d, LinesOpt}, chart::Chart};
+use crate::{artist::{Lines2d, LinesOpt}, chart::{Chart, IntoAxisData}};
 
 pub fn plot(
     chart: &mut Chart, 
-    x: impl Into<Tensor>, 
+    x: impl IntoAxisData, 
     y: impl Into<Tensor>, 
 ) -> LinesOpt {
+    let x = chart.x_values(x);
+
     let lines = Lines2d::from_xy(x, y);
 
     //self.artist(lines)
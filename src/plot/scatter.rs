@@ -8,17 +8,17 @@ use crate::{
     artist::{
         paths::{self}, Artist, ArtistDraw, ArtistView, Markers, PathCollection,
     }, 
-    chart::{Chart, Data, LegendHandler}, 
+    chart::{Chart, Data, IntoAxisData, LegendHandler}, 
     config::{ConfigArc, PathStyle},
     data_artist_option_struct, path_style_options, transform::ToCanvas 
 };
 
 pub fn scatter(
     graph: &mut Chart, 
-    x: impl Into<Tensor>, 
+    x: impl IntoAxisData, 
     y: impl Into<Tensor>, 
 ) -> ScatterOpt {
-    let x : Tensor = x.into();
+    let x : Tensor = graph.x_values(x);
     let y : Tensor = y.into();
 
     let plot = ScatterPlot::new(x.stack([y], -1));
@@ -32,6 +32,7 @@ pub struct ScatterPlot {
     is_stale: bool,
 
     style: PathStyle,
+    label: Option<String>,
 
     size: f32,
 
@@ -53,6 +54,7 @@ impl ScatterPlot {
         Self {
             xy,
             style,
+            label: None,
             size,
             marker: Markers::Circle,
             collection,
@@ -122,7 +124,18 @@ impl Artist<Data> for ScatterPlot {
     }
 
     fn get_legend(&self) -> Option<LegendHandler> {
-        None
+        match &self.label {
+            Some(label) => {
+                let path = self.marker.get_scaled_path(0.5 * self.size.sqrt());
+
+                let handler = LegendHandler::new(label.clone(), |_, _, _| Ok(()))
+                    .marker(path, self.style.clone())
+                    .points(self.xy.clone());
+
+                Some(handler)
+            }
+            None => None,
+        }
     }
 }
 
@@ -136,4 +149,12 @@ impl ScatterOpt {
 
         self
     }
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        let label = label.to_string();
+
+        self.write(|plot| plot.label = Some(label));
+
+        self
+    }
 }
@@ -0,0 +1,34 @@
+///
+/// Linearly interpolated percentile of sorted data, or NaN for no data.
+///
+pub(super) fn percentile(sorted: &[f32], p: f32) -> f32 {
+    if sorted.len() == 0 {
+        return f32::NAN;
+    }
+
+    let pos = p / 100. * (sorted.len() - 1) as f32;
+    let i = pos.floor() as usize;
+
+    if i + 1 >= sorted.len() {
+        sorted[sorted.len() - 1]
+    } else {
+        sorted[i] + (pos - i as f32) * (sorted[i + 1] - sorted[i])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::percentile;
+
+    #[test]
+    fn stats_percentile() {
+        let data = [1., 2., 3., 4.];
+
+        assert_eq!(percentile(&data, 0.), 1.);
+        assert_eq!(percentile(&data, 25.), 1.75);
+        assert_eq!(percentile(&data, 50.), 2.5);
+        assert_eq!(percentile(&data, 100.), 4.);
+        assert_eq!(percentile(&[5.], 25.), 5.);
+        assert!(percentile(&[], 50.).is_nan());
+    }
+}
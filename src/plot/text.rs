@@ -1,6 +1,6 @@
 use essay_graphics::api::Point;
 
-use crate::{artist::{TextOpt, Text}, chart::Chart};
+use crate::{artist::{Annotation, AnnotationOpt, TextOpt, Text}, chart::Chart};
 
 pub fn text(
     graph: &mut Chart, 
@@ -11,3 +11,29 @@ pub fn text(
 
     graph.artist(text)
 }
+
+pub fn annotate(
+    graph: &mut Chart,
+    text: impl AsRef<str>,
+    xy: impl Into<Point>,
+    xy_text: impl Into<Point>,
+) -> AnnotationOpt {
+    let annotation = Annotation::new(text, xy, xy_text);
+
+    graph.artist(annotation)
+}
+
+impl Chart {
+    ///
+    /// Labels the point `xy` with text at `xy_text` and an arrow from the
+    /// text to the point. Both default to data coordinates.
+    ///
+    pub fn annotate(
+        &mut self,
+        text: impl AsRef<str>,
+        xy: impl Into<Point>,
+        xy_text: impl Into<Point>,
+    ) -> AnnotationOpt {
+        annotate(self, text, xy, xy_text)
+    }
+}
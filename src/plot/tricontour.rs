@@ -1,13 +1,46 @@
 use essay_tensor::tensor::Tensor;
 
-use crate::{chart::Chart, artist::TriContour, tri::Triangulation};
+use crate::{chart::Chart, artist::{TriContour, TriContourOpt}, tri::Triangulation};
 
 pub fn tricontour(
     graph: &mut Chart, 
     tri: impl Into<Triangulation>,
     data: impl Into<Tensor>,
-) {
+) -> TriContourOpt {
     let tricontour = TriContour::new(tri, data);
     
-    graph.add_simple_artist(tricontour);
+    graph.artist(tricontour)
+}
+
+///
+/// Filled regions between consecutive contour levels of values on a
+/// triangulation's vertices.
+///
+pub fn tricontourf(
+    graph: &mut Chart, 
+    tri: impl Into<Triangulation>,
+    data: impl Into<Tensor>,
+) -> TriContourOpt {
+    let mut tricontour = TriContour::new(tri, data);
+    tricontour.filled(true);
+    
+    graph.artist(tricontour)
+}
+
+impl Chart {
+    pub fn tricontour(
+        &mut self,
+        tri: impl Into<Triangulation>,
+        data: impl Into<Tensor>
+    ) -> TriContourOpt {
+        tricontour(self, tri, data)
+    }
+
+    pub fn tricontourf(
+        &mut self,
+        tri: impl Into<Triangulation>,
+        data: impl Into<Tensor>
+    ) -> TriContourOpt {
+        tricontourf(self, tri, data)
+    }
 }
@@ -0,0 +1,23 @@
+use essay_tensor::tensor::Tensor;
+
+use crate::{chart::Chart, artist::{TriColor, TriColorOpt}, tri::Triangulation};
+
+pub fn tripcolor(
+    graph: &mut Chart, 
+    tri: impl Into<Triangulation>,
+    data: impl Into<Tensor>,
+) -> TriColorOpt {
+    let tripcolor = TriColor::new(tri, data);
+    
+    graph.artist(tripcolor)
+}
+
+impl Chart {
+    pub fn tripcolor(
+        &mut self,
+        tri: impl Into<Triangulation>,
+        data: impl Into<Tensor>
+    ) -> TriColorOpt {
+        tripcolor(self, tri, data)
+    }
+}
@@ -1,12 +1,10 @@
-use essay_tensor::tensor::Tensor;
-
-use crate::{chart::Chart, artist::TriPlot};
+use crate::{chart::Chart, artist::TriPlot, tri::Triangulation};
 
 pub fn triplot(
     graph: &mut Chart, 
-    data: impl Into<Tensor>,
+    tri: impl Into<Triangulation>,
 ) {
-    let triplot = TriPlot::new(data);
+    let triplot = TriPlot::new(tri);
     
     graph.add_simple_artist(triplot);
 }
@@ -0,0 +1,570 @@
+use std::cmp::Ordering;
+
+use essay_graphics::api::{
+    renderer::{Renderer, Result}, Bounds, Color, Path, PathCode, PathOpt, Point
+};
+use essay_tensor::tensor::{IntoTensorList, Tensor};
+
+use crate::{
+    artist::{paths, Artist, ArtistDraw, ArtistView},
+    chart::{Chart, Data, LegendHandler},
+    config::{ConfigArc, PathStyle},
+    data_artist_option_struct, palette::Palette, path_style_options, transform::ToCanvas
+};
+
+use super::stats::percentile;
+
+pub fn violin_plot(
+    chart: &mut Chart,
+    data: impl IntoTensorList<f32>,
+) -> ViolinPlotOpt {
+    let mut vec = Vec::new();
+    data.into_list(&mut vec);
+
+    let plot = ViolinPlot::new(vec);
+
+    chart.artist(plot)
+}
+
+///
+/// Kernel bandwidth rule for the violin's density estimate.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bandwidth {
+    Scott,
+    Silverman,
+    /// Bandwidth factor multiplied by the data's standard deviation
+    Factor(f32),
+}
+
+///
+/// Marks drawn inside each violin.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ViolinInner {
+    None,
+    Box,
+    Quartiles,
+}
+
+pub struct ViolinPlot {
+    data: Vec<Tensor>,
+
+    bandwidth: Bandwidth,
+    n_points: usize,
+    width: f32,
+    inner: ViolinInner,
+    is_split: bool,
+    is_vertical: bool,
+
+    colors: Option<Palette>,
+    style: PathStyle,
+    inner_style: PathStyle,
+
+    // item index and path of each violin or half violin
+    violins: Vec<(usize, Path<Data>)>,
+    inner_paths: Vec<Path<Data>>,
+    bounds: Bounds<Data>,
+
+    is_stale: bool,
+}
+
+impl ViolinPlot {
+    fn new(data: Vec<Tensor>) -> Self {
+        let mut inner_style = PathStyle::new();
+        inner_style.color("black");
+
+        Self {
+            data,
+
+            bandwidth: Bandwidth::Scott,
+            n_points: 100,
+            width: 0.5,
+            inner: ViolinInner::Box,
+            is_split: false,
+            is_vertical: true,
+
+            colors: None,
+            style: PathStyle::new(),
+            inner_style,
+
+            violins: Vec::new(),
+            inner_paths: Vec::new(),
+            bounds: Bounds::none(),
+
+            is_stale: true,
+        }
+    }
+
+    ///
+    /// Position and side of the i-th violin. Split violins pair up the
+    /// data, with even items on the left and odd items on the right.
+    ///
+    fn position(&self, i: usize) -> (f32, Side) {
+        if self.is_split {
+            let side = if i % 2 == 0 { Side::Left } else { Side::Right };
+
+            ((i / 2 + 1) as f32, side)
+        } else {
+            ((i + 1) as f32, Side::Both)
+        }
+    }
+
+    ///
+    /// Face color of the i-th item. Split violins color by side, so the
+    /// two compared groups differ even without `colors`.
+    ///
+    fn face_color(&self, i: usize) -> Option<Color> {
+        match (&self.colors, self.is_split) {
+            (Some(colors), true) => Some(colors.color(i % 2, 2)),
+            (None, true) => Some(Palette::default().color(i % 2, 2)),
+            (Some(colors), false) => Some(colors.color(i, self.data.len())),
+            (None, false) => None,
+        }
+    }
+
+    fn update(&mut self) {
+        if ! self.is_stale {
+            return;
+        }
+
+        self.is_stale = false;
+
+        let mut violins = Vec::new();
+        let mut inner_paths = Vec::new();
+
+        let (mut x_min, mut x_max) = (f32::MAX, f32::MIN);
+        let (mut y_min, mut y_max) = (f32::MAX, f32::MIN);
+
+        for (i, item) in self.data.iter().enumerate() {
+            let mut values = Vec::from(item.as_slice());
+            values.retain(|v| v.is_finite());
+
+            if values.len() == 0 {
+                continue;
+            }
+
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+            let (x, side) = self.position(i);
+
+            let kde = Kde::new(&values, self.bandwidth, self.n_points);
+            let half = 0.5 * self.width / kde.max_density();
+
+            violins.push((i, self.violin_path(x, side, &kde, half)));
+
+            match self.inner {
+                ViolinInner::None => {},
+                ViolinInner::Box => {
+                    let w = 0.03 * self.width;
+
+                    let (q1, q3) = (percentile(&values, 25.), percentile(&values, 75.));
+                    inner_paths.push(self.rect(x - w, q1, x + w, q3));
+
+                    let median = percentile(&values, 50.);
+                    inner_paths.push(self.line(x - 2. * w, median, x + 2. * w, median));
+                }
+                ViolinInner::Quartiles => {
+                    for p in [25., 50., 75.] {
+                        let y = percentile(&values, p);
+                        let d = half * kde.density_at(y);
+
+                        let (x0, x1) = match side {
+                            Side::Both => (x - d, x + d),
+                            Side::Left => (x - d, x),
+                            Side::Right => (x, x + d),
+                        };
+
+                        inner_paths.push(self.line(x0, y, x1, y));
+                    }
+                }
+            }
+
+            x_min = x_min.min(x - 0.5 * self.width);
+            x_max = x_max.max(x + 0.5 * self.width);
+            y_min = y_min.min(values[0]);
+            y_max = y_max.max(values[values.len() - 1]);
+        }
+
+        self.bounds = if violins.len() == 0 {
+            Bounds::none()
+        } else if self.is_vertical {
+            Bounds::new(Point(x_min, y_min), Point(x_max, y_max))
+        } else {
+            Bounds::new(Point(y_min, x_min), Point(y_max, x_max))
+        };
+
+        self.violins = violins;
+        self.inner_paths = inner_paths;
+    }
+
+    fn violin_path(&self, x: f32, side: Side, kde: &Kde, half: f32) -> Path<Data> {
+        let mut points = Vec::<Point>::new();
+
+        // right side going up, then left side going down
+        match side {
+            Side::Both | Side::Right => {
+                for (y, d) in kde.points() {
+                    points.push(Point(x + half * d, y));
+                }
+            }
+            Side::Left => {
+                points.push(Point(x, kde.y_min()));
+                points.push(Point(x, kde.y_max()));
+            }
+        }
+
+        match side {
+            Side::Both | Side::Left => {
+                for (y, d) in kde.points().rev() {
+                    points.push(Point(x - half * d, y));
+                }
+            }
+            Side::Right => {
+                points.push(Point(x, kde.y_max()));
+                points.push(Point(x, kde.y_min()));
+            }
+        }
+
+        let mut codes = Vec::<PathCode>::new();
+        let n = points.len();
+
+        for (i, point) in points.into_iter().enumerate() {
+            let point = self.orient(point);
+
+            if i == 0 {
+                codes.push(PathCode::MoveTo(point));
+            } else if i == n - 1 {
+                codes.push(PathCode::ClosePoly(point));
+            } else {
+                codes.push(PathCode::LineTo(point));
+            }
+        }
+
+        Path::new(codes)
+    }
+
+    fn orient(&self, point: Point) -> Point {
+        if self.is_vertical {
+            point
+        } else {
+            Point(point.1, point.0)
+        }
+    }
+
+    fn line(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> Path<Data> {
+        Path::new(vec![
+            PathCode::MoveTo(self.orient(Point(x0, y0))),
+            PathCode::LineTo(self.orient(Point(x1, y1))),
+        ])
+    }
+
+    fn rect(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> Path<Data> {
+        paths::rect(self.orient(Point(x0, y0)), self.orient(Point(x1, y1)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Both,
+    Left,
+    Right,
+}
+
+///
+/// Gaussian kernel density estimate sampled between the data's extremes.
+///
+struct Kde {
+    y: Vec<f32>,
+    density: Vec<f32>,
+}
+
+impl Kde {
+    fn new(sorted: &[f32], bandwidth: Bandwidth, n_points: usize) -> Self {
+        let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+
+        let y: Vec<f32> = if max > min {
+            (0..n_points).map(|i| {
+                min + (max - min) * i as f32 / (n_points - 1) as f32
+            }).collect()
+        } else {
+            vec![min, max]
+        };
+
+        let h = kde_bandwidth(sorted, bandwidth);
+
+        let density = y.iter().map(|y| gaussian_kde(sorted, h, *y)).collect();
+
+        Self {
+            y,
+            density,
+        }
+    }
+
+    fn points(&self) -> impl DoubleEndedIterator<Item=(f32, f32)> + '_ {
+        self.y.iter().zip(self.density.iter()).map(|(y, d)| (*y, *d))
+    }
+
+    fn y_min(&self) -> f32 {
+        self.y[0]
+    }
+
+    fn y_max(&self) -> f32 {
+        self.y[self.y.len() - 1]
+    }
+
+    fn max_density(&self) -> f32 {
+        let max = self.density.iter().fold(0., |a: f32, b| a.max(*b));
+
+        if max > 0. { max } else { 1. }
+    }
+
+    ///
+    /// Density at `y` interpolated between the samples.
+    ///
+    fn density_at(&self, y: f32) -> f32 {
+        let n = self.y.len();
+
+        if y <= self.y[0] {
+            return self.density[0];
+        } else if self.y[n - 1] <= y {
+            return self.density[n - 1];
+        }
+
+        let i = self.y.partition_point(|v| *v <= y).max(1);
+        let (y0, y1) = (self.y[i - 1], self.y[i]);
+        let t = (y - y0) / (y1 - y0);
+
+        self.density[i - 1] + t * (self.density[i] - self.density[i - 1])
+    }
+}
+
+///
+/// Kernel standard deviation, the rule's factor times the data's
+/// standard deviation.
+///
+fn kde_bandwidth(data: &[f32], bandwidth: Bandwidth) -> f32 {
+    let n = data.len() as f32;
+
+    let factor = match bandwidth {
+        Bandwidth::Scott => n.powf(-0.2),
+        Bandwidth::Silverman => (n * 0.75).powf(-0.2),
+        Bandwidth::Factor(factor) => factor,
+    };
+
+    let mean = data.iter().sum::<f32>() / n;
+    let var = data.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / (n - 1.).max(1.);
+
+    let h = factor * var.sqrt();
+
+    // constant data still gets a visible shape
+    if h > 0. { h } else { factor.max(1e-3) }
+}
+
+fn gaussian_kde(data: &[f32], h: f32, y: f32) -> f32 {
+    let norm = 1. / (data.len() as f32 * h * (2. * std::f32::consts::PI).sqrt());
+
+    data.iter().map(|v| {
+        let z = (y - v) / h;
+
+        (-0.5 * z * z).exp()
+    }).sum::<f32>() * norm
+}
+
+impl ArtistDraw<Data> for ViolinPlot {
+    fn bounds(&mut self) -> Bounds<Data> {
+        self.update();
+
+        self.bounds.clone()
+    }
+
+    fn draw(
+        &mut self,
+        ui: &mut dyn Renderer,
+        to_canvas: &ToCanvas<Data>,
+        style: &dyn PathOpt,
+    ) -> Result<()> {
+        self.update();
+
+        let style = self.style.push(style);
+
+        for (i, violin) in &self.violins {
+            let mut violin_style = PathStyle::new();
+
+            if let Some(color) = self.face_color(*i) {
+                violin_style.face_color(color);
+            }
+
+            ui.draw_path(&to_canvas.transform_path(violin), &violin_style.push(&style))?;
+        }
+
+        let inner_style = self.inner_style.push(&style);
+
+        for path in &self.inner_paths {
+            ui.draw_path(&to_canvas.transform_path(path), &inner_style)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Artist<Data> for ViolinPlot {
+    type Opt = ViolinPlotOpt;
+
+    fn config(&mut self, cfg: &ConfigArc) {
+        self.style = PathStyle::from_config(cfg, "violin_plot");
+    }
+
+    fn opt(&mut self, view: ArtistView<Data, ViolinPlot>) -> Self::Opt {
+        ViolinPlotOpt::new(view)
+    }
+
+    fn get_legend(&self) -> Option<LegendHandler> {
+        None
+    }
+}
+
+data_artist_option_struct!(ViolinPlotOpt, ViolinPlot);
+
+impl ViolinPlotOpt {
+    path_style_options!(style);
+
+    pub fn data(&mut self, data: impl IntoTensorList<f32>) -> &mut Self {
+        self.write(|plot| {
+            let mut vec = Vec::new();
+            data.into_list(&mut vec);
+            plot.data = vec;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn bandwidth(&mut self, bandwidth: Bandwidth) -> &mut Self {
+        self.write(|plot| {
+            plot.bandwidth = bandwidth;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Maximum violin width in data units.
+    ///
+    pub fn width(&mut self, width: f32) -> &mut Self {
+        self.write(|plot| {
+            plot.width = width;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Number of density samples along each violin.
+    ///
+    pub fn n_points(&mut self, n_points: usize) -> &mut Self {
+        assert!(n_points >= 2, "violin plot needs at least 2 points");
+
+        self.write(|plot| {
+            plot.n_points = n_points;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn inner(&mut self, inner: ViolinInner) -> &mut Self {
+        self.write(|plot| {
+            plot.inner = inner;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    ///
+    /// Violin face colors, one palette color per violin, or one per side
+    /// for split violins.
+    ///
+    pub fn colors(&mut self, colors: impl Into<Palette>) -> &mut Self {
+        self.write(|plot| {
+            plot.colors = Some(colors.into());
+        });
+
+        self
+    }
+
+    ///
+    /// Draws pairs of data items as the two halves of one violin, each
+    /// side in its own color.
+    ///
+    pub fn split(&mut self, is_split: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_split = is_split;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+
+    pub fn vert(&mut self, is_vertical: bool) -> &mut Self {
+        self.write(|plot| {
+            plot.is_vertical = is_vertical;
+            plot.is_stale = true;
+        });
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use essay_tensor::ten;
+
+    use super::{gaussian_kde, kde_bandwidth, Bandwidth, Kde, ViolinPlot};
+
+    #[test]
+    fn violin_bandwidth() {
+        let data = [-1., 1.];
+        let std = 2f32.sqrt();
+
+        assert!((kde_bandwidth(&data, Bandwidth::Scott) - 2f32.powf(-0.2) * std).abs() < 1e-5);
+        assert!((kde_bandwidth(&data, Bandwidth::Silverman) - 1.5f32.powf(-0.2) * std).abs() < 1e-5);
+        assert!((kde_bandwidth(&data, Bandwidth::Factor(0.5)) - 0.5 * std).abs() < 1e-5);
+    }
+
+    #[test]
+    fn violin_kde() {
+        // single point is a unit gaussian
+        let peak = 1. / (2. * std::f32::consts::PI).sqrt();
+        assert!((gaussian_kde(&[0.], 1., 0.) - peak).abs() < 1e-6);
+        assert!((gaussian_kde(&[0.], 1., 1.) - peak * (-0.5f32).exp()).abs() < 1e-6);
+
+        let kde = Kde::new(&[0., 1., 2.], Bandwidth::Scott, 5);
+        assert_eq!(kde.y_min(), 0.);
+        assert_eq!(kde.y_max(), 2.);
+
+        // symmetric data has a symmetric density
+        assert!((kde.density_at(0.5) - kde.density_at(1.5)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn violin_split_colors() {
+        let mut plot = ViolinPlot::new(vec![ten![1., 2., 3.], ten![2., 3., 4.]]);
+        plot.is_split = true;
+        plot.update();
+
+        // both halves of the one violin, in different colors
+        assert_eq!(plot.violins.len(), 2);
+
+        let (left, right) = (plot.violins[0].0, plot.violins[1].0);
+        assert!(plot.face_color(left).is_some());
+        assert!(plot.face_color(left) != plot.face_color(right));
+
+        plot.colors = Some((&["pink", "lightblue"]).into());
+        assert!(plot.face_color(left) == Some("pink".into()));
+        assert!(plot.face_color(right) == Some("lightblue".into()));
+    }
+}
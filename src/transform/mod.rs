@@ -3,7 +3,7 @@ use std::{f32::consts::TAU, marker::PhantomData};
 use essay_graphics::api::{renderer::Canvas, Affine2d, Bounds, Coord, Path, Point};
 use essay_tensor::tensor::Tensor;
 
-use crate::{artist::Stale, chart::Data};
+use crate::{artist::Stale, chart::{Data, Scale}};
 
 pub trait Transform<M: Coord> {
     fn transform_point(&self, point: Point) -> Point;
@@ -21,6 +21,8 @@ pub struct CartesianTransform<M: Coord> {
     fy: f32,
     tx: f32,
     ty: f32,
+    x_scale: Scale,
+    y_scale: Scale,
     marker: PhantomData<fn(M)>,
 }
 
@@ -37,6 +39,8 @@ impl<M: Coord> CartesianTransform<M> {
             ty: to.1,
             sx: scale[0],
             sy: scale[0],
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
             marker: Default::default(),
         }
     }
@@ -55,14 +59,45 @@ impl<M: Coord> CartesianTransform<M> {
             ty: dst.ymin(),
             sx: dst.width() / src_w,
             sy: dst.height() / src_h,
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
+            marker: Default::default(),
+        }
+    }
+
+    ///
+    /// Maps src to dst after applying a per-axis scale, such as log10,
+    /// to the source coordinates.
+    ///
+    pub fn scaled_bounds_to(
+        src: Bounds<M>,
+        dst: Bounds<Canvas>,
+        x_scale: Scale,
+        y_scale: Scale,
+    ) -> Self {
+        let (x0, x1) = (x_scale.forward(src.xmin()), x_scale.forward(src.xmax()));
+        let (y0, y1) = (y_scale.forward(src.ymin()), y_scale.forward(src.ymax()));
+
+        let src_w = if x1 == x0 { f32::EPSILON } else { x1 - x0 };
+        let src_h = if y1 == y0 { f32::EPSILON } else { y1 - y0 };
+
+        Self {
+            fx: x0,
+            fy: y0,
+            tx: dst.xmin(),
+            ty: dst.ymin(),
+            sx: dst.width() / src_w,
+            sy: dst.height() / src_h,
+            x_scale,
+            y_scale,
             marker: Default::default(),
         }
     }
 
     fn transform(&self, x: f32, y: f32) -> [f32; 2] {
         [
-            self.tx + self.sx * (x - self.fx),
-            self.ty + self.sy * (y - self.fy),
+            self.tx + self.sx * (self.x_scale.forward(x) - self.fx),
+            self.ty + self.sy * (self.y_scale.forward(y) - self.fy),
         ]
     }
 }
@@ -97,6 +132,8 @@ impl<M: Coord> Default for CartesianTransform<M> {
             fy: 0.,
             tx: 0.,
             ty: 0.,
+            x_scale: Scale::Linear,
+            y_scale: Scale::Linear,
             marker: Default::default(),
         }
     }
@@ -230,6 +267,7 @@ pub struct ToCanvas<'a, M: Coord> {
     id: Stale,
     bounds: Bounds<M>,
     transform: &'a dyn Transform<M>,
+    figure: Bounds<Canvas>,
 }
 
 impl<'a, M: Coord> ToCanvas<'a, M> {
@@ -242,9 +280,20 @@ impl<'a, M: Coord> ToCanvas<'a, M> {
             id: stale,
             bounds,
             transform,
+            figure: Bounds::none(),
         }
     }
 
+    ///
+    /// Sets the canvas bounds of the whole figure, for positions relative
+    /// to the figure rather than the chart.
+    ///
+    pub fn figure(mut self, figure: Bounds<Canvas>) -> Self {
+        self.figure = figure;
+
+        self
+    }
+
     #[inline]
     pub fn stale(&self) -> Stale {
         self.id
@@ -255,6 +304,14 @@ impl<'a, M: Coord> ToCanvas<'a, M> {
         self.bounds
     }
 
+    ///
+    /// The figure's canvas bounds, or none when the frame didn't set them.
+    ///
+    #[inline]
+    pub fn figure_bounds(&self) -> Bounds<Canvas> {
+        self.figure
+    }
+
     #[inline]
     pub fn get_transform(&self) -> &dyn Transform<M> {
         self.transform
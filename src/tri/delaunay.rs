@@ -357,11 +357,26 @@ impl TriDelaunay {
     }
 }
 
+///
+/// Boundary triangles flatter than this ratio of inscribed to circumscribed
+/// circle radius are masked by `triangulate`.
+///
+const MIN_CIRCLE_RATIO: f32 = 0.01;
+
 pub fn triangulate(points: &Tensor) -> Triangulation {
     let mut tri = TriDelaunay::new(points);
     tri.build();
     // tri.remove_ext_triangle();
-    tri.to_triangulation()
+    let mut triangulation = tri.to_triangulation();
+
+    // the convex hull leaves slivers along nearly straight boundaries
+    let mask = triangulation.flat_tri_mask(MIN_CIRCLE_RATIO);
+
+    if mask.iter().any(|is_masked| *is_masked) {
+        triangulation.set_mask(Tensor::from(mask));
+    }
+
+    triangulation
 }
 
 fn initial_points(points: &Tensor) -> Vec<Point> {
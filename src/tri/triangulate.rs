@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
 use essay_tensor::tensor::Tensor;
 
 use super::triangulate;
 
+#[derive(Clone)]
 pub struct Triangulation {
     xy: Tensor,
     triangles: Tensor<usize>,
+    mask: Option<Tensor<bool>>,
 }
 
 impl Triangulation {
@@ -20,6 +24,41 @@ impl Triangulation {
         Self {
             xy,
             triangles,
+            mask: None,
+        }
+    }
+
+    ///
+    /// Hides triangles where the mask is true from plots and contours.
+    ///
+    pub fn set_mask(&mut self, mask: impl Into<Tensor<bool>>) -> &mut Self {
+        let mask = mask.into();
+
+        assert_eq!(
+            mask.len(), self.triangles.rows(),
+            "mask length must match the number of triangles"
+        );
+
+        self.mask = Some(mask);
+
+        self
+    }
+
+    pub fn clear_mask(&mut self) -> &mut Self {
+        self.mask = None;
+
+        self
+    }
+
+    pub fn mask(&self) -> Option<&Tensor<bool>> {
+        self.mask.as_ref()
+    }
+
+    #[inline]
+    pub fn is_masked(&self, triangle: usize) -> bool {
+        match &self.mask {
+            Some(mask) => mask.as_slice()[triangle],
+            None => false,
         }
     }
 
@@ -34,7 +73,11 @@ impl Triangulation {
     pub fn edges(&self) -> Tensor<usize> {
         let mut edges = Vec::<[usize; 2]>::new();
 
-        for triangle in self.triangles.iter_row() {
+        for (i, triangle) in self.triangles.iter_row().enumerate() {
+            if self.is_masked(i) {
+                continue;
+            }
+
             let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
 
             if a < b {
@@ -58,6 +101,91 @@ impl Triangulation {
 
         Tensor::from(edges)
     }
+
+    ///
+    /// Mask of nearly flat triangles on the boundary, whose ratio of
+    /// inscribed to circumscribed circle radius is below `min_circle_ratio`.
+    /// Masking repeats inward while newly exposed boundary triangles are
+    /// also flat. Existing masked triangles stay masked.
+    ///
+    pub fn flat_tri_mask(&self, min_circle_ratio: f32) -> Vec<bool> {
+        let tris: Vec<[usize; 3]> = self.triangles.iter_row()
+            .map(|t| [t[0], t[1], t[2]])
+            .collect();
+
+        let xy: Vec<[f32; 2]> = self.xy.iter_row()
+            .map(|p| [p[0], p[1]])
+            .collect();
+
+        let mut mask: Vec<bool> = (0..tris.len()).map(|i| self.is_masked(i)).collect();
+
+        flat_boundary_mask(&xy, &tris, &mut mask, min_circle_ratio);
+
+        mask
+    }
+}
+
+///
+/// Ratio of the inscribed to the circumscribed circle radius, 0.5 for an
+/// equilateral triangle and 0 for a degenerate one.
+///
+fn circle_ratio(a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> f32 {
+    let ab = (b[0] - a[0]).hypot(b[1] - a[1]);
+    let bc = (c[0] - b[0]).hypot(c[1] - b[1]);
+    let ca = (a[0] - c[0]).hypot(a[1] - c[1]);
+
+    let area = 0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs();
+
+    let denom = ab * bc * ca * (ab + bc + ca);
+
+    if denom > 0. {
+        8. * area * area / denom
+    } else {
+        0.
+    }
+}
+
+fn flat_boundary_mask(
+    xy: &[[f32; 2]],
+    tris: &[[usize; 3]],
+    mask: &mut [bool],
+    min_circle_ratio: f32,
+) {
+    let mut edge_tris = HashMap::<(usize, usize), Vec<usize>>::new();
+
+    for (i, [a, b, c]) in tris.iter().enumerate() {
+        for (v0, v1) in [(*a, *b), (*b, *c), (*c, *a)] {
+            edge_tris.entry((v0.min(v1), v0.max(v1))).or_default().push(i);
+        }
+    }
+
+    let is_flat: Vec<bool> = tris.iter()
+        .map(|[a, b, c]| circle_ratio(xy[*a], xy[*b], xy[*c]) < min_circle_ratio)
+        .collect();
+
+    loop {
+        let mut is_changed = false;
+
+        for (i, [a, b, c]) in tris.iter().enumerate() {
+            if mask[i] || ! is_flat[i] {
+                continue;
+            }
+
+            // boundary when an edge has no unmasked neighbor
+            let is_boundary = [(*a, *b), (*b, *c), (*c, *a)].iter().any(|(v0, v1)| {
+                ! edge_tris[&(*v0.min(v1), *v0.max(v1))].iter().any(|t| *t != i && ! mask[*t])
+            });
+
+            if is_boundary {
+                mask[i] = true;
+                is_changed = true;
+            }
+        }
+
+        if ! is_changed {
+            break;
+        }
+    }
 }
 
 impl From<Tensor> for Triangulation {
@@ -70,4 +198,35 @@ impl From<&Tensor> for Triangulation {
     fn from(value: &Tensor) -> Self {
         triangulate(value)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use super::{circle_ratio, flat_boundary_mask};
+
+    #[test]
+    fn tri_circle_ratio() {
+        let h = 3f32.sqrt() / 2.;
+
+        assert!((circle_ratio([0., 0.], [1., 0.], [0.5, h]) - 0.5).abs() < 1e-5);
+        assert_eq!(circle_ratio([0., 0.], [1., 0.], [2., 0.]), 0.);
+    }
+
+    #[test]
+    fn tri_flat_boundary_mask() {
+        // square split into two triangles, with a sliver on the bottom edge
+        let xy = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, -0.001]];
+        let tris = [[0, 1, 2], [0, 2, 3], [0, 4, 1]];
+
+        let mut mask = vec![false; 3];
+        flat_boundary_mask(&xy, &tris, &mut mask, 0.01);
+        assert_eq!(mask, vec![false, false, true]);
+
+        // the sliver is interior when its neighbor is unmasked
+        let xy = [[0., 0.], [1., 0.], [0.5, 0.001], [0.5, 1.], [0.5, -1.]];
+        let tris = [[0, 1, 2], [0, 2, 3], [2, 1, 3], [0, 4, 1]];
+
+        let mut mask = vec![false; 4];
+        flat_boundary_mask(&xy, &tris, &mut mask, 0.01);
+        assert_eq!(mask, vec![false, false, false, false]);
+    }
+}